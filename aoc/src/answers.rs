@@ -0,0 +1,305 @@
+use std::{collections::BTreeMap, str::FromStr, time::Duration};
+
+use toml::{
+    de::{DeTable, DeValue},
+    Spanned,
+};
+use utils::{parse_number, ParseError};
+
+/// The known-correct answers of one input, e.g. the `[day01.puzzle]` section for `day01/puzzle.txt`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// How long solving took when the answers were recorded
+    pub time: Option<Duration>,
+    /// Answers the website rejected, which are not submitted again
+    pub part1_wrong: Vec<String>,
+    pub part2_wrong: Vec<String>,
+}
+
+/// The contents of `answers.toml`, keyed by day and input name.
+#[derive(Debug, Default)]
+pub struct Registry {
+    entries: BTreeMap<(usize, String), Expected>,
+}
+
+impl Registry {
+    pub fn get(&self, day: usize, input: &str) -> Option<&Expected> {
+        self.entries.get(&(day, input.to_string()))
+    }
+
+    /// The names of all inputs with recorded answers for the given day.
+    pub fn inputs(&self, day: usize) -> impl Iterator<Item = &str> {
+        self.entries
+            .keys()
+            .filter(move |(d, _)| *d == day)
+            .map(|(_, input)| input.as_str())
+    }
+}
+
+// `[dayNN.input]` sections holding `part1`, `part2`, `time_ms` and the lists `part1_wrong` and
+// `part2_wrong`, with strings or numbers as values
+impl FromStr for Registry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = DeTable::parse(s).map_err(|e| ParseError::toml(s, &e))?;
+        let mut entries = BTreeMap::new();
+        for (key, inputs) in table.get_ref() {
+            let section = &s[key.span()];
+            let inputs = inputs
+                .get_ref()
+                .as_table()
+                .ok_or_else(|| ParseError::new(s, section, "a section like [day01.puzzle]"))?;
+            let day = section
+                .strip_prefix("day")
+                .and_then(|day| day.parse().ok())
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| ParseError::new(s, section, "a day between day01 and day25"))?;
+
+            for (input, values) in inputs {
+                let values = values.get_ref().as_table().ok_or_else(|| {
+                    ParseError::new(s, &s[input.span()], "a section like [day01.puzzle]")
+                })?;
+                let mut expected = Expected::default();
+                for (key, value) in values {
+                    match key.get_ref().as_ref() {
+                        "part1" => expected.part1 = Some(parse_answer(s, value)?),
+                        "part2" => expected.part2 = Some(parse_answer(s, value)?),
+                        "time_ms" => expected.time = Some(parse_millis(s, value)?),
+                        "part1_wrong" => expected.part1_wrong = parse_list(s, value)?,
+                        "part2_wrong" => expected.part2_wrong = parse_list(s, value)?,
+                        _ => {
+                            return Err(ParseError::new(
+                                s,
+                                &s[key.span()],
+                                "part1, part2, time_ms, part1_wrong or part2_wrong",
+                            ))
+                        }
+                    }
+                }
+                entries.insert((day, input.to_string()), expected);
+            }
+        }
+
+        Ok(Registry { entries })
+    }
+}
+
+// Answers are either integers or strings, the latter may contain \n for multi-line answers
+fn parse_answer(source: &str, value: &Spanned<DeValue>) -> Result<String, ParseError> {
+    let text = &source[value.span()];
+    match value.get_ref() {
+        DeValue::String(answer) => Ok(answer.to_string()),
+        DeValue::Integer(number) => i64::from_str_radix(number.as_str(), number.radix())
+            .map(|number| number.to_string())
+            .map_err(|_| ParseError::new(source, text, "an integer that fits into 64 bits")),
+        _ => Err(ParseError::new(source, text, "an integer or a string")),
+    }
+}
+
+// A list like ["CMZ", 42], whose items are answers
+fn parse_list(source: &str, value: &Spanned<DeValue>) -> Result<Vec<String>, ParseError> {
+    let text = &source[value.span()];
+    value
+        .get_ref()
+        .as_array()
+        .ok_or_else(|| ParseError::new(source, text, "a list like [\"CMZ\", 42]"))?
+        .iter()
+        .map(|item| parse_answer(source, item))
+        .collect()
+}
+
+fn parse_millis(source: &str, value: &Spanned<DeValue>) -> Result<Duration, ParseError> {
+    let text = &source[value.span()];
+    let millis: f64 = match value.get_ref() {
+        DeValue::Integer(number) if number.radix() == 10 => parse_number(source, number.as_str())?,
+        DeValue::Float(number) => parse_number(source, number.as_str())?,
+        _ => f64::NAN,
+    };
+    if !millis.is_finite() || millis < 0.0 {
+        return Err(ParseError::new(
+            source,
+            text,
+            "a positive number of milliseconds",
+        ));
+    }
+    Ok(Duration::from_secs_f64(millis / 1000.0))
+}
+
+/// Sets `key` in the `[dayNN.input]` section of answers.toml to `value`, which is already
+/// rendered as TOML, and keeps the rest of the file as it is, comments included. Missing
+/// sections are appended.
+pub fn record(text: &str, day: usize, input: &str, key: &str, value: &str) -> String {
+    let entry = format!("{} = {}", key, value);
+    let table = DeTable::parse(text).ok();
+    let section = table.as_ref().and_then(|table| {
+        let (_, inputs) = table
+            .get_ref()
+            .iter()
+            .find(|(name, _)| *name.get_ref() == format!("day{:02}", day))?;
+        let (_, values) = inputs
+            .get_ref()
+            .as_table()?
+            .iter()
+            .find(|(name, _)| *name.get_ref() == input)?;
+        Some((values.span(), values.get_ref().as_table()?))
+    });
+    let Some((header, values)) = section else {
+        let text = text.trim_end();
+        let separator = if text.is_empty() { "" } else { "\n\n" };
+        return format!(
+            "{}{}[day{:02}.{}]\n{}\n",
+            text, separator, day, input, entry
+        );
+    };
+
+    if let Some((name, old)) = values.iter().find(|(name, _)| *name.get_ref() == key) {
+        return format!(
+            "{}{}{}",
+            &text[..name.span().start],
+            entry,
+            &text[old.span().end..]
+        );
+    }
+    // After the line of the last value of the section, or of its header
+    let last = values
+        .iter()
+        .map(|(_, value)| value.span().end)
+        .fold(header.end, usize::max);
+    let end = text[last..].find('\n').map_or(text.len(), |i| last + i);
+    let rest = if end == text.len() {
+        "\n"
+    } else {
+        &text[end..]
+    };
+    format!("{}\n{}{}", &text[..end], entry, rest)
+}
+
+/// Renders a list of answers as a TOML value.
+pub fn list_to_toml(answers: &[String]) -> String {
+    let items: Vec<String> = answers.iter().map(|a| to_toml(a)).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// Renders an answer as a TOML value, so that it can be pasted into answers.toml.
+pub fn to_toml(answer: &str) -> String {
+    if answer.parse::<i64>().is_ok() {
+        return answer.to_string();
+    }
+    let escaped = answer
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sections() {
+        let registry: Registry = "# Known answers
+[day01.puzzle]
+part1 = 67633
+part2 = \"199628\" # a comment
+time_ms = 0.25
+
+[day10.simple_puzzle]
+part2 = \"##..\\n#\\\"#\"
+"
+        .parse()
+        .unwrap();
+
+        assert_eq!(
+            registry.get(1, "puzzle"),
+            Some(&Expected {
+                part1: Some(String::from("67633")),
+                part2: Some(String::from("199628")),
+                time: Some(Duration::from_micros(250)),
+                ..Expected::default()
+            })
+        );
+        let day10 = registry.get(10, "simple_puzzle").unwrap();
+        assert_eq!(day10.part1, None);
+        assert_eq!(day10.part2.as_deref(), Some("##..\n#\"#"));
+        assert_eq!(registry.inputs(10).collect::<Vec<_>>(), ["simple_puzzle"]);
+        assert_eq!(registry.get(2, "puzzle"), None);
+    }
+
+    #[test]
+    fn points_at_errors() {
+        let error = "[day01.puzzle]\npart1 = 1\npart3 = 2"
+            .parse::<Registry>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (3, 1));
+        assert_eq!(error.text, "part3");
+
+        let error = "[day26.puzzle]".parse::<Registry>().unwrap_err();
+        assert_eq!(error.expected, "a day between day01 and day25");
+
+        let error = "part1 = 1".parse::<Registry>().unwrap_err();
+        assert_eq!(error.expected, "a section like [day01.puzzle]");
+
+        let error = "[day01.puzzle]\npart1 = 1__0"
+            .parse::<Registry>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 10));
+    }
+
+    #[test]
+    fn parses_wrong_answers() {
+        let registry: Registry = "[day05.puzzle]\npart1_wrong = [\"A,B\", 3]\npart2_wrong = []"
+            .parse()
+            .unwrap();
+        let day05 = registry.get(5, "puzzle").unwrap();
+        assert_eq!(day05.part1_wrong, ["A,B", "3"]);
+        assert!(day05.part2_wrong.is_empty());
+
+        let error = "[day05.puzzle]\npart1_wrong = 3"
+            .parse::<Registry>()
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (2, 15));
+    }
+
+    #[test]
+    fn records_answers() {
+        let text = "# Answers\n[day01.puzzle]\npart1 = 1 # old\n\n[day02.puzzle]\npart1 = 2\n";
+        assert_eq!(
+            record(text, 1, "puzzle", "part1", "3"),
+            "# Answers\n[day01.puzzle]\npart1 = 3 # old\n\n[day02.puzzle]\npart1 = 2\n"
+        );
+        assert_eq!(
+            record(text, 1, "puzzle", "part2_wrong", "[4]"),
+            "# Answers\n[day01.puzzle]\npart1 = 1 # old\npart2_wrong = [4]\n\n\
+             [day02.puzzle]\npart1 = 2\n"
+        );
+        assert_eq!(
+            record(text, 3, "puzzle", "part1", "\"CMZ\""),
+            format!("{}\n[day03.puzzle]\npart1 = \"CMZ\"\n", text)
+        );
+        assert_eq!(
+            record("", 3, "puzzle", "part1", "5"),
+            "[day03.puzzle]\npart1 = 5\n"
+        );
+
+        let wrong = vec![String::from("1"), String::from("a\"b")];
+        let text = record(text, 2, "puzzle", "part1_wrong", &list_to_toml(&wrong));
+        let registry: Registry = text.parse().unwrap();
+        assert_eq!(registry.get(2, "puzzle").unwrap().part1_wrong, wrong);
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        for answer in ["42", "-3", "CMZ", "#.\n.#", "a \"quoted\" \\"] {
+            let toml = format!("[day01.puzzle]\npart1 = {}", to_toml(answer));
+            let registry: Registry = toml.parse().unwrap();
+            assert_eq!(
+                registry.get(1, "puzzle").unwrap().part1.as_deref(),
+                Some(answer)
+            );
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::Path,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use utils::{parse_lines, parse_number, Config, ParseError, Timings};
+
+use crate::{default_input, run_day, table::Table, Outcome};
+
+const HEADER: &str = "timestamp,day,parse_ms,part1_ms,part2_ms";
+
+/// One line of the benchmark history: the fastest timings of a day in one benchmark run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    /// Seconds since the unix epoch
+    pub timestamp: u64,
+    pub day: usize,
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl Entry {
+    fn total(&self) -> Duration {
+        self.parse + self.part1 + self.part2
+    }
+}
+
+impl FromStr for Entry {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [timestamp, day, parse, part1, part2] = s.split(',').collect::<Vec<_>>()[..] else {
+            return Err(ParseError::new(s, s, HEADER));
+        };
+        let millis = |field: &str| -> Result<Duration, ParseError> {
+            let millis: f64 = parse_number(s, field)?;
+            if !millis.is_finite() || millis < 0.0 {
+                return Err(ParseError::new(
+                    s,
+                    field,
+                    "a positive number of milliseconds",
+                ));
+            }
+            Ok(Duration::from_secs_f64(millis / 1000.0))
+        };
+
+        Ok(Entry {
+            timestamp: parse_number(s, timestamp)?,
+            day: parse_number(s, day)?,
+            parse: millis(parse)?,
+            part1: millis(part1)?,
+            part2: millis(part2)?,
+        })
+    }
+}
+
+/// Reads the benchmark history written by earlier runs.
+pub fn load_history(text: &str) -> Result<Vec<Entry>, ParseError> {
+    let Some(entries) = text.strip_prefix(HEADER) else {
+        return match text.lines().next() {
+            Some(first) => Err(ParseError::new(text, first, HEADER)),
+            None => Ok(Vec::new()),
+        };
+    };
+    parse_lines(entries.trim_start_matches(['\r', '\n'])).map_err(|e| e.shifted(1))
+}
+
+/// Times every day on its puzzle.txt, compares with the latest entry of the day in the
+/// history and appends the new results to it.
+///
+/// Returns whether every day could be solved.
+pub fn bench(
+    days: &[usize],
+    dir: &Path,
+    runs: usize,
+    history_file: &Path,
+    config: &Config,
+) -> bool {
+    let history = match std::fs::read_to_string(history_file) {
+        Ok(text) => load_history(&text).unwrap_or_else(|e| {
+            eprintln!(
+                "{}\n  = note: in {}",
+                e.render(&text),
+                history_file.display()
+            );
+            std::process::exit(1);
+        }),
+        Err(_) => Vec::new(),
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut table = Table::new(&["Day", "Parse", "Part 1", "Part 2", "Total"]);
+    let mut results = Vec::new();
+    let mut failures = 0;
+
+    for &day in days {
+        let input = default_input(dir, day);
+        let entry = match fastest(day, &input, runs, config) {
+            Ok(timings) => Entry {
+                timestamp,
+                day,
+                parse: timings.parse,
+                part1: timings.part1.unwrap_or_default(),
+                part2: timings.part2.unwrap_or_default(),
+            },
+            Err(reason) => {
+                failures += 1;
+                let mut row = vec![day.to_string(), reason];
+                row.extend(std::iter::repeat_n(String::from("-"), 3));
+                table.add_row(row);
+                continue;
+            }
+        };
+
+        let previous = history.iter().rev().find(|e| e.day == day);
+        table.add_row(vec![
+            day.to_string(),
+            compare(entry.parse, previous.map(|p| p.parse)),
+            compare(entry.part1, previous.map(|p| p.part1)),
+            compare(entry.part2, previous.map(|p| p.part2)),
+            compare(entry.total(), previous.map(Entry::total)),
+        ]);
+        results.push(entry);
+    }
+
+    print!("{}", table);
+    if let Err(e) = append_history(history_file, history.is_empty(), &results) {
+        eprintln!("error: could not write {}: {}", history_file.display(), e);
+        return false;
+    }
+    failures == 0
+}
+
+// Solves the day `runs` times and keeps the fastest time of every step, which is the one
+// least disturbed by whatever else the machine was doing
+fn fastest(day: usize, input: &Path, runs: usize, config: &Config) -> Result<Timings, String> {
+    let mut best: Option<Timings> = None;
+    for _ in 0..runs {
+        let timings = match run_day(day, input, config) {
+            Outcome::Solved(report) => report.timings,
+            Outcome::MissingInput => return Err(format!("could not read {}", input.display())),
+            Outcome::Invalid(error) => return Err(format!("invalid input: {}", error)),
+            Outcome::Panicked => return Err(String::from("solver panicked")),
+        };
+        best = Some(match best {
+            None => timings,
+            Some(best) => Timings {
+                parse: best.parse.min(timings.parse),
+                part1: best.part1.min(timings.part1),
+                part2: best.part2.min(timings.part2),
+            },
+        });
+    }
+    best.ok_or_else(|| String::from("no runs"))
+}
+
+fn compare(time: Duration, previous: Option<Duration>) -> String {
+    let Some(previous) = previous.filter(|p| !p.is_zero()) else {
+        return format!("{:.2?}", time);
+    };
+    let change = (time.as_secs_f64() / previous.as_secs_f64() - 1.0) * 100.0;
+    format!("{:.2?} ({:+.0}% from {:.2?})", time, change, previous)
+}
+
+fn append_history(path: &Path, write_header: bool, entries: &[Entry]) -> std::io::Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if write_header && file.metadata()?.len() == 0 {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for entry in entries {
+        writeln!(
+            file,
+            "{},{},{:.6},{:.6},{:.6}",
+            entry.timestamp,
+            entry.day,
+            entry.parse.as_secs_f64() * 1000.0,
+            entry.part1.as_secs_f64() * 1000.0,
+            entry.part2.as_secs_f64() * 1000.0
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_history() {
+        let history = load_history(&format!(
+            "{}\n1700000000,1,0.010,0.250,1.500\n1700000100,16,0.5,3000,7000\n",
+            HEADER
+        ))
+        .unwrap();
+        assert_eq!(
+            history,
+            [
+                Entry {
+                    timestamp: 1700000000,
+                    day: 1,
+                    parse: Duration::from_micros(10),
+                    part1: Duration::from_micros(250),
+                    part2: Duration::from_micros(1500),
+                },
+                Entry {
+                    timestamp: 1700000100,
+                    day: 16,
+                    parse: Duration::from_micros(500),
+                    part1: Duration::from_secs(3),
+                    part2: Duration::from_secs(7),
+                }
+            ]
+        );
+        assert_eq!(load_history("").unwrap(), []);
+    }
+
+    #[test]
+    fn points_at_broken_lines() {
+        let error = load_history(&format!(
+            "{}\n1700000000,1,0.1,0.2,0.3\n1700000000,2,x,1,1",
+            HEADER
+        ))
+        .unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (3, 14, "x")
+        );
+
+        let error = load_history("day,time\n").unwrap_err();
+        assert_eq!((error.line, error.expected.as_str()), (1, HEADER));
+    }
+
+    #[test]
+    fn shows_change() {
+        assert_eq!(compare(Duration::from_millis(3), None), "3.00ms");
+        assert_eq!(
+            compare(Duration::from_millis(3), Some(Duration::from_millis(4))),
+            "3.00ms (-25% from 4.00ms)"
+        );
+        assert_eq!(
+            compare(Duration::from_millis(6), Some(Duration::from_millis(4))),
+            "6.00ms (+50% from 4.00ms)"
+        );
+    }
+}
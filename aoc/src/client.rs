@@ -0,0 +1,212 @@
+use std::{fmt::Display, path::Path, time::Duration};
+
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+pub const YEAR: usize = 2022;
+
+/// The environment variable that takes precedence over the session file.
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+const USER_AGENT: &str = "github.com/lukaswilde/advent-of-code-2022 aoc runner";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Talks to the Advent of Code website, or to anything that answers like it at another URL.
+pub struct Client {
+    url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ClientError {
+    /// Too many requests, the server asks to wait before trying again
+    RateLimited,
+    /// The puzzle is not unlocked yet
+    NotAvailable,
+    /// The session cookie is missing its user or has expired
+    BadSession,
+    Status(u16, String),
+    Network(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::RateLimited => write!(f, "rate limited by the server, try again later"),
+            ClientError::NotAvailable => write!(f, "the puzzle is not available yet"),
+            ClientError::BadSession => {
+                write!(
+                    f,
+                    "the server rejected the session cookie, it may have expired"
+                )
+            }
+            ClientError::Status(code, text) => write!(f, "unexpected response {} {}", code, text),
+            ClientError::Network(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+/// How the server judged a submitted answer.
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    /// An answer was submitted too recently, nothing was checked
+    RateLimited,
+    /// The part was solved before, nothing was checked
+    AlreadySolved,
+    Unknown,
+}
+
+impl Client {
+    pub fn new(url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build();
+        Client {
+            url: url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent,
+        }
+    }
+
+    pub fn input(&self, day: usize) -> Result<String, ClientError> {
+        let url = format!("{}/{}/day/{}/input", self.url, YEAR, day);
+        let request = self.agent.get(&url).set("Cookie", &self.cookie());
+        read(request.call())
+    }
+
+    /// Submits an answer and returns the verdict with the message of the server.
+    pub fn submit(
+        &self,
+        day: usize,
+        part: usize,
+        answer: &str,
+    ) -> Result<(Verdict, String), ClientError> {
+        let url = format!("{}/{}/day/{}/answer", self.url, YEAR, day);
+        let request = self.agent.post(&url).set("Cookie", &self.cookie());
+        let page = read(request.send_form(&[("level", &part.to_string()), ("answer", answer)]))?;
+        let message = article_text(&page);
+        Ok((verdict(&message), message))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Reads the session cookie from the environment, or from the given file.
+pub fn session(path: &Path) -> Result<String, String> {
+    if let Some(session) = std::env::var(SESSION_VARIABLE)
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    {
+        return Ok(session.trim().to_string());
+    }
+    match std::fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        _ => Err(format!(
+            "no session cookie, set {} or write it to {}",
+            SESSION_VARIABLE,
+            path.display()
+        )),
+    }
+}
+
+fn read(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| ClientError::Network(format!("could not read the response: {}", e))),
+        Err(ureq::Error::Status(429, _)) => Err(ClientError::RateLimited),
+        Err(ureq::Error::Status(404, _)) => Err(ClientError::NotAvailable),
+        // The server answers with these when the cookie does not belong to a user
+        Err(ureq::Error::Status(400 | 500, _)) => Err(ClientError::BadSession),
+        Err(ureq::Error::Status(code, response)) => Err(ClientError::Status(
+            code,
+            response.status_text().to_string(),
+        )),
+        Err(ureq::Error::Transport(e)) => Err(ClientError::Network(e.to_string())),
+    }
+}
+
+// The server explains its verdict in the article of the page, e.g. "That's not the right answer"
+fn verdict(message: &str) -> Verdict {
+    if message.contains("That's the right answer") {
+        Verdict::Correct
+    } else if message.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if message.contains("You gave an answer too recently") {
+        Verdict::RateLimited
+    } else if message.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unknown
+    }
+}
+
+// The text of the first <article> of a page, or of the whole page if it has none, without tags
+// and with whitespace collapsed
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map(|(_, rest)| {
+            rest.split_once("</article>")
+                .map_or(rest, |(inner, _)| inner)
+        })
+        .unwrap_or(page);
+    strip_tags(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The text of a piece of a page without its tags and with the characters HTML escapes
+/// written out.
+pub fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_verdicts() {
+        let page = "<html><main><article><p>That's not the right answer; your answer is too \
+                    high.  If you're stuck, make sure you're using the <em>full</em> input \
+                    data.</p></article></main></html>";
+        let message = article_text(page);
+        assert_eq!(
+            message,
+            "That's not the right answer; your answer is too high. If you're stuck, make sure \
+             you're using the full input data."
+        );
+        assert_eq!(verdict(&message), Verdict::Wrong);
+
+        let page = "<article><p>You gave an answer too recently; you have to wait after \
+                    submitting an answer before trying again. You have 42s left to wait.\
+                    </p></article>";
+        assert_eq!(verdict(&article_text(page)), Verdict::RateLimited);
+        assert_eq!(
+            verdict("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(verdict("Something else"), Verdict::Unknown);
+    }
+}
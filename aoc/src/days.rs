@@ -0,0 +1,148 @@
+use utils::{generate, Param, Params, ParseError, Part, Report, Solution, Step};
+
+// Solves the given part or both, timing parsing and each part separately and reporting every
+// step when it is done
+pub type Solver =
+    fn(&str, &Params, Option<Part>, &mut dyn FnMut(Step)) -> Result<Report, ParseError>;
+
+// Index i holds the solver of day i + 1
+const SOLVERS: [Solver; 25] = [
+    Report::measure_steps::<day01::Day01>,
+    Report::measure_steps::<day02::Day02>,
+    Report::measure_steps::<day03::Day03>,
+    Report::measure_steps::<day04::Day04>,
+    Report::measure_steps::<day05::Day05>,
+    Report::measure_steps::<day06::Day06>,
+    Report::measure_steps::<day07::Day07>,
+    Report::measure_steps::<day08::Day08>,
+    Report::measure_steps::<day09::Day09>,
+    Report::measure_steps::<day10::Day10>,
+    Report::measure_steps::<day11::Day11>,
+    Report::measure_steps::<day12::Day12>,
+    Report::measure_steps::<day13::Day13>,
+    Report::measure_steps::<day14::Day14>,
+    Report::measure_steps::<day15::Day15>,
+    Report::measure_steps::<day16::Day16>,
+    Report::measure_steps::<day17::Day17>,
+    Report::measure_steps::<day18::Day18>,
+    Report::measure_steps::<day19::Day19>,
+    Report::measure_steps::<day20::Day20>,
+    Report::measure_steps::<day21::Day21>,
+    Report::measure_steps::<day22::Day22>,
+    Report::measure_steps::<day23::Day23>,
+    Report::measure_steps::<day24::Day24>,
+    Report::measure_steps::<day25::Day25>,
+];
+
+// Index i holds the parameters of day i + 1
+const PARAMS: [&[Param]; 25] = [
+    day01::Day01::PARAMS,
+    day02::Day02::PARAMS,
+    day03::Day03::PARAMS,
+    day04::Day04::PARAMS,
+    day05::Day05::PARAMS,
+    day06::Day06::PARAMS,
+    day07::Day07::PARAMS,
+    day08::Day08::PARAMS,
+    day09::Day09::PARAMS,
+    day10::Day10::PARAMS,
+    day11::Day11::PARAMS,
+    day12::Day12::PARAMS,
+    day13::Day13::PARAMS,
+    day14::Day14::PARAMS,
+    day15::Day15::PARAMS,
+    day16::Day16::PARAMS,
+    day17::Day17::PARAMS,
+    day18::Day18::PARAMS,
+    day19::Day19::PARAMS,
+    day20::Day20::PARAMS,
+    day21::Day21::PARAMS,
+    day22::Day22::PARAMS,
+    day23::Day23::PARAMS,
+    day24::Day24::PARAMS,
+    day25::Day25::PARAMS,
+];
+
+// Makes up an input from a seed, of the given size or one like the real input
+pub type Generator = fn(u64, Option<usize>) -> String;
+
+// Index i holds the generator of day i + 1
+const GENERATORS: [Generator; 25] = [
+    generate::input::<day01::Day01>,
+    generate::input::<day02::Day02>,
+    generate::input::<day03::Day03>,
+    generate::input::<day04::Day04>,
+    generate::input::<day05::Day05>,
+    generate::input::<day06::Day06>,
+    generate::input::<day07::Day07>,
+    generate::input::<day08::Day08>,
+    generate::input::<day09::Day09>,
+    generate::input::<day10::Day10>,
+    generate::input::<day11::Day11>,
+    generate::input::<day12::Day12>,
+    generate::input::<day13::Day13>,
+    generate::input::<day14::Day14>,
+    generate::input::<day15::Day15>,
+    generate::input::<day16::Day16>,
+    generate::input::<day17::Day17>,
+    generate::input::<day18::Day18>,
+    generate::input::<day19::Day19>,
+    generate::input::<day20::Day20>,
+    generate::input::<day21::Day21>,
+    generate::input::<day22::Day22>,
+    generate::input::<day23::Day23>,
+    generate::input::<day24::Day24>,
+    generate::input::<day25::Day25>,
+];
+
+pub const NUM_DAYS: usize = SOLVERS.len();
+
+pub fn solver(day: usize) -> Option<Solver> {
+    (1..=NUM_DAYS).contains(&day).then(|| SOLVERS[day - 1])
+}
+
+pub fn params(day: usize) -> Option<&'static [Param]> {
+    (1..=NUM_DAYS).contains(&day).then(|| PARAMS[day - 1])
+}
+
+pub fn generator(day: usize) -> Option<Generator> {
+    (1..=NUM_DAYS).contains(&day).then(|| GENERATORS[day - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_single_parts() {
+        let text = "1000\n2000\n\n3000\n";
+        let solve = |part| solver(1).unwrap()(text, &Params::default(), part, &mut |_| ());
+        let report = solve(Some(Part::Two)).unwrap();
+        assert!(report.part1.is_none() && report.part2.is_some());
+        let report = solve(None).unwrap();
+        assert!(report.part1.is_some() && report.part2.is_some());
+    }
+
+    #[test]
+    fn generated_inputs_are_solved() {
+        for day in 1..=NUM_DAYS {
+            let generate = generator(day).unwrap();
+            for (seed, size) in [(0, 1), (1, 8), (2, 8), (3, 8)] {
+                let text = generate(seed, Some(size));
+                // Solving the blueprints of day 19 takes too long for a test
+                if day == 19 {
+                    assert!(day19::Day19::parse(&text).is_ok());
+                    continue;
+                }
+                let report = solver(day).unwrap()(&text, &Params::default(), None, &mut |_| ());
+                assert!(
+                    report.is_ok(),
+                    "day {}, seed {}: {:?}",
+                    day,
+                    seed,
+                    report.err()
+                );
+            }
+        }
+    }
+}
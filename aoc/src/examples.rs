@@ -0,0 +1,273 @@
+use std::path::Path;
+
+use utils::ParseError;
+
+use crate::{
+    answers::{record, to_toml, Registry},
+    client::strip_tags,
+};
+
+// The examples of the puzzle descriptions are stored next to the personal input
+const INPUT: &str = "simple_puzzle";
+
+/// The example input of a puzzle description and the answers the description gives for it.
+#[derive(Debug, PartialEq)]
+struct Example {
+    input: String,
+    // None for parts that were not unlocked yet when the page was saved
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Saves the example of a locally saved puzzle description as `dayNN/simple_puzzle.txt` and
+/// records the answers the description gives for it in the registry, so that `verify` checks
+/// them.
+///
+/// The example is the `block`th `<pre><code>` block of the first part, and the answer to a part
+/// is the last emphasised code of its description. An example or answers that differ from what
+/// is there already are only replaced with `force`.
+///
+/// Returns whether the example was saved.
+pub fn examples(
+    day: usize,
+    page: &Path,
+    block: usize,
+    dir: &Path,
+    answers: &Path,
+    force: bool,
+) -> bool {
+    match save(day, page, block, dir, answers, force) {
+        Ok(()) => true,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            false
+        }
+    }
+}
+
+fn save(
+    day: usize,
+    page: &Path,
+    block: usize,
+    dir: &Path,
+    answers: &Path,
+    force: bool,
+) -> Result<(), String> {
+    let html = std::fs::read_to_string(page)
+        .map_err(|e| format!("could not read {}: {}", page.display(), e))?;
+    let example = extract(&html, day, block)?;
+
+    // Everything is checked before anything is written
+    let path = dir
+        .join(format!("day{:02}", day))
+        .join(format!("{}.txt", INPUT));
+    let existing = std::fs::read_to_string(&path).ok();
+    if existing.as_ref().is_some_and(|e| *e != example.input) && !force {
+        return Err(format!(
+            "{} differs from the example, use --force to replace it",
+            path.display()
+        ));
+    }
+    let text = std::fs::read_to_string(answers).unwrap_or_default();
+    let registry: Registry = text.parse().map_err(|e: ParseError| {
+        format!("{}\n  = note: in {}", e.render(&text), answers.display())
+    })?;
+    let expected = registry.get(day, INPUT).cloned().unwrap_or_default();
+    let parts = [
+        ("part1", example.part1, expected.part1),
+        ("part2", example.part2, expected.part2),
+    ];
+    for (key, found, recorded) in &parts {
+        if let (Some(found), Some(recorded)) = (found, recorded) {
+            if found != recorded && !force {
+                return Err(format!(
+                    "{} of {} is recorded as {}, but the page says {}, use --force to replace it",
+                    key,
+                    path.display(),
+                    recorded,
+                    found
+                ));
+            }
+        }
+    }
+
+    if existing.as_ref() == Some(&example.input) {
+        println!("{} already holds the example", path.display());
+    } else {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, &example.input)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        println!(
+            "Saved the example to {} ({} lines)",
+            path.display(),
+            example.input.lines().count()
+        );
+    }
+
+    let mut updated = text.clone();
+    let mut entries = vec![];
+    for (key, found, _) in parts {
+        match found {
+            Some(answer) => {
+                let value = to_toml(&answer);
+                updated = record(&updated, day, INPUT, key, &value);
+                entries.push(format!("{} = {}", key, value));
+            }
+            None => println!("The page has no answer to {} yet", key),
+        }
+    }
+    if updated != text {
+        std::fs::write(answers, updated)
+            .map_err(|e| format!("could not write {}: {}", answers.display(), e))?;
+        println!("Recorded {} in {}", entries.join(", "), answers.display());
+    }
+    Ok(())
+}
+
+fn extract(page: &str, day: usize, block: usize) -> Result<Example, String> {
+    // Saving the page of another day by mistake would overwrite a good example
+    if let Some(other) = title_day(page).filter(|&d| d != day) {
+        return Err(format!("the page is about day {}, not day {}", other, day));
+    }
+    let articles = articles(page);
+    let first = articles
+        .first()
+        .ok_or("the page has no puzzle description")?;
+    let blocks = code_blocks(first);
+    let mut input = match blocks.get(block - 1) {
+        Some(input) => input.clone(),
+        None if blocks.is_empty() => return Err(String::from("the first part has no example")),
+        None => {
+            return Err(format!(
+                "the first part has only {} code blocks",
+                blocks.len()
+            ))
+        }
+    };
+    if !input.ends_with('\n') {
+        input.push('\n');
+    }
+    Ok(Example {
+        input,
+        part1: answer(first),
+        part2: articles.get(1).and_then(|article| answer(article)),
+    })
+}
+
+// The day in the `--- Day 1: Calorie Counting ---` heading
+fn title_day(page: &str) -> Option<usize> {
+    let (_, title) = page.split_once("--- Day ")?;
+    title.split_once(':')?.0.trim().parse().ok()
+}
+
+// The insides of the <article> elements, which hold the descriptions of the parts
+fn articles(page: &str) -> Vec<&str> {
+    page.split("<article")
+        .skip(1)
+        .filter_map(|rest| {
+            let (_, inner) = rest.split_once('>')?;
+            Some(
+                inner
+                    .split_once("</article>")
+                    .map_or(inner, |(inner, _)| inner),
+            )
+        })
+        .collect()
+}
+
+fn code_blocks(article: &str) -> Vec<String> {
+    article
+        .split("<pre><code>")
+        .skip(1)
+        .map(|rest| {
+            strip_tags(
+                rest.split_once("</code></pre>")
+                    .map_or(rest, |(code, _)| code),
+            )
+        })
+        .collect()
+}
+
+// The descriptions end with what the example gives, like `<code><em>24000</em></code>`, and
+// the tags are sometimes nested the other way round
+fn answer(article: &str) -> Option<String> {
+    let last = |open: &str, close: &str| {
+        let start = article.rfind(open)? + open.len();
+        let end = start + article[start..].find(close)?;
+        Some((start, &article[start..end]))
+    };
+    let (_, answer) = [
+        last("<code><em>", "</em></code>"),
+        last("<em><code>", "</code></em>"),
+    ]
+    .into_iter()
+    .flatten()
+    .max()?;
+    Some(strip_tags(answer).trim().to_string()).filter(|a| !a.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<html><main>
+<article class=\"day-desc\"><h2>--- Day 5: Supply Stacks ---</h2>
+<p>For example:</p>
+<pre><code>    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+move 1 from 2 to 1
+</code></pre>
+<p>Then, the rearrangement procedure is given: <code>move <em>1</em> from 2 to 1</code></p>
+<pre><code>[<em>D</em>]</code></pre>
+<p>The crates end up in stacks <code><em>CMZ</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>VPCDMSLWJ</code>.</p>
+<article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>
+<p>In this example, the top crates are <em><code>MCD</code></em>.</p>
+</article>
+</main></html>";
+
+    #[test]
+    fn extracts_example() {
+        assert_eq!(
+            extract(PAGE, 5, 1),
+            Ok(Example {
+                input: String::from(
+                    "    [D]\n[N] [C]\n[Z] [M] [P]\n 1   2   3\n\nmove 1 from 2 to 1\n"
+                ),
+                part1: Some(String::from("CMZ")),
+                part2: Some(String::from("MCD")),
+            })
+        );
+        assert_eq!(
+            extract(PAGE, 5, 2).map(|e| e.input),
+            Ok(String::from("[D]\n"))
+        );
+        assert_eq!(
+            extract(PAGE, 5, 3),
+            Err(String::from("the first part has only 2 code blocks"))
+        );
+        assert_eq!(
+            extract(PAGE, 6, 1),
+            Err(String::from("the page is about day 5, not day 6"))
+        );
+    }
+
+    #[test]
+    fn first_part_only() {
+        let page = PAGE.split("<p>Your puzzle answer").next().unwrap();
+        let example = extract(page, 5, 1).unwrap();
+        assert_eq!(example.part1.as_deref(), Some("CMZ"));
+        assert_eq!(example.part2, None);
+        assert_eq!(
+            extract("<html>Not found</html>", 5, 1),
+            Err(String::from("the page has no puzzle description"))
+        );
+    }
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use crate::{
+    client::{Client, ClientError},
+    default_input,
+};
+
+/// Downloads the puzzle inputs of the given days to `dayNN/puzzle.txt`, unless they are there
+/// already and `force` is not set.
+///
+/// `connect` is only called once the first input has to be downloaded, so that cached inputs
+/// need no session.
+///
+/// Returns whether every input is available afterwards.
+pub fn fetch(days: &[usize], dir: &Path, connect: impl Fn() -> Client, force: bool) -> bool {
+    let mut success = true;
+    let mut client = None;
+    for &day in days {
+        let path = default_input(dir, day);
+        if path.exists() && !force {
+            println!("Day {}: using the cached {}", day, path.display());
+            continue;
+        }
+
+        let client = client.get_or_insert_with(&connect);
+        let input = match client.input(day) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("Day {}: error: {}", day, error);
+                success = false;
+                // The remaining days would only be refused as well
+                if error == ClientError::RateLimited {
+                    break;
+                }
+                continue;
+            }
+        };
+        match save(&path, &input) {
+            Ok(()) => println!(
+                "Day {}: saved {} lines to {}",
+                day,
+                input.lines().count(),
+                path.display()
+            ),
+            Err(msg) => {
+                eprintln!("Day {}: error: {}", day, msg);
+                success = false;
+            }
+        }
+    }
+    success
+}
+
+fn save(path: &Path, input: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+    }
+    std::fs::write(path, input).map_err(|e| format!("could not write {}: {}", path.display(), e))
+}
@@ -0,0 +1,845 @@
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+use answers::Registry;
+use client::Client;
+use schedule::{Limits, Stage, Status};
+use table::Table;
+use utils::{Config, Logging, ParseError, Part, Report, Step};
+
+mod answers;
+mod bench;
+mod client;
+mod days;
+mod examples;
+mod fetch;
+mod scaffold;
+mod schedule;
+mod stars;
+mod submit;
+mod table;
+mod verify;
+mod watch;
+
+const USAGE: &str = "Usage: aoc run <day|all> [--input <file>] [--dir <directory>] [--config <file>] [--jobs <n>] [--timeout <seconds>] [-v|-vv] [--log <modules>]
+       aoc verify <day|all> [--dir <directory>] [--config <file>] [--answers <file>] [--tolerance <percent>] [--strict-timing]
+       aoc bench <day|all> [--dir <directory>] [--config <file>] [--runs <n>] [--history <file>]
+       aoc gen <day> [--seed <n>] [--size <n>]
+       aoc fetch <day|all> [--dir <directory>] [--url <url>] [--session <file>] [--force]
+       aoc submit <day> <part> [<answer>] [--dir <directory>] [--config <file>] [--answers <file>] [--url <url>] [--session <file>]
+       aoc new --day <n> [--year <n>] [--dir <directory>]
+       aoc watch <day> [--dir <directory>] [--interval <seconds>]
+       aoc examples <day> <page> [--dir <directory>] [--answers <file>] [--block <n>] [--force]
+       aoc stars [--dir <directory>] [--config <file>] [--answers <file>] [--readme <file>] [--times] [--timeout <seconds>]
+
+  <day>                  Number of the day to run (1-25)
+  all                    Run every day on its dayNN/puzzle.txt
+  <part>                 Part of the puzzle to submit the answer of (1 or 2)
+  <answer>               Answer to submit (default: the one found for dayNN/puzzle.txt)
+  <page>                 Puzzle description saved from the website as HTML
+  --input <file>         Input for a single day (default: <directory>/dayNN/puzzle.txt)
+  --jobs <n>             How many days are solved at the same time (default: one per processor)
+  --timeout <seconds>    How long parsing and each part may take before the day is given up on (default: no limit)
+  -v, -vv                Log what the solvers do to standard error, -vv for every step
+  --log <modules>        Only log the given modules, like day24,utils::search
+  --dir <directory>      Directory containing the dayNN folders (default: .)
+  --config <file>        Parameters of the days (default: <directory>/aoc.toml if it exists)
+  --answers <file>       Known answers to verify against (default: <directory>/answers.toml)
+  --tolerance <percent>  How much slower than recorded a day may get (default: 50)
+  --strict-timing        Fail on days that got slower than the tolerance allows instead of warning
+  --runs <n>             How often each day is solved, the fastest run counts (default: 1)
+  --history <file>       Results of previous benchmarks (default: <directory>/bench_history.csv)
+  --seed <n>             Seed of the random input, the same seed gives the same input (default: 0)
+  --size <n>             Size of the random input, its meaning depends on the day (default: like the real input)
+  --url <url>            Address of the Advent of Code website (default: https://adventofcode.com)
+  --session <file>       File holding the session cookie, unless AOC_SESSION is set (default: <directory>/.session)
+  --force                Download inputs again even if they are cached, or replace an example that differs
+  --block <n>            Which code block of the description is the example (default: 1)
+  --readme <file>        README with the table of stars to update (default: <directory>/README.md)
+  --times                Add how long each day took to the table of stars, solving one day at a time
+  --day <n>              Day to create a crate for (1-25)
+  --year <n>             Year of the new day, other years than 2022 go into <directory>/<year> (default: 2022)
+  --interval <seconds>   How often the sources and inputs are checked for changes (default: 1)";
+
+const DEFAULT_TOLERANCE: f64 = 50.0;
+
+fn main() {
+    let command = parse_args(std::env::args().skip(1)).unwrap_or_else(|msg| {
+        eprintln!("error: {}\n\n{}", msg, USAGE);
+        process::exit(2);
+    });
+
+    let success = match command {
+        Command::Run(config) => {
+            config.logging.init();
+            run(&config, &load_config(&config.config))
+        }
+        Command::Verify(config) => {
+            let registry = load_registry(&config.answers);
+            verify::verify(
+                &config.selection.days(),
+                &config.dir,
+                &registry,
+                config.timing,
+                &load_config(&config.config),
+            )
+        }
+        Command::Bench(config) => bench::bench(
+            &config.selection.days(),
+            &config.dir,
+            config.runs,
+            &config.history,
+            &load_config(&config.config),
+        ),
+        Command::Gen(config) => {
+            let generator = days::generator(config.day).expect("Day was validated while parsing");
+            print!("{}", generator(config.seed, config.size));
+            true
+        }
+        Command::Fetch(config) => fetch::fetch(
+            &config.selection.days(),
+            &config.dir,
+            || connect(&config.server),
+            config.force,
+        ),
+        Command::Submit(config) => submit::submit(
+            config.day,
+            config.part,
+            config.answer.as_deref(),
+            &config.dir,
+            &config.answers,
+            &connect(&config.server),
+            &load_config(&config.config),
+        ),
+        Command::New(config) => scaffold::new_day(config.year, config.day, &config.dir),
+        Command::Watch(config) => watch::watch(config.day, &config.dir, config.interval),
+        Command::Stars(config) => stars::stars(
+            &config.dir,
+            &config.readme,
+            &load_registry(&config.answers),
+            &load_config(&config.config),
+            config.times,
+            config.timeout,
+        ),
+        Command::Examples(config) => examples::examples(
+            config.day,
+            &config.page,
+            config.block,
+            &config.dir,
+            &config.answers,
+            config.force,
+        ),
+    };
+
+    if !success {
+        process::exit(1);
+    }
+}
+
+fn load_registry(path: &Path) -> Registry {
+    let text = std::fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("error: could not read {}: {}", path.display(), e);
+        process::exit(1);
+    });
+    text.parse().unwrap_or_else(|e: ParseError| {
+        eprintln!("{}\n  = note: in {}", e.render(&text), path.display());
+        process::exit(1);
+    })
+}
+
+fn connect(server: &Server) -> Client {
+    let session = client::session(&server.session).unwrap_or_else(|msg| {
+        eprintln!("error: {}", msg);
+        process::exit(1);
+    });
+    Client::new(&server.url, &session)
+}
+
+// Without a configuration file, all days use their default parameters
+fn load_config(source: &ConfigSource) -> Config {
+    let path = match source {
+        ConfigSource::Given(path) => path,
+        ConfigSource::Default(path) if path.exists() => path,
+        ConfigSource::Default(_) => return Config::default(),
+    };
+    let config = Config::load(path).unwrap_or_else(|msg| {
+        eprintln!("error: {}", msg);
+        process::exit(1);
+    });
+    for day in config.days() {
+        let checked = match days::params(day) {
+            Some(params) => config.check(day, params),
+            None => Err(format!("there is no day {}", day)),
+        };
+        if let Err(msg) = checked {
+            eprintln!("error: {}: {}", path.display(), msg);
+            process::exit(1);
+        }
+    }
+    config
+}
+
+// Returns whether every day could be solved in time
+fn run(config: &RunConfig, params: &Config) -> bool {
+    let jobs = config
+        .selection
+        .days()
+        .into_iter()
+        .map(|day| {
+            let input = config
+                .input
+                .clone()
+                .unwrap_or_else(|| default_input(&config.dir, day));
+            (day, input)
+        })
+        .collect::<Vec<_>>();
+    let inputs: HashMap<usize, PathBuf> = jobs.iter().cloned().collect();
+    let start = Instant::now();
+    let statuses = schedule::run(jobs, params, config.limits);
+    let wall_time = start.elapsed();
+
+    let mut table = Table::new(&["Day", "Part 1", "Part 2", "Time", "Status"]);
+    let mut total_time = Duration::ZERO;
+    let (mut passed, mut failed, mut timed_out) = (0, 0, 0);
+    for (day, status) in statuses {
+        let mut row = vec![day.to_string()];
+        match status {
+            Status::Finished(Outcome::Solved(report)) => {
+                passed += 1;
+                let (answers, time) = (report.answers(), report.timings.total());
+                total_time += time;
+                row.extend([
+                    answers.part1.to_string(),
+                    answers.part2.to_string(),
+                    format!("{:.2?}", time),
+                    String::from("passed"),
+                ]);
+            }
+            Status::Finished(failure) => {
+                failed += 1;
+                let reason = match failure {
+                    Outcome::MissingInput => format!("could not read {}", inputs[&day].display()),
+                    Outcome::Invalid(error) => format!("invalid input: {}", error),
+                    _ => String::from("solver panicked"),
+                };
+                row.extend([
+                    reason,
+                    String::from("-"),
+                    String::from("-"),
+                    String::from("failed"),
+                ]);
+            }
+            Status::TimedOut(stage, part1) => {
+                timed_out += 1;
+                let timeout = config.limits.timeout.unwrap_or_default();
+                let late = format!("timed out after {:.2?}", timeout);
+                let (part1, part2) = match (stage, part1) {
+                    (Stage::Parsing, _) => (format!("{} parsing", late), String::from("-")),
+                    (_, Some(answer)) => (answer.to_string(), late),
+                    (_, None) => (late, String::from("-")),
+                };
+                row.extend([part1, part2, String::from("-"), String::from("timed out")]);
+            }
+        };
+        table.add_row(row);
+    }
+
+    print!("{}", table);
+    println!(
+        "Total time: {:.2?} ({:.2?} wall clock, {} at a time)",
+        total_time, wall_time, config.limits.jobs
+    );
+    println!(
+        "{} passed, {} failed, {} timed out",
+        passed, failed, timed_out
+    );
+    failed + timed_out == 0
+}
+
+enum Outcome {
+    Solved(Report),
+    MissingInput,
+    Invalid(ParseError),
+    Panicked,
+}
+
+fn run_day(day: usize, input: &Path, config: &Config) -> Outcome {
+    run_day_steps(day, None, input, config, &mut |_| ())
+}
+
+// Solves only `part` of the day, or both without one
+fn run_day_steps(
+    day: usize,
+    part: Option<Part>,
+    input: &Path,
+    config: &Config,
+    on_step: &mut dyn FnMut(Step),
+) -> Outcome {
+    let solver = days::solver(day).expect("Day was validated while parsing arguments");
+    let text = match std::fs::read_to_string(input) {
+        Ok(text) => text,
+        Err(_) => return Outcome::MissingInput,
+    };
+    let name = input.file_stem().and_then(|stem| stem.to_str());
+    let params = config.params(day, name);
+
+    // A bug in a solver should not abort the remaining days
+    match panic::catch_unwind(AssertUnwindSafe(|| solver(&text, &params, part, on_step))) {
+        Ok(Ok(report)) => Outcome::Solved(report),
+        Ok(Err(error)) => Outcome::Invalid(error),
+        Err(_) => Outcome::Panicked,
+    }
+}
+
+fn default_input(dir: &Path, day: usize) -> PathBuf {
+    dir.join(format!("day{:02}", day)).join("puzzle.txt")
+}
+
+#[derive(Debug, PartialEq)]
+enum Selection {
+    Single(usize),
+    All,
+}
+
+impl Selection {
+    fn days(&self) -> Vec<usize> {
+        match self {
+            Selection::Single(day) => vec![*day],
+            Selection::All => (1..=days::NUM_DAYS).collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Command {
+    Run(RunConfig),
+    Verify(VerifyConfig),
+    Bench(BenchConfig),
+    Gen(GenConfig),
+    Fetch(FetchConfig),
+    Submit(SubmitConfig),
+    New(NewConfig),
+    Watch(WatchConfig),
+    Examples(ExamplesConfig),
+    Stars(StarsConfig),
+}
+
+// Where the parameters of the days come from, the default file is optional
+#[derive(Debug)]
+enum ConfigSource {
+    Given(PathBuf),
+    Default(PathBuf),
+}
+
+#[derive(Debug)]
+struct RunConfig {
+    selection: Selection,
+    input: Option<PathBuf>,
+    dir: PathBuf,
+    config: ConfigSource,
+    limits: Limits,
+    logging: Logging,
+}
+
+#[derive(Debug)]
+struct VerifyConfig {
+    selection: Selection,
+    dir: PathBuf,
+    config: ConfigSource,
+    answers: PathBuf,
+    timing: verify::Timing,
+}
+
+#[derive(Debug)]
+struct BenchConfig {
+    selection: Selection,
+    dir: PathBuf,
+    config: ConfigSource,
+    runs: usize,
+    history: PathBuf,
+}
+
+// Where the website is and how to log in
+#[derive(Debug)]
+struct Server {
+    url: String,
+    session: PathBuf,
+}
+
+#[derive(Debug)]
+struct FetchConfig {
+    selection: Selection,
+    dir: PathBuf,
+    server: Server,
+    force: bool,
+}
+
+#[derive(Debug)]
+struct SubmitConfig {
+    day: usize,
+    part: usize,
+    answer: Option<String>,
+    dir: PathBuf,
+    config: ConfigSource,
+    answers: PathBuf,
+    server: Server,
+}
+
+#[derive(Debug)]
+struct NewConfig {
+    year: usize,
+    day: usize,
+    dir: PathBuf,
+}
+
+#[derive(Debug)]
+struct WatchConfig {
+    day: usize,
+    dir: PathBuf,
+    interval: Duration,
+}
+
+#[derive(Debug)]
+struct ExamplesConfig {
+    day: usize,
+    page: PathBuf,
+    block: usize,
+    dir: PathBuf,
+    answers: PathBuf,
+    force: bool,
+}
+
+#[derive(Debug)]
+struct StarsConfig {
+    dir: PathBuf,
+    config: ConfigSource,
+    answers: PathBuf,
+    readme: PathBuf,
+    times: bool,
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct GenConfig {
+    day: usize,
+    seed: u64,
+    size: Option<usize>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let command = args.next().ok_or("missing command")?;
+    let commands = [
+        "run", "verify", "bench", "gen", "fetch", "submit", "new", "watch", "examples", "stars",
+    ];
+    if !commands.contains(&command.as_str()) {
+        return Err(format!("unknown command '{}'", command));
+    }
+    if command == "gen" {
+        return parse_gen_args(args);
+    }
+    if command == "submit" {
+        return parse_submit_args(args);
+    }
+    if command == "new" {
+        return parse_new_args(args);
+    }
+    if command == "watch" {
+        return parse_watch_args(args);
+    }
+    if command == "examples" {
+        return parse_examples_args(args);
+    }
+    if command == "stars" {
+        return parse_stars_args(args);
+    }
+
+    let selection = match args.next().as_deref() {
+        Some("all") => Selection::All,
+        Some(day) => Selection::Single(parse_day(day, |day| days::solver(day).is_some())?),
+        None => return Err(String::from("missing day")),
+    };
+
+    let mut input = None;
+    let mut dir = PathBuf::from(".");
+    let mut config = None;
+    let mut answers = None;
+    let mut timing = verify::Timing {
+        tolerance: DEFAULT_TOLERANCE,
+        strict: false,
+    };
+    let mut runs = 1;
+    let mut history = None;
+    let mut url = None;
+    let mut session = None;
+    let mut force = false;
+    let mut limits = Limits {
+        jobs: thread::available_parallelism().map_or(1, |n| n.get()),
+        timeout: None,
+    };
+    let mut logging = Logging::default();
+    while let Some(arg) = args.next() {
+        match (command.as_str(), arg.as_str()) {
+            ("run", "--input" | "-i") => {
+                let value = args.next().ok_or("--input requires a file")?;
+                input = Some(PathBuf::from(value));
+            }
+            ("run", "--jobs") => {
+                let value = args.next().ok_or("--jobs requires a number")?;
+                limits.jobs = parse_positive(&value, "number of jobs")?;
+            }
+            ("run", "--timeout") => {
+                let value = args
+                    .next()
+                    .ok_or("--timeout requires a number of seconds")?;
+                limits.timeout = Some(parse_seconds(&value)?);
+            }
+            ("run", "-v" | "--verbose") => logging.verbosity += 1,
+            ("run", "-vv") => logging.verbosity += 2,
+            ("run", "--log") => {
+                let list = args.next().ok_or("--log requires a list of modules")?;
+                logging.add_targets(&list)?;
+            }
+            (_, "--dir") => {
+                let value = args.next().ok_or("--dir requires a directory")?;
+                dir = PathBuf::from(value);
+            }
+            ("run" | "verify" | "bench", "--config") => {
+                let value = args.next().ok_or("--config requires a file")?;
+                config = Some(PathBuf::from(value));
+            }
+            ("fetch", "--url") => url = Some(args.next().ok_or("--url requires an address")?),
+            ("fetch", "--session") => {
+                let value = args.next().ok_or("--session requires a file")?;
+                session = Some(PathBuf::from(value));
+            }
+            ("fetch", "--force") => force = true,
+            ("verify", "--answers") => {
+                let value = args.next().ok_or("--answers requires a file")?;
+                answers = Some(PathBuf::from(value));
+            }
+            ("verify", "--tolerance") => {
+                let value = args.next().ok_or("--tolerance requires a percentage")?;
+                timing.tolerance = value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|t| t.is_finite() && *t >= 0.0)
+                    .ok_or_else(|| format!("'{}' is not a valid percentage", value))?;
+            }
+            ("verify", "--strict-timing") => timing.strict = true,
+            ("bench", "--runs") => {
+                let value = args.next().ok_or("--runs requires a number")?;
+                runs = parse_positive(&value, "number of runs")?;
+            }
+            ("bench", "--history") => {
+                let value = args.next().ok_or("--history requires a file")?;
+                history = Some(PathBuf::from(value));
+            }
+            (_, other) => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+
+    if command == "fetch" {
+        return Ok(Command::Fetch(FetchConfig {
+            selection,
+            server: server(url, session, &dir),
+            dir,
+            force,
+        }));
+    }
+
+    let config = config_source(config, &dir);
+
+    if command == "verify" {
+        return Ok(Command::Verify(VerifyConfig {
+            selection,
+            answers: answers.unwrap_or_else(|| dir.join("answers.toml")),
+            dir,
+            config,
+            timing,
+        }));
+    }
+
+    if command == "bench" {
+        return Ok(Command::Bench(BenchConfig {
+            selection,
+            history: history.unwrap_or_else(|| dir.join("bench_history.csv")),
+            dir,
+            config,
+            runs,
+        }));
+    }
+
+    if input.is_some() && selection == Selection::All {
+        return Err(String::from("--input can only be used with a single day"));
+    }
+
+    Ok(Command::Run(RunConfig {
+        selection,
+        input,
+        dir,
+        config,
+        limits,
+        logging,
+    }))
+}
+
+fn config_source(config: Option<PathBuf>, dir: &Path) -> ConfigSource {
+    match config {
+        Some(path) => ConfigSource::Given(path),
+        None => ConfigSource::Default(dir.join(Config::FILE_NAME)),
+    }
+}
+
+fn server(url: Option<String>, session: Option<PathBuf>, dir: &Path) -> Server {
+    Server {
+        url: url.unwrap_or_else(|| String::from(client::DEFAULT_URL)),
+        session: session.unwrap_or_else(|| dir.join(".session")),
+    }
+}
+
+// Answers are submitted one part at a time
+fn parse_submit_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = args.next().ok_or("missing day")?;
+    let day = parse_day(&day, |day| days::solver(day).is_some())?;
+    let part = match args.next() {
+        Some(part) => match part.as_str() {
+            "1" => 1,
+            "2" => 2,
+            _ => return Err(format!("'{}' is not a part, use 1 or 2", part)),
+        },
+        None => return Err(String::from("missing part")),
+    };
+
+    let mut answer = None;
+    let mut dir = PathBuf::from(".");
+    let mut config = None;
+    let mut answers = None;
+    let mut url = None;
+    let mut session = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dir" => dir = PathBuf::from(args.next().ok_or("--dir requires a directory")?),
+            "--config" => {
+                config = Some(PathBuf::from(
+                    args.next().ok_or("--config requires a file")?,
+                ));
+            }
+            "--answers" => {
+                answers = Some(PathBuf::from(
+                    args.next().ok_or("--answers requires a file")?,
+                ));
+            }
+            "--url" => url = Some(args.next().ok_or("--url requires an address")?),
+            "--session" => {
+                session = Some(PathBuf::from(
+                    args.next().ok_or("--session requires a file")?,
+                ));
+            }
+            other if answer.is_none() && !other.starts_with("--") => {
+                answer = Some(other.to_string());
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    Ok(Command::Submit(SubmitConfig {
+        day,
+        part,
+        answer,
+        config: config_source(config, &dir),
+        answers: answers.unwrap_or_else(|| dir.join("answers.toml")),
+        server: server(url, session, &dir),
+        dir,
+    }))
+}
+
+// Examples are extracted for one day at a time, as every day has its own page
+fn parse_examples_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = args.next().ok_or("missing day")?;
+    let day = parse_day(&day, |day| days::solver(day).is_some())?;
+    let mut page = None;
+    let mut dir = PathBuf::from(".");
+    let mut answers = None;
+    let mut block = 1;
+    let mut force = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dir" => dir = PathBuf::from(args.next().ok_or("--dir requires a directory")?),
+            "--answers" => {
+                answers = Some(PathBuf::from(
+                    args.next().ok_or("--answers requires a file")?,
+                ));
+            }
+            "--block" => {
+                let value = args.next().ok_or("--block requires a number")?;
+                block = parse_positive(&value, "number")?;
+            }
+            "--force" => force = true,
+            other if page.is_none() && !other.starts_with("--") => {
+                page = Some(PathBuf::from(other));
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    Ok(Command::Examples(ExamplesConfig {
+        day,
+        page: page.ok_or("missing page")?,
+        block,
+        answers: answers.unwrap_or_else(|| dir.join("answers.toml")),
+        dir,
+        force,
+    }))
+}
+
+// The table always covers every day
+fn parse_stars_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut dir = PathBuf::from(".");
+    let mut config = None;
+    let mut answers = None;
+    let mut readme = None;
+    let mut times = false;
+    let mut timeout = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dir" => dir = PathBuf::from(args.next().ok_or("--dir requires a directory")?),
+            "--config" => {
+                config = Some(PathBuf::from(
+                    args.next().ok_or("--config requires a file")?,
+                ));
+            }
+            "--answers" => {
+                answers = Some(PathBuf::from(
+                    args.next().ok_or("--answers requires a file")?,
+                ));
+            }
+            "--readme" => {
+                readme = Some(PathBuf::from(
+                    args.next().ok_or("--readme requires a file")?,
+                ));
+            }
+            "--times" => times = true,
+            "--timeout" => {
+                let value = args
+                    .next()
+                    .ok_or("--timeout requires a number of seconds")?;
+                timeout = Some(parse_seconds(&value)?);
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    Ok(Command::Stars(StarsConfig {
+        config: config_source(config, &dir),
+        answers: answers.unwrap_or_else(|| dir.join("answers.toml")),
+        readme: readme.unwrap_or_else(|| dir.join("README.md")),
+        dir,
+        times,
+        timeout,
+    }))
+}
+
+// Every day of Advent of Code since its first year can be created
+fn parse_new_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut year = client::YEAR;
+    let mut day = None;
+    let mut dir = PathBuf::from(".");
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => {
+                let value = args.next().ok_or("--year requires a number")?;
+                year = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&year| year >= 2015)
+                    .ok_or_else(|| format!("'{}' is not a year of Advent of Code", value))?;
+            }
+            "--day" => {
+                let value = args.next().ok_or("--day requires a number")?;
+                day = Some(parse_day(&value, |day| {
+                    (1..=days::NUM_DAYS).contains(&day)
+                })?);
+            }
+            "--dir" => dir = PathBuf::from(args.next().ok_or("--dir requires a directory")?),
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    let day = day.ok_or("missing --day")?;
+    Ok(Command::New(NewConfig { year, day, dir }))
+}
+
+// Watching several days at once would mix up their output
+fn parse_watch_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = args.next().ok_or("missing day")?;
+    let day = parse_day(&day, |day| days::solver(day).is_some())?;
+
+    let mut dir = PathBuf::from(".");
+    let mut interval = Duration::from_secs(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--dir" => dir = PathBuf::from(args.next().ok_or("--dir requires a directory")?),
+            "--interval" => {
+                let value = args
+                    .next()
+                    .ok_or("--interval requires a number of seconds")?;
+                interval = parse_seconds(&value)?;
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    Ok(Command::Watch(WatchConfig { day, dir, interval }))
+}
+
+// Generating inputs only makes sense for a single day
+fn parse_gen_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let day = args.next().ok_or("missing day")?;
+    let day = parse_day(&day, |day| days::generator(day).is_some())?;
+
+    let mut seed = 0;
+    let mut size = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                let value = args.next().ok_or("--seed requires a number")?;
+                seed = value
+                    .parse::<u64>()
+                    .map_err(|_| format!("'{}' is not a valid seed", value))?;
+            }
+            "--size" => {
+                let value = args.next().ok_or("--size requires a number")?;
+                size = Some(parse_positive(&value, "size")?);
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    Ok(Command::Gen(GenConfig { day, seed, size }))
+}
+
+// Each subcommand decides which days it can handle, the message stays the same
+fn parse_day(value: &str, available: impl Fn(usize) -> bool) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|&day| available(day))
+        .ok_or_else(|| format!("'{}' is not a day between 1 and 25", value))
+}
+
+fn parse_positive(value: &str, what: &str) -> Result<usize, String> {
+    value
+        .parse::<usize>()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| format!("'{}' is not a positive {}", value, what))
+}
+
+// Fractions of a second are allowed, but no zero, negative or infinite durations
+fn parse_seconds(value: &str) -> Result<Duration, String> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|t| t.is_finite() && *t > 0.0)
+        .map(Duration::from_secs_f64)
+        .ok_or_else(|| format!("'{}' is not a positive number of seconds", value))
+}
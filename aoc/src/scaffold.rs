@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+
+use crate::client::YEAR;
+
+// The files of a new day, relative to its directory, with the template they are made from
+const TEMPLATES: [(&str, &str); 5] = [
+    (
+        "Cargo.toml",
+        include_str!("../templates/Cargo.toml.template"),
+    ),
+    ("src/lib.rs", include_str!("../templates/lib.rs")),
+    ("src/main.rs", include_str!("../templates/main.rs")),
+    ("tests/example.rs", include_str!("../templates/example.rs")),
+    (
+        "simple_puzzle.txt",
+        include_str!("../templates/simple_puzzle.txt"),
+    ),
+];
+
+/// Where the crate of a day goes and what it is called.
+///
+/// The days of this repository's year live in `dayNN` next to `utils`, those of other years in
+/// `<year>/dayNN`. Package names have to be unique in the workspace, so the latter are called
+/// `dayNN-<year>`.
+#[derive(Debug, PartialEq)]
+struct Layout {
+    // Relative to the workspace root
+    path: String,
+    package: String,
+    // The workspace member that covers the crate
+    member: String,
+}
+
+impl Layout {
+    fn new(year: usize, day: usize) -> Self {
+        if year == YEAR {
+            Layout {
+                path: format!("day{:02}", day),
+                package: format!("day{:02}", day),
+                member: String::from("day*"),
+            }
+        } else {
+            Layout {
+                path: format!("{}/day{:02}", year, day),
+                package: format!("day{:02}-{}", day, year),
+                member: format!("{}/day*", year),
+            }
+        }
+    }
+
+    fn fill(&self, template: &str, day: usize) -> String {
+        let depth = self.path.matches('/').count() + 1;
+        template
+            .replace("{{package}}", &self.package)
+            .replace("{{lib}}", &self.package.replace('-', "_"))
+            .replace("{{utils}}", &format!("{}utils", "../".repeat(depth)))
+            .replace("{{Day}}", &format!("Day{:02}", day))
+            .replace("{{day}}", &day.to_string())
+    }
+}
+
+/// Creates the crate of a new day from the templates and registers it in the workspace.
+///
+/// Returns whether the crate was created.
+pub fn new_day(year: usize, day: usize, dir: &Path) -> bool {
+    match create(year, day, dir) {
+        Ok(path) => {
+            println!(
+                "Created {}, add the puzzle input as {}",
+                path.display(),
+                path.join("puzzle.txt").display()
+            );
+            true
+        }
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            false
+        }
+    }
+}
+
+fn create(year: usize, day: usize, dir: &Path) -> Result<PathBuf, String> {
+    let layout = Layout::new(year, day);
+    let manifest = dir.join("Cargo.toml");
+    let workspace = std::fs::read_to_string(&manifest)
+        .map_err(|e| format!("could not read {}: {}", manifest.display(), e))?;
+    let path = dir.join(&layout.path);
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+
+    // Checked before anything is written, so that a broken manifest leaves no half-made day
+    let registered = register(&workspace, &layout.member).ok_or_else(|| {
+        format!(
+            "could not find the workspace members in {}",
+            manifest.display()
+        )
+    })?;
+    for (file, template) in TEMPLATES {
+        let file = path.join(file);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&file, layout.fill(template, day))
+            .map_err(|e| format!("could not write {}: {}", file.display(), e))?;
+    }
+    if registered != workspace {
+        std::fs::write(&manifest, registered)
+            .map_err(|e| format!("could not write {}: {}", manifest.display(), e))?;
+        println!("Added \"{}\" to the workspace members", layout.member);
+    }
+    Ok(path)
+}
+
+// Adds a member to the `members` list of a workspace manifest unless one of them covers it
+// already, keeping the list sorted. None if the manifest has no such list.
+fn register(manifest: &str, member: &str) -> Option<String> {
+    let start = manifest.find("members")?;
+    let open = start + manifest[start..].find('[')?;
+    let close = open + manifest[open..].find(']')?;
+
+    let mut members: Vec<&str> = manifest[open + 1..close]
+        .split(',')
+        .map(|m| m.trim().trim_matches('"'))
+        .filter(|m| !m.is_empty())
+        .collect();
+    if members.contains(&member) {
+        return Some(manifest.to_string());
+    }
+    members.push(member);
+    members.sort_unstable();
+
+    let list: String = members
+        .iter()
+        .map(|m| format!("    \"{}\",\n", m))
+        .collect();
+    Some(format!(
+        "{}[\n{}{}",
+        &manifest[..open],
+        list,
+        &manifest[close..]
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKSPACE: &str = "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day*\",\n    \"utils\",\n]\n\n[profile.test]\nopt-level = 3\n";
+
+    #[test]
+    fn lays_out_years() {
+        let layout = Layout::new(2023, 7);
+        assert_eq!(layout.path, "2023/day07");
+        assert_eq!(layout.package, "day07-2023");
+        assert_eq!(
+            layout.fill("{{lib}}::{{Day}} {{day}} {{utils}}", 7),
+            "day07_2023::Day07 7 ../../utils"
+        );
+        assert_eq!(
+            Layout::new(YEAR, 3).fill("{{package}} {{utils}}", 3),
+            "day03 ../utils"
+        );
+    }
+
+    #[test]
+    fn registers_members() {
+        assert_eq!(register(WORKSPACE, "day*").as_deref(), Some(WORKSPACE));
+        assert_eq!(
+            register(WORKSPACE, "2023/day*").as_deref(),
+            Some("[workspace]\n\nmembers = [\n    \"2023/day*\",\n    \"aoc\",\n    \"day*\",\n    \"utils\",\n]\n\n[profile.test]\nopt-level = 3\n")
+        );
+        assert_eq!(
+            register("[workspace]\nmembers = [\"aoc\"]\n", "2023/day*").as_deref(),
+            Some("[workspace]\nmembers = [\n    \"2023/day*\",\n    \"aoc\",\n]\n")
+        );
+        assert_eq!(register("[package]\nname = \"aoc\"\n", "day*"), None);
+    }
+}
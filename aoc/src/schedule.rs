@@ -0,0 +1,174 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use utils::{Answer, Config, Part, Step};
+
+use crate::{run_day_steps, Outcome};
+
+/// How many days are solved at the same time, and how long each step of a day may take.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    pub jobs: usize,
+    pub timeout: Option<Duration>,
+}
+
+/// The step of a day that is running, or that took too long.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stage {
+    Parsing,
+    Solving(Part),
+}
+
+/// What became of a day.
+pub enum Status {
+    Finished(Outcome),
+    /// The step that took too long, with the answer to the first part if it was found in time
+    TimedOut(Stage, Option<Answer>),
+}
+
+enum Message {
+    Step(usize, Step),
+    Done(usize, Outcome),
+}
+
+struct Running {
+    stage: Stage,
+    deadline: Option<Instant>,
+    part1: Option<Answer>,
+}
+
+/// Solves the days on their inputs on up to `limits.jobs` threads at once, in the order given.
+///
+/// A day that takes longer than the timeout for parsing or a part is given up on. Its thread
+/// cannot be stopped, so it keeps running in the background until the runner exits, but the
+/// next day takes its place and the runner never waits for it.
+pub fn run(
+    jobs: Vec<(usize, PathBuf)>,
+    config: &Config,
+    limits: Limits,
+) -> BTreeMap<usize, Status> {
+    let config = config.clone();
+    schedule(jobs, limits, move |day, input, report| {
+        run_day_steps(day, None, input, &config, report)
+    })
+}
+
+// Runs `solve` for every day, which reports the steps of the day as it goes
+fn schedule<F>(jobs: Vec<(usize, PathBuf)>, limits: Limits, solve: F) -> BTreeMap<usize, Status>
+where
+    F: Fn(usize, &Path, &mut dyn FnMut(Step)) -> Outcome + Clone + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let mut pending = jobs.into_iter();
+    let mut running: HashMap<usize, Running> = HashMap::new();
+    let mut statuses = BTreeMap::new();
+    let deadline = |timeout: Option<Duration>| timeout.map(|t| Instant::now() + t);
+
+    loop {
+        while running.len() < limits.jobs.max(1) {
+            let Some((day, input)) = pending.next() else {
+                break;
+            };
+            let (sender, solve) = (sender.clone(), solve.clone());
+            thread::spawn(move || {
+                let outcome = solve(day, &input, &mut |step| {
+                    // The runner no longer listens once it has given up on the day
+                    let _ = sender.send(Message::Step(day, step));
+                });
+                let _ = sender.send(Message::Done(day, outcome));
+            });
+            running.insert(
+                day,
+                Running {
+                    stage: Stage::Parsing,
+                    deadline: deadline(limits.timeout),
+                    part1: None,
+                },
+            );
+        }
+        if running.is_empty() {
+            return statuses;
+        }
+
+        let next_deadline = running.values().filter_map(|r| r.deadline).min();
+        let message = match next_deadline {
+            Some(next) => receiver.recv_timeout(next.saturating_duration_since(Instant::now())),
+            None => receiver
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match message {
+            Ok(Message::Step(day, step)) => {
+                if let Some(day) = running.get_mut(&day) {
+                    day.stage = match step {
+                        Step::Parsed(_) => Stage::Solving(Part::One),
+                        Step::Solved(Part::One, answer, _) => {
+                            day.part1 = Some(answer);
+                            Stage::Solving(Part::Two)
+                        }
+                        Step::Solved(Part::Two, ..) => day.stage,
+                    };
+                    day.deadline = deadline(limits.timeout);
+                }
+            }
+            Ok(Message::Done(day, outcome)) => {
+                if running.remove(&day).is_some() {
+                    statuses.insert(day, Status::Finished(outcome));
+                }
+            }
+            Err(_) => (),
+        }
+
+        // Checked after every message, as a busy channel would otherwise hide late days
+        let now = Instant::now();
+        let late: Vec<usize> = running
+            .iter()
+            .filter(|(_, r)| r.deadline.is_some_and(|d| d <= now))
+            .map(|(&day, _)| day)
+            .collect();
+        for day in late {
+            let running = running.remove(&day).expect("Day should be running");
+            statuses.insert(day, Status::TimedOut(running.stage, running.part1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn does_not_wait_for_hung_days() {
+        let jobs = (1..=3).map(|day| (day, PathBuf::new())).collect();
+        let limits = Limits {
+            jobs: 1,
+            timeout: Some(Duration::from_millis(100)),
+        };
+        let statuses = schedule(jobs, limits, |day, _, _| {
+            // The first day never returns
+            if day == 1 {
+                loop {
+                    thread::park();
+                }
+            }
+            Outcome::MissingInput
+        });
+        assert!(matches!(
+            statuses[&1],
+            Status::TimedOut(Stage::Parsing, None)
+        ));
+        assert!(matches!(
+            statuses[&2],
+            Status::Finished(Outcome::MissingInput)
+        ));
+        assert!(matches!(
+            statuses[&3],
+            Status::Finished(Outcome::MissingInput)
+        ));
+    }
+}
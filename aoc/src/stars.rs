@@ -0,0 +1,243 @@
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use utils::{Answer, Config};
+
+use crate::{
+    answers::Registry,
+    client::YEAR,
+    days, default_input,
+    schedule::{self, Limits, Status},
+    Outcome,
+};
+
+/// The line before and after the table of stars in the README.
+pub const MARKER: &str = "<!--- advent_readme_stars table --->";
+
+// Stars are only given for the personal input
+const INPUT: &str = "puzzle";
+
+// What became of one part of a day
+#[derive(Debug, Clone, PartialEq)]
+enum Check {
+    Verified,
+    // The part has nothing to solve, like the second part of day 25
+    Free,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Day {
+    parts: [Check; 2],
+    time: Option<Duration>,
+}
+
+/// Solves every day on its `dayNN/puzzle.txt`, compares the answers with the registry and
+/// rewrites the table of stars between the two [`MARKER`] lines of the README, with a star
+/// for every part whose answer is recorded and matches. `times` adds a column with how long
+/// each day took, in which case the days are solved one at a time.
+///
+/// Like on the website, the second part of the last day is only a star once all others are.
+/// A part that takes longer than `timeout` gets no star.
+///
+/// Returns whether the README could be updated.
+pub fn stars(
+    dir: &Path,
+    readme: &Path,
+    registry: &Registry,
+    config: &Config,
+    times: bool,
+    timeout: Option<Duration>,
+) -> bool {
+    match update(dir, readme, registry, config, times, timeout) {
+        Ok(()) => true,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            false
+        }
+    }
+}
+
+fn update(
+    dir: &Path,
+    readme: &Path,
+    registry: &Registry,
+    config: &Config,
+    times: bool,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    // Checked before solving anything, which takes a while
+    let text = std::fs::read_to_string(readme)
+        .map_err(|e| format!("could not read {}: {}", readme.display(), e))?;
+    replace_table(&text, "")
+        .ok_or_else(|| format!("{} has no table between two {}", readme.display(), MARKER))?;
+
+    let jobs = (1..=days::NUM_DAYS)
+        .map(|day| (day, default_input(dir, day)))
+        .collect();
+    let limits = Limits {
+        jobs: if times {
+            1
+        } else {
+            std::thread::available_parallelism().map_or(1, |n| n.get())
+        },
+        timeout,
+    };
+    let days: BTreeMap<usize, Day> = schedule::run(jobs, config, limits)
+        .into_iter()
+        .map(|(day, status)| (day, check_day(day, status, dir, registry)))
+        .collect();
+
+    let mut earned = 0;
+    for (day, result) in &days {
+        // A day that could not be solved fails both parts for the same reason
+        if let [Check::Failed(first), Check::Failed(second)] = &result.parts {
+            if first == second {
+                println!("Day {}: {}", day, first);
+                continue;
+            }
+        }
+        for (part, check) in result.parts.iter().enumerate() {
+            match check {
+                Check::Verified => earned += 1,
+                Check::Free => (),
+                Check::Failed(reason) => println!("Day {} part {}: {}", day, part + 1, reason),
+            }
+        }
+    }
+    let last = days.keys().max().copied().unwrap_or_default();
+    let free = earned + 1 == 2 * days.len();
+    let table = render(&days, last, free, times);
+    let updated = replace_table(&text, &table).expect("Markers were checked before");
+    if updated != text {
+        std::fs::write(readme, updated)
+            .map_err(|e| format!("could not write {}: {}", readme.display(), e))?;
+    }
+    println!(
+        "{} of {} stars verified, updated {}",
+        earned + usize::from(free),
+        2 * days.len(),
+        readme.display()
+    );
+    Ok(())
+}
+
+fn check_day(day: usize, status: Status, dir: &Path, registry: &Registry) -> Day {
+    let failed = |reason: String| Day {
+        parts: [Check::Failed(reason.clone()), Check::Failed(reason)],
+        time: None,
+    };
+    let expected = registry.get(day, INPUT).cloned().unwrap_or_default();
+    let check = |expected: Option<String>, answer: Answer| match expected {
+        Some(expected) if expected == answer.to_string() => Check::Verified,
+        Some(expected) => Check::Failed(format!("expected {}, got {}", expected, answer)),
+        None if answer == Answer::Unsolved => Check::Free,
+        None => Check::Failed(format!("no recorded answer to check {} against", answer)),
+    };
+    match status {
+        Status::Finished(Outcome::Solved(report)) => {
+            let answers = report.answers();
+            Day {
+                parts: [
+                    check(expected.part1, answers.part1),
+                    check(expected.part2, answers.part2),
+                ],
+                time: Some(report.timings.total()),
+            }
+        }
+        Status::Finished(Outcome::MissingInput) => {
+            let input = default_input(dir, day);
+            failed(format!("could not read {}", input.display()))
+        }
+        Status::Finished(Outcome::Invalid(error)) => failed(format!("invalid input: {}", error)),
+        Status::Finished(Outcome::Panicked) => failed(String::from("solver panicked")),
+        // The first part still counts if it was solved in time
+        Status::TimedOut(_, Some(part1)) => Day {
+            parts: [
+                check(expected.part1, part1),
+                Check::Failed(String::from("timed out")),
+            ],
+            time: None,
+        },
+        Status::TimedOut(..) => failed(String::from("timed out")),
+    }
+}
+
+// The table in the format of the advent-readme-stars action, so that either can update it
+fn render(days: &BTreeMap<usize, Day>, last: usize, free: bool, times: bool) -> String {
+    let mut table = format!("## {} Results\n\n| Day | Part 1 | Part 2 |", YEAR);
+    table += if times {
+        " Time |\n| :---: | :---: | :---: | ---: |\n"
+    } else {
+        "\n| :---: | :---: | :---: |\n"
+    };
+    for (&day, result) in days {
+        let star = |check: &Check| match check {
+            Check::Verified => "⭐",
+            Check::Free if day == last && free => "⭐",
+            _ => " ",
+        };
+        table += &format!(
+            "| [Day {}](https://adventofcode.com/{}/day/{}) | {} | {} |",
+            day,
+            YEAR,
+            day,
+            star(&result.parts[0]),
+            star(&result.parts[1])
+        );
+        if times {
+            let time = result
+                .time
+                .map_or(String::from("-"), |t| format!("{:.2?}", t));
+            table += &format!(" {} |", time);
+        }
+        table.push('\n');
+    }
+    table
+}
+
+// The text with everything between the first two markers replaced by the table, None if
+// there are no two markers
+fn replace_table(text: &str, table: &str) -> Option<String> {
+    let start = text.find(MARKER)? + MARKER.len();
+    let end = start + text[start..].find(MARKER)?;
+    Some(format!("{}\n{}{}", &text[..start], table, &text[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_table() {
+        let text = format!("# Title\n\n{}\nold\n{}\n\nMore\n", MARKER, MARKER);
+        assert_eq!(
+            replace_table(&text, "new\n"),
+            Some(format!("# Title\n\n{}\nnew\n{}\n\nMore\n", MARKER, MARKER))
+        );
+        assert_eq!(replace_table(&format!("{}\nold\n", MARKER), "new\n"), None);
+    }
+
+    #[test]
+    fn renders_stars() {
+        let day = |parts: [Check; 2], millis| Day {
+            parts,
+            time: Some(Duration::from_millis(millis)),
+        };
+        let days = BTreeMap::from([
+            (1, day([Check::Verified, Check::Verified], 2)),
+            (2, day([Check::Verified, Check::Failed(String::new())], 3)),
+            (3, day([Check::Verified, Check::Free], 1)),
+        ]);
+        assert_eq!(
+            render(&days, 3, false, true),
+            "## 2022 Results\n\n\
+             | Day | Part 1 | Part 2 | Time |\n\
+             | :---: | :---: | :---: | ---: |\n\
+             | [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ | 2.00ms |\n\
+             | [Day 2](https://adventofcode.com/2022/day/2) | ⭐ |   | 3.00ms |\n\
+             | [Day 3](https://adventofcode.com/2022/day/3) | ⭐ |   | 1.00ms |\n"
+        );
+        assert!(render(&days, 3, true, false)
+            .ends_with("| [Day 3](https://adventofcode.com/2022/day/3) | ⭐ | ⭐ |\n"));
+    }
+}
@@ -0,0 +1,135 @@
+use std::path::Path;
+
+use utils::{Answer, Config, ParseError, Part};
+
+use crate::{
+    answers::{list_to_toml, record, to_toml, Registry},
+    client::{Client, Verdict},
+    default_input, run_day_steps, Outcome,
+};
+
+// Answers are only submitted for the personal input
+const INPUT: &str = "puzzle";
+
+/// Submits the answer to one part of a day, or the one the solver finds for `dayNN/puzzle.txt`.
+///
+/// Answers the registry already knows are not submitted again. The verdict is recorded in the
+/// registry: right answers as `partN`, wrong ones in the `partN_wrong` list.
+///
+/// Returns whether the answer is right.
+pub fn submit(
+    day: usize,
+    part: usize,
+    answer: Option<&str>,
+    dir: &Path,
+    answers: &Path,
+    client: &Client,
+    config: &Config,
+) -> bool {
+    match try_submit(day, part, answer, dir, answers, client, config) {
+        Ok(correct) => correct,
+        Err(msg) => {
+            eprintln!("error: {}", msg);
+            false
+        }
+    }
+}
+
+fn try_submit(
+    day: usize,
+    part: usize,
+    answer: Option<&str>,
+    dir: &Path,
+    answers: &Path,
+    client: &Client,
+    config: &Config,
+) -> Result<bool, String> {
+    let answer = match answer {
+        Some(answer) => answer.to_string(),
+        None => solve(day, part, dir, config)?,
+    };
+
+    // A missing registry is created with the first right answer
+    let text = std::fs::read_to_string(answers).unwrap_or_default();
+    let registry: Registry = text.parse().map_err(|e: ParseError| {
+        format!("{}\n  = note: in {}", e.render(&text), answers.display())
+    })?;
+    let expected = registry.get(day, INPUT).cloned().unwrap_or_default();
+    let (right, mut wrong) = match part {
+        1 => (expected.part1, expected.part1_wrong),
+        _ => (expected.part2, expected.part2_wrong),
+    };
+    match right {
+        Some(right) if right == answer => {
+            println!(
+                "{} is already recorded as the right answer, not submitting it",
+                answer
+            );
+            return Ok(true);
+        }
+        Some(right) => {
+            return Err(format!(
+                "the right answer {} is already recorded, not submitting {}",
+                right, answer
+            ))
+        }
+        None if wrong.contains(&answer) => {
+            return Err(format!(
+                "{} was rejected before, not submitting it again",
+                answer
+            ))
+        }
+        None => (),
+    }
+
+    let (verdict, message) = client
+        .submit(day, part, &answer)
+        .map_err(|e| e.to_string())?;
+    println!("{}", message);
+    let entry = match verdict {
+        Verdict::Correct => (format!("part{}", part), to_toml(&answer)),
+        Verdict::Wrong => {
+            wrong.push(answer);
+            (format!("part{}_wrong", part), list_to_toml(&wrong))
+        }
+        Verdict::RateLimited => return Err(String::from("rate limited, nothing was checked")),
+        Verdict::AlreadySolved => {
+            return Err(format!(
+                "part {} of day {} was solved before, nothing was checked",
+                part, day
+            ))
+        }
+        Verdict::Unknown => return Err(String::from("could not tell whether the answer is right")),
+    };
+
+    let updated = record(&text, day, INPUT, &entry.0, &entry.1);
+    std::fs::write(answers, updated)
+        .map_err(|e| format!("could not write {}: {}", answers.display(), e))?;
+    println!("Recorded {} in {}", entry.0, answers.display());
+    Ok(verdict == Verdict::Correct)
+}
+
+// Only the submitted part is solved
+fn solve(day: usize, part: usize, dir: &Path, config: &Config) -> Result<String, String> {
+    let input = default_input(dir, day);
+    let part = if part == 1 { Part::One } else { Part::Two };
+    let report = match run_day_steps(day, Some(part), &input, config, &mut |_| ()) {
+        Outcome::Solved(report) => report,
+        Outcome::MissingInput => return Err(format!("could not read {}", input.display())),
+        Outcome::Invalid(error) => return Err(format!("invalid input: {}", error)),
+        Outcome::Panicked => return Err(String::from("solver panicked")),
+    };
+    let answers = report.answers();
+    let (answer, part) = match part {
+        Part::One => (answers.part1, 1),
+        Part::Two => (answers.part2, 2),
+    };
+    match answer {
+        Answer::Unsolved => Err(format!("day {} has no answer for part {}", day, part)),
+        // Like the letters of day 10, which are drawn rather than printed
+        Answer::Text(text) if text.contains('\n') => Err(String::from(
+            "the answer is a drawing, pass the letters it shows instead",
+        )),
+        answer => Ok(answer.to_string()),
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Display;
+
+// Simple text table, where cells may span multiple lines (e.g. the CRT image of day 10)
+pub struct Table {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(header: &[&str]) -> Self {
+        Self {
+            header: header.iter().map(|h| h.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        assert_eq!(row.len(), self.header.len());
+        self.rows.push(row);
+    }
+
+    fn column_widths(&self) -> Vec<usize> {
+        (0..self.header.len())
+            .map(|col| {
+                std::iter::once(&self.header)
+                    .chain(self.rows.iter())
+                    .flat_map(|row| row[col].lines())
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+}
+
+fn write_row(
+    f: &mut std::fmt::Formatter<'_>,
+    row: &[String],
+    widths: &[usize],
+) -> std::fmt::Result {
+    let height = row
+        .iter()
+        .map(|cell| cell.lines().count())
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    for line in 0..height {
+        write!(f, "|")?;
+        for (cell, &width) in row.iter().zip(widths) {
+            let content = cell.lines().nth(line).unwrap_or_default();
+            write!(f, " {:<width$} |", content, width = width)?;
+        }
+        writeln!(f)?;
+    }
+    Ok(())
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let widths = self.column_widths();
+        write_row(f, &self.header, &widths)?;
+
+        write!(f, "|")?;
+        for width in widths.iter() {
+            write!(f, "{}|", "-".repeat(width + 2))?;
+        }
+        writeln!(f)?;
+
+        for row in self.rows.iter() {
+            write_row(f, row, &widths)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,175 @@
+use std::{collections::BTreeSet, path::Path, time::Duration};
+
+use utils::{Answer, Answers, Config};
+
+use crate::{
+    answers::{to_toml, Expected, Registry},
+    run_day,
+    table::Table,
+    Outcome,
+};
+
+// Differences below this are scheduler noise on the fast days rather than regressions
+const MIN_REGRESSION: Duration = Duration::from_millis(1);
+
+/// The inputs every day is checked against when they exist, next to those named in answers.toml.
+const STORED_INPUTS: [&str; 2] = ["simple_puzzle", "puzzle"];
+
+/// How much slower than recorded a day may get, and whether that fails verification.
+///
+/// The recorded times come from one machine, so by default a slower run is only a warning.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timing {
+    /// In percent of the recorded time
+    pub tolerance: f64,
+    pub strict: bool,
+}
+
+#[derive(Default)]
+struct Summary {
+    inputs: usize,
+    mismatches: usize,
+    missing: usize,
+    regressions: usize,
+    errors: usize,
+    // Answers that are not yet recorded, as TOML sections that can be pasted into answers.toml
+    unrecorded: Vec<String>,
+}
+
+/// Runs the given days on their stored inputs and compares the results with the registry.
+///
+/// Returns whether every recorded answer matched, and with strict timing, whether no day got
+/// slower than the tolerance allows.
+pub fn verify(
+    days: &[usize],
+    dir: &Path,
+    registry: &Registry,
+    timing: Timing,
+    config: &Config,
+) -> bool {
+    let mut table = Table::new(&["Day", "Input", "Part 1", "Part 2", "Time"]);
+    let mut summary = Summary::default();
+
+    for &day in days {
+        let day_dir = dir.join(format!("day{:02}", day));
+        let mut inputs: BTreeSet<&str> = registry.inputs(day).collect();
+        inputs.extend(
+            STORED_INPUTS
+                .iter()
+                .filter(|name| day_dir.join(format!("{}.txt", name)).exists()),
+        );
+
+        for input in inputs {
+            summary.inputs += 1;
+            let path = day_dir.join(format!("{}.txt", input));
+            let expected = registry.get(day, input).cloned().unwrap_or_default();
+            let mut row = vec![day.to_string(), input.to_string()];
+            row.extend(match run_day(day, &path, config) {
+                Outcome::Solved(report) => {
+                    let (answers, time) = (report.answers(), report.timings.total());
+                    let part1 = check(&expected.part1, &answers.part1, &mut summary);
+                    let part2 = check(&expected.part2, &answers.part2, &mut summary);
+                    let unrecorded = unrecorded(&expected, &answers, time);
+                    if !unrecorded.is_empty() {
+                        summary
+                            .unrecorded
+                            .push(format!("[day{:02}.{}]\n{}", day, input, unrecorded));
+                    }
+                    [
+                        part1,
+                        part2,
+                        check_time(expected.time, time, timing.tolerance, &mut summary),
+                    ]
+                }
+                failure => {
+                    summary.errors += 1;
+                    let reason = match failure {
+                        Outcome::MissingInput => format!("could not read {}", path.display()),
+                        Outcome::Invalid(error) => format!("invalid input: {}", error),
+                        _ => String::from("solver panicked"),
+                    };
+                    [reason, String::from("-"), String::from("-")]
+                }
+            });
+            table.add_row(row);
+        }
+    }
+
+    print!("{}", table);
+    println!(
+        "Verified {} inputs: {} mismatches, {} missing answers, {} timing regressions, {} errors",
+        summary.inputs, summary.mismatches, summary.missing, summary.regressions, summary.errors
+    );
+    if summary.regressions > 0 && !timing.strict {
+        println!("warning: timing regressions only fail with --strict-timing");
+    }
+    if !summary.unrecorded.is_empty() {
+        println!(
+            "\nTo record the missing answers and timings, add them to answers.toml:\n\n{}",
+            summary.unrecorded.join("\n\n")
+        );
+    }
+
+    let regressions = if timing.strict {
+        summary.regressions
+    } else {
+        0
+    };
+    summary.mismatches + regressions + summary.errors == 0
+}
+
+fn check(expected: &Option<String>, answer: &Answer, summary: &mut Summary) -> String {
+    let solved = !matches!(answer, Answer::Unsolved);
+    let answer = answer.to_string();
+    match expected {
+        Some(expected) if *expected == answer => String::from("ok"),
+        Some(expected) => {
+            summary.mismatches += 1;
+            format!("expected {}, got {}", expected, answer)
+        }
+        // Nothing to check for parts without a solution, like the second part of day 25
+        None if !solved => String::from("-"),
+        None => {
+            summary.missing += 1;
+            format!("missing, got {}", answer)
+        }
+    }
+}
+
+fn check_time(
+    baseline: Option<Duration>,
+    time: Duration,
+    tolerance: f64,
+    summary: &mut Summary,
+) -> String {
+    let Some(baseline) = baseline else {
+        return format!("{:.2?}", time);
+    };
+    let limit = baseline
+        .mul_f64(1.0 + tolerance / 100.0)
+        .max(baseline + MIN_REGRESSION);
+    if time <= limit {
+        return format!("{:.2?}", time);
+    }
+    summary.regressions += 1;
+    let change = (time.as_secs_f64() / baseline.as_secs_f64() - 1.0) * 100.0;
+    format!(
+        "{:.2?}, regressed from {:.2?} (+{:.0}%)",
+        time, baseline, change
+    )
+}
+
+fn unrecorded(expected: &Expected, answers: &Answers, time: Duration) -> String {
+    let mut lines = [
+        ("part1", &expected.part1, &answers.part1),
+        ("part2", &expected.part2, &answers.part2),
+    ]
+    .into_iter()
+    .filter(|(_, expected, answer)| expected.is_none() && !matches!(answer, Answer::Unsolved))
+    .map(|(key, _, answer)| format!("{} = {}", key, to_toml(&answer.to_string())))
+    .collect::<Vec<_>>();
+    if expected.time.is_none() {
+        lines.push(format!("time_ms = {:.3}", time.as_secs_f64() * 1000.0));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,190 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::OsString,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use utils::Report;
+
+use crate::table::Table;
+
+// Examples come first, as a broken solution shows there quicker
+const INPUTS: [&str; 2] = ["simple_puzzle", "puzzle"];
+
+// When a file was last changed and how large it was then, which is enough to tell edits apart
+// without a notification service of the operating system
+type Snapshot = BTreeMap<PathBuf, (SystemTime, u64)>;
+
+// The answers of one input, `None` for parts without an answer
+#[derive(Debug, Clone, PartialEq)]
+struct Answers {
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+/// Runs a day on its inputs whenever its sources or inputs change, polling them every
+/// `interval`, and shows how the answers differ from the previous run. Never returns.
+///
+/// The day is built with `cargo build` and then run with `cargo run`, so that changes to the
+/// sources count and build errors are told apart from inputs the day fails on. The cargo
+/// program is taken from the `CARGO` environment variable like cargo itself does.
+pub fn watch(day: usize, dir: &Path, interval: Duration) -> bool {
+    let day_dir = format!("day{:02}", day);
+    let mut last = Snapshot::new();
+    let mut previous: HashMap<&str, Answers> = HashMap::new();
+    println!(
+        "Watching {} for changes, press Ctrl+C to stop",
+        dir.join(&day_dir).display()
+    );
+
+    loop {
+        let current = snapshot(&dir.join(&day_dir));
+        let changed = changes(&last, &current);
+        if !changed.is_empty() {
+            last = current;
+            println!("\nChanged: {}", changed.join(", "));
+            // A day that does not build is not worth running on any input
+            if let Err(msg) = build(day, dir) {
+                println!("Build failed:\n{}", msg.trim_end());
+                thread::sleep(interval);
+                continue;
+            }
+
+            let mut table = Table::new(&["Input", "Part 1", "Part 2"]);
+            let mut failure = None;
+            for input in INPUTS {
+                let path = Path::new(&day_dir).join(format!("{}.txt", input));
+                if !dir.join(&path).exists() {
+                    continue;
+                }
+                let answers = match solve(day, dir, &path) {
+                    Ok(answers) => answers,
+                    Err(msg) => {
+                        // The real input is not worth running while an example fails
+                        failure = Some((input, msg));
+                        break;
+                    }
+                };
+                let before = previous.insert(input, answers.clone());
+                table.add_row(vec![
+                    input.to_string(),
+                    show(&answers.part1, before.as_ref().map(|b| &b.part1)),
+                    show(&answers.part2, before.as_ref().map(|b| &b.part2)),
+                ]);
+            }
+            print!("{}", table);
+            if let Some((input, msg)) = failure {
+                println!("{} failed:\n{}", input, msg.trim_end());
+            }
+        }
+        thread::sleep(interval);
+    }
+}
+
+fn snapshot(day_dir: &Path) -> Snapshot {
+    let mut files = Snapshot::new();
+    let mut dirs = vec![day_dir.join("src")];
+    while let Some(dir) = dirs.pop() {
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if let Ok(metadata) = entry.metadata() {
+                files.insert(path, (modified(&metadata), metadata.len()));
+            }
+        }
+    }
+    for input in INPUTS {
+        let path = day_dir.join(format!("{}.txt", input));
+        if let Ok(metadata) = path.metadata() {
+            files.insert(path, (modified(&metadata), metadata.len()));
+        }
+    }
+    files
+}
+
+fn modified(metadata: &std::fs::Metadata) -> SystemTime {
+    metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+// The files that were added, changed or removed
+fn changes(last: &Snapshot, current: &Snapshot) -> Vec<String> {
+    let mut paths: Vec<&PathBuf> = current
+        .iter()
+        .filter(|(path, state)| last.get(*path) != Some(state))
+        .map(|(path, _)| path)
+        .chain(last.keys().filter(|path| !current.contains_key(*path)))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect()
+}
+
+fn build(day: usize, dir: &Path) -> Result<(), String> {
+    cargo(dir, "build", day, &[]).map(|_| ())
+}
+
+fn solve(day: usize, dir: &Path, input: &Path) -> Result<Answers, String> {
+    let input = input.to_string_lossy();
+    let stdout = cargo(
+        dir,
+        "run",
+        day,
+        &["--", "--format", "json", "--input", &input],
+    )?;
+    let json = stdout
+        .lines()
+        .rfind(|line| line.starts_with('{'))
+        .ok_or_else(|| format!("expected the answers as JSON, got:\n{}", stdout))?;
+    let report = Report::from_json(json)
+        .map_err(|e| format!("could not read the answers from {}: {}", json, e))?;
+    Ok(Answers {
+        part1: report.part1.map(|answer| answer.to_string()),
+        part2: report.part2.map(|answer| answer.to_string()),
+    })
+}
+
+// Runs a cargo command for the binary of the day in `dir` and returns what it printed, or what
+// it printed as errors if it failed
+fn cargo(dir: &Path, command: &str, day: usize, args: &[&str]) -> Result<String, String> {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"));
+    let output = Command::new(&cargo)
+        .current_dir(dir)
+        .args([command, "--release", "--quiet", "--bin"])
+        .arg(format!("day{:02}", day))
+        .args(args)
+        .output()
+        .map_err(|e| format!("could not run {}: {}", cargo.to_string_lossy(), e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).into_owned());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+// An answer together with the one of the previous run if it differs
+fn show(answer: &Option<String>, before: Option<&Option<String>>) -> String {
+    let text = |answer: &Option<String>| answer.clone().unwrap_or_else(|| String::from("-"));
+    match before {
+        Some(before) if before != answer => format!("{} (was {})", text(answer), text(before)),
+        _ => text(answer),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shows_changes() {
+        let answer = Some(String::from("42"));
+        assert_eq!(show(&answer, None), "42");
+        assert_eq!(show(&answer, Some(&answer)), "42");
+        assert_eq!(show(&answer, Some(&None)), "42 (was -)");
+        assert_eq!(show(&None, Some(&answer)), "- (was 42)");
+    }
+}
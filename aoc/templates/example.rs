@@ -0,0 +1,14 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn simple() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("{{package}}")?;
+    cmd.args(["-i", "simple_puzzle.txt"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The answer to the first part is 2"))
+        .stdout(predicate::str::contains("The answer to the second part is 11"));
+    Ok(())
+}
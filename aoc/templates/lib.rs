@@ -0,0 +1,25 @@
+use utils::{Input, ParseError, Solution};
+
+pub struct {{Day}};
+
+// Stubs to replace with the solution: the lines of the input, counted in the first part and
+// their characters in the second
+impl Solution for {{Day}} {
+    const DAY: usize = {{day}};
+
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Ok(Input::new(text).lines().map(String::from).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> Self::Part1 {
+        lines.len()
+    }
+
+    fn part2(lines: &Self::Input) -> Self::Part2 {
+        lines.iter().map(|line| line.chars().count()).sum()
+    }
+}
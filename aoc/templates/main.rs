@@ -0,0 +1,19 @@
+use {{lib}}::{{Day}};
+use utils::{Args, Format, Part, Solution};
+
+fn main() {
+    let args = Args::parse::<{{Day}}>();
+    if args.format == Format::Json {
+        args.print_json::<{{Day}}>();
+        return;
+    }
+
+    let input = args.input::<{{Day}}>();
+
+    if args.runs(Part::One) {
+        println!("The answer to the first part is {}", {{Day}}::part1(&input));
+    }
+    if args.runs(Part::Two) {
+        println!("The answer to the second part is {}", {{Day}}::part2(&input));
+    }
+}
@@ -0,0 +1,39 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn keeps_history() -> Result<(), Box<dyn std::error::Error>> {
+    let history = std::env::temp_dir().join(format!("aoc-bench-{}.csv", std::process::id()));
+    let _ = std::fs::remove_file(&history);
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["bench", "2", "--dir", "..", "--runs", "2", "--history"])
+        .arg(&history);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| Day | Parse"))
+        .stdout(predicate::str::contains("% from").not());
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["bench", "2", "--dir", "..", "--history"])
+        .arg(&history);
+    cmd.assert().success().stdout(predicate::str::is_match(
+        r"\| 2   \| [0-9.]+[µnm]?s \([+-]\d+% from ",
+    )?);
+
+    let lines = std::fs::read_to_string(&history)?;
+    assert!(lines.starts_with("timestamp,day,parse_ms,part1_ms,part2_ms\n"));
+    assert_eq!(lines.lines().filter(|l| l.contains(",2,")).count(), 2);
+    Ok(())
+}
+
+#[test]
+fn invalid_runs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["bench", "all", "--runs", "0"]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "'0' is not a positive number of runs",
+    ));
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    process::Command,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+const RIGHT: &str = "<html><main><article><p>That's the right answer! You are <em>one gold \
+                     star</em> closer to collecting enough star fruit.</p></article></main></html>";
+const WRONG: &str = "<html><main><article><p>That's not the right answer; your answer is too \
+                     low.</p></article></main></html>";
+const TOO_RECENT: &str = "<html><main><article><p>You gave an answer too recently; you have to \
+                          wait after submitting an answer before trying again. You have 31s left \
+                          to wait.</p></article></main></html>";
+
+/// Stands in for the Advent of Code website on a local port, answering every request with
+/// `respond` and keeping the requests to check them.
+struct MockServer {
+    url: String,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    fn start(respond: fn(&str) -> (u16, &'static str)) -> MockServer {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Should bind to a free port");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let seen = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(&stream);
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap_or(0);
+                    }
+                    request.push_str(&line);
+                }
+                let mut body = vec![0; length];
+                let _ = reader.read_exact(&mut body);
+                request.push_str(&String::from_utf8_lossy(&body));
+
+                let (status, page) = respond(&request);
+                seen.lock().unwrap().push(request);
+                let response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    page.len(),
+                    page
+                );
+                let _ = (&stream).write_all(response.as_bytes());
+            }
+        });
+        MockServer { url, requests }
+    }
+
+    fn requests(&self) -> Vec<String> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+fn inputs(request: &str) -> (u16, &'static str) {
+    match request.lines().next() {
+        _ if !request.contains("Cookie: session=secret") => (400, "Please log in"),
+        Some("GET /2022/day/1/input HTTP/1.1") => (200, "1000\n2000\n\n3000\n"),
+        _ => (404, "Not found"),
+    }
+}
+
+fn answers(request: &str) -> (u16, &'static str) {
+    if request.ends_with("level=1&answer=24000") {
+        (200, RIGHT)
+    } else {
+        (200, WRONG)
+    }
+}
+
+fn temp_dir(name: &str) -> Result<PathBuf, std::io::Error> {
+    let dir = std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn aoc(
+    server: &MockServer,
+    dir: &PathBuf,
+    args: &[&str],
+) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(args)
+        .arg("--dir")
+        .arg(dir)
+        .args(["--url", &server.url])
+        .env("AOC_SESSION", "secret");
+    Ok(cmd)
+}
+
+#[test]
+fn fetch_caches_inputs() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(inputs);
+    let dir = temp_dir("fetch")?;
+
+    aoc(&server, &dir, &["fetch", "1"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Day 1: saved 4 lines to"));
+    let input = std::fs::read_to_string(dir.join("day01").join("puzzle.txt"))?;
+    assert_eq!(input, "1000\n2000\n\n3000\n");
+
+    aoc(&server, &dir, &["fetch", "1"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Day 1: using the cached"));
+    assert_eq!(server.requests().len(), 1);
+
+    // Cached inputs need no session
+    aoc(&server, &dir, &["fetch", "1"])?
+        .env_remove("AOC_SESSION")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Day 1: using the cached"));
+
+    aoc(&server, &dir, &["fetch", "1", "--force"])?
+        .assert()
+        .success();
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn fetch_reports_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(inputs);
+    let dir = temp_dir("fetch-errors")?;
+
+    aoc(&server, &dir, &["fetch", "2"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Day 2: error: the puzzle is not available yet",
+        ));
+    aoc(&server, &dir, &["fetch", "1"])?
+        .env("AOC_SESSION", "expired")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("rejected the session cookie"));
+    assert!(!dir.join("day01").exists());
+    Ok(())
+}
+
+#[test]
+fn fetch_stops_when_rate_limited() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(|_| (429, "Too many requests"));
+    let dir = temp_dir("rate-limit")?;
+
+    aoc(&server, &dir, &["fetch", "all"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "Day 1: error: rate limited by the server",
+        ));
+    assert_eq!(server.requests().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn session_file() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(inputs);
+    let dir = temp_dir("session")?;
+
+    aoc(&server, &dir, &["fetch", "1"])?
+        .env_remove("AOC_SESSION")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "no session cookie, set AOC_SESSION or write it to",
+        ));
+
+    std::fs::write(dir.join(".session"), "secret\n")?;
+    aoc(&server, &dir, &["fetch", "1"])?
+        .env_remove("AOC_SESSION")
+        .assert()
+        .success();
+    Ok(())
+}
+
+#[test]
+fn submit_records_verdicts() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(answers);
+    let dir = temp_dir("submit")?;
+    let registry = dir.join("answers.toml");
+    std::fs::write(&registry, "# Answers\n[day01.puzzle]\npart2 = 45000\n")?;
+
+    aoc(&server, &dir, &["submit", "1", "1", "42"])?
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "That's not the right answer; your answer is too low.",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(&registry)?,
+        "# Answers\n[day01.puzzle]\npart2 = 45000\npart1_wrong = [42]\n"
+    );
+
+    aoc(&server, &dir, &["submit", "1", "1", "42"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "42 was rejected before, not submitting it again",
+        ));
+    assert_eq!(server.requests().len(), 1);
+
+    aoc(&server, &dir, &["submit", "1", "1", "24000"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("That's the right answer!"));
+    assert!(std::fs::read_to_string(&registry)?.contains("part1 = 24000\n"));
+
+    aoc(&server, &dir, &["submit", "1", "1", "24000"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "already recorded as the right answer",
+        ));
+    aoc(&server, &dir, &["submit", "1", "2", "1"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "the right answer 45000 is already recorded",
+        ));
+    assert_eq!(server.requests().len(), 2);
+    Ok(())
+}
+
+#[test]
+fn submit_solves_puzzle() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(answers);
+    let dir = temp_dir("solve")?;
+    std::fs::create_dir(dir.join("day01"))?;
+    std::fs::copy(
+        "../day01/simple_puzzle.txt",
+        dir.join("day01").join("puzzle.txt"),
+    )?;
+
+    aoc(&server, &dir, &["submit", "1", "1"])?
+        .assert()
+        .success();
+    let requests = server.requests();
+    assert!(requests[0].starts_with("POST /2022/day/1/answer HTTP/1.1"));
+    assert!(requests[0].ends_with("level=1&answer=24000"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("answers.toml"))?,
+        "[day01.puzzle]\npart1 = 24000\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn submit_rate_limited() -> Result<(), Box<dyn std::error::Error>> {
+    let server = MockServer::start(|_| (200, TOO_RECENT));
+    let dir = temp_dir("submit-rate-limit")?;
+
+    aoc(&server, &dir, &["submit", "1", "2", "45000"])?
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("You have 31s left to wait."))
+        .stderr(predicate::str::contains(
+            "error: rate limited, nothing was checked",
+        ));
+    assert!(!dir.join("answers.toml").exists());
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{path::PathBuf, process::Command};
+
+// The example of day 1 as the website shows it, which ends with a line break
+fn example() -> Result<String, std::io::Error> {
+    Ok(std::fs::read_to_string("../day01/simple_puzzle.txt")?
+        .trim_end()
+        .to_string()
+        + "\n")
+}
+
+// The parts of the description of day 1 that matter
+fn page(part2: bool) -> Result<String, std::io::Error> {
+    let example = example()?;
+    let mut page = format!(
+        "<!DOCTYPE html>\n<html lang=\"en-us\"><body><main>\n\
+         <article class=\"day-desc\"><h2>--- Day 1: Calorie Counting ---</h2>\n\
+         <p>For example, suppose the Elves finish writing their items' Calories:</p>\n\
+         <pre><code>{}</code></pre>\n\
+         <p>In the example above, this is <em>24000</em> Calories (carried by the fourth \
+         Elf), so the answer is <code><em>24000</em></code>.</p>\n</article>\n",
+        example
+    );
+    if part2 {
+        page += "<p>Your puzzle answer was <code>67633</code>.</p>\
+                 <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\n\
+                 <p>In the example above, the top three Elves are carrying <code>24000</code>, \
+                 <code>11000</code> and <code>10000</code>, for a total of \
+                 <code><em>45000</em></code>.</p>\n</article>\n";
+    }
+    Ok(page + "</main></body></html>\n")
+}
+
+fn workspace(name: &str, part2: bool) -> Result<PathBuf, std::io::Error> {
+    let dir = std::env::temp_dir().join(format!("aoc-examples-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("page.html"), page(part2)?)?;
+    Ok(dir)
+}
+
+fn examples(dir: &PathBuf, args: &[&str]) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.arg("examples")
+        .args(args)
+        .arg(dir.join("page.html"))
+        .arg("--dir")
+        .arg(dir);
+    Ok(cmd)
+}
+
+#[test]
+fn saves_example_and_answers() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("save", false)?;
+    examples(&dir, &["1"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Saved the example to"))
+        .stdout(predicate::str::contains(
+            "The page has no answer to part2 yet",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("day01").join("simple_puzzle.txt"))?,
+        example()?
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("answers.toml"))?,
+        "[day01.simple_puzzle]\npart1 = 24000\n"
+    );
+
+    // Once the second part is unlocked, its answer is added
+    std::fs::write(dir.join("page.html"), page(true)?)?;
+    examples(&dir, &["1"])?
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("already holds the example"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("answers.toml"))?,
+        "[day01.simple_puzzle]\npart1 = 24000\npart2 = 45000\n"
+    );
+
+    // The saved example passes verification
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "1", "--dir"]).arg(&dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| simple_puzzle | ok     | ok "));
+    Ok(())
+}
+
+#[test]
+fn keeps_differing_examples() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("differ", true)?;
+    std::fs::write(
+        dir.join("answers.toml"),
+        "[day01.simple_puzzle]\npart2 = 1\n",
+    )?;
+    examples(&dir, &["1"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "is recorded as 1, but the page says 45000, use --force to replace it",
+        ));
+    assert!(!dir.join("day01").exists());
+
+    examples(&dir, &["1", "--force"])?.assert().success();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("answers.toml"))?,
+        "[day01.simple_puzzle]\npart2 = 45000\npart1 = 24000\n"
+    );
+
+    examples(&dir, &["2"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "the page is about day 1, not day 2",
+        ));
+    examples(&dir, &["1", "--block", "2"])?
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "the first part has only 1 code blocks",
+        ));
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+fn generate(args: &[&str]) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::cargo_bin("aoc")?.arg("gen").args(args).output()?;
+    assert!(output.status.success());
+    Ok(String::from_utf8(output.stdout)?)
+}
+
+#[test]
+fn same_seed_same_input() -> Result<(), Box<dyn std::error::Error>> {
+    let first = generate(&["9", "--seed", "7"])?;
+    assert_eq!(first, generate(&["9", "--seed", "7"])?);
+    assert_ne!(first, generate(&["9", "--seed", "8"])?);
+    // Day 9 counts the moves of the head
+    assert_eq!(first.lines().count(), 2000);
+    assert_eq!(generate(&["9", "--size", "30"])?.lines().count(), 30);
+    Ok(())
+}
+
+#[test]
+fn generated_input_is_solved() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = std::env::temp_dir().join("aoc_gen_test");
+    std::fs::create_dir_all(&dir)?;
+    let input = dir.join("monkeys.txt");
+    std::fs::write(&input, generate(&["21", "--seed", "3"])?)?;
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "21", "--input"]).arg(&input);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| 21  |"));
+    Ok(())
+}
+
+#[test]
+fn invalid_arguments() -> Result<(), Box<dyn std::error::Error>> {
+    for (args, message) in [
+        (&["gen", "all"][..], "'all' is not a day between 1 and 25"),
+        (&["gen", "16", "--size", "0"], "'0' is not a positive size"),
+        (&["gen", "16", "--seed", "-1"], "'-1' is not a valid seed"),
+        (
+            &["gen", "16", "--input", "x.txt"],
+            "unexpected argument '--input'",
+        ),
+    ] {
+        let mut cmd = Command::cargo_bin("aoc")?;
+        cmd.args(args);
+        cmd.assert()
+            .code(2)
+            .stderr(predicate::str::contains(message))
+            .stderr(predicate::str::contains("aoc gen <day>"));
+    }
+    Ok(())
+}
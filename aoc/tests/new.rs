@@ -0,0 +1,85 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{path::PathBuf, process::Command};
+
+const WORKSPACE: &str =
+    "[workspace]\n\nmembers = [\n    \"aoc\",\n    \"day*\",\n    \"utils\",\n]\n";
+
+fn workspace(name: &str) -> Result<PathBuf, std::io::Error> {
+    let dir = std::env::temp_dir().join(format!("aoc-new-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("Cargo.toml"), WORKSPACE)?;
+    Ok(dir)
+}
+
+#[test]
+fn new_year() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("year")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["new", "--year", "2023", "--day", "1", "--dir"])
+        .arg(&dir);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "Added \"2023/day*\" to the workspace members",
+    ));
+
+    let day = dir.join("2023").join("day01");
+    for file in [
+        "src/lib.rs",
+        "src/main.rs",
+        "tests/example.rs",
+        "simple_puzzle.txt",
+    ] {
+        assert!(day.join(file).exists(), "{} is missing", file);
+    }
+    let manifest = std::fs::read_to_string(day.join("Cargo.toml"))?;
+    assert!(manifest.contains("name = \"day01-2023\""));
+    assert!(manifest.contains("utils = {path = \"../../utils\"}"));
+    let main = std::fs::read_to_string(day.join("src").join("main.rs"))?;
+    assert!(main.starts_with("use day01_2023::Day01;"));
+    assert!(std::fs::read_to_string(dir.join("Cargo.toml"))?.contains("    \"2023/day*\",\n"));
+
+    // The second day of the year is covered by the same member
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["new", "--year", "2023", "--day", "2", "--dir"])
+        .arg(&dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("workspace members").not());
+    Ok(())
+}
+
+#[test]
+fn new_day_of_this_year() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("day")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["new", "--day", "12", "--dir"]).arg(&dir);
+    cmd.assert().success();
+
+    let lib = std::fs::read_to_string(dir.join("day12").join("src").join("lib.rs"))?;
+    assert!(lib.contains("impl Solution for Day12 {\n    const DAY: usize = 12;"));
+    assert_eq!(std::fs::read_to_string(dir.join("Cargo.toml"))?, WORKSPACE);
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["new", "--day", "12", "--dir"]).arg(&dir);
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("day12 already exists"));
+    Ok(())
+}
+
+#[test]
+fn invalid_new_arguments() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["new", "--year", "2023"]);
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("error: missing --day"));
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["new", "--year", "2014", "--day", "1"]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "'2014' is not a year of Advent of Code",
+    ));
+    Ok(())
+}
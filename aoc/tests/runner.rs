@@ -0,0 +1,166 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::process::Command;
+
+#[test]
+fn single_day() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "1", "--input", "../day01/puzzle.txt"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| Day | Part 1"))
+        .stdout(predicate::str::contains("| 1   | 67633  | 199628 |"));
+    Ok(())
+}
+
+#[test]
+fn default_input_directory() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "25", "--dir", ".."]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("20=02=120-=-2110-0=1"));
+    Ok(())
+}
+
+#[test]
+fn missing_input() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "3", "--input", "does_not_exist.txt"]);
+    cmd.assert().failure().stdout(predicate::str::contains(
+        "could not read does_not_exist.txt",
+    ));
+    Ok(())
+}
+
+#[test]
+fn invalid_day() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "26"]);
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "'26' is not a day between 1 and 25",
+        ))
+        .stderr(predicate::str::contains("Usage: aoc run"));
+    Ok(())
+}
+
+#[test]
+fn configured_parameters() -> Result<(), Box<dyn std::error::Error>> {
+    // The example of day 15 needs the row and search area from ../aoc.toml
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args([
+        "run",
+        "15",
+        "--dir",
+        "..",
+        "--input",
+        "../day15/simple_puzzle.txt",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| 15  | 26     | 56000011 |"));
+
+    let path = std::env::temp_dir().join(format!("aoc-{}.toml", std::process::id()));
+    std::fs::write(&path, "[day15]\nrow = 11\nsearch-max = 20\n")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args([
+        "run",
+        "15",
+        "--input",
+        "../day15/simple_puzzle.txt",
+        "--config",
+    ])
+    .arg(&path);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("| 15  | 28     | 56000011 |"));
+
+    std::fs::write(&path, "[day26]\nrow = 11\n")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "15", "--config"]).arg(&path);
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("there is no day 26"));
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn timeout() -> Result<(), Box<dyn std::error::Error>> {
+    // The first part of day 16 takes a fraction of the time of the second
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "16", "--dir", "..", "--timeout", "0.5"]);
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::is_match(
+            r"\| 16  \| 2181   \| timed out after 500\.00ms \| -    \| timed out \|",
+        )?)
+        .stdout(predicate::str::contains("0 passed, 0 failed, 1 timed out"));
+    Ok(())
+}
+
+#[test]
+fn all_days_in_parallel() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = std::env::temp_dir().join(format!("aoc-parallel-{}", std::process::id()));
+    for day in ["day01", "day25"] {
+        std::fs::create_dir_all(dir.join(day))?;
+        std::fs::copy(
+            format!("../{}/puzzle.txt", day),
+            dir.join(day).join("puzzle.txt"),
+        )?;
+    }
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "all", "--jobs", "4", "--dir"]).arg(&dir);
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("| 1   | 67633"))
+        .stdout(predicate::str::is_match(
+            r"\| 2   \| could not read .*day02.puzzle\.txt \| -  .*\| failed ",
+        )?)
+        .stdout(predicate::str::contains("| 25  | 20=02=120-=-2110-0=1"))
+        .stdout(predicate::str::contains("4 at a time"))
+        .stdout(predicate::str::contains("2 passed, 23 failed, 0 timed out"));
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+#[test]
+fn invalid_limits() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "all", "--jobs", "0"]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "'0' is not a positive number of jobs",
+    ));
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "all", "--timeout", "-1"]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "'-1' is not a positive number of seconds",
+    ));
+    Ok(())
+}
+
+#[test]
+fn logging() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "24", "--input", "../day24/simple_puzzle.txt", "-v"]);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "[DEBUG utils::search] Reached a goal",
+        ))
+        .stderr(predicate::str::contains(
+            "[DEBUG day24] Trip 1 from (1, 0) to (6, 5) arrives at minute 18\n",
+        ))
+        .stderr(predicate::str::contains("TRACE").not());
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["run", "24", "--log", "day24, ,"]);
+    cmd.assert()
+        .code(2)
+        .stderr(predicate::str::contains("is not a list of modules"));
+    Ok(())
+}
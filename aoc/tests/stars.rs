@@ -0,0 +1,114 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{path::PathBuf, process::Command};
+
+const MARKER: &str = "<!--- advent_readme_stars table --->";
+
+// A workspace with the inputs of days 1 and 2, where the answer recorded for the second part
+// of day 2 is wrong and the other days have no input
+fn workspace(name: &str) -> Result<PathBuf, std::io::Error> {
+    let dir = std::env::temp_dir().join(format!("aoc-stars-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    for day in ["day01", "day02"] {
+        std::fs::create_dir_all(dir.join(day))?;
+        std::fs::copy(
+            PathBuf::from("..").join(day).join("puzzle.txt"),
+            dir.join(day).join("puzzle.txt"),
+        )?;
+    }
+    std::fs::write(
+        dir.join("answers.toml"),
+        "[day01.puzzle]\npart1 = 67633\npart2 = 199628\n\n\
+         [day02.puzzle]\npart1 = 14069\npart2 = 1\n",
+    )?;
+    std::fs::write(
+        dir.join("README.md"),
+        format!("# Results\n\n{}\n## Old\n{}\n\nThe end\n", MARKER, MARKER),
+    )?;
+    Ok(dir)
+}
+
+#[test]
+fn only_verified_parts_get_stars() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("verified")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["stars", "--dir"]).arg(&dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Day 2 part 2: expected 1, got 12411",
+        ))
+        .stdout(predicate::str::contains("Day 3: could not read"))
+        .stdout(predicate::str::contains("3 of 50 stars verified"));
+
+    let readme = std::fs::read_to_string(dir.join("README.md"))?;
+    assert!(readme.starts_with(&format!(
+        "# Results\n\n{}\n## 2022 Results\n\n| Day | Part 1 | Part 2 |\n| :---: | :---: | :---: |\n\
+         | [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |\n\
+         | [Day 2](https://adventofcode.com/2022/day/2) | ⭐ |   |\n\
+         | [Day 3](https://adventofcode.com/2022/day/3) |   |   |\n",
+        MARKER
+    )));
+    assert!(readme.ends_with(&format!(
+        "| [Day 25](https://adventofcode.com/2022/day/25) |   |   |\n{}\n\nThe end\n",
+        MARKER
+    )));
+    Ok(())
+}
+
+#[test]
+fn runtime_column() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("times")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["stars", "--times", "--dir"]).arg(&dir);
+    cmd.assert().success();
+
+    let readme = std::fs::read_to_string(dir.join("README.md"))?;
+    assert!(readme.contains("| Day | Part 1 | Part 2 | Time |\n| :---: | :---: | :---: | ---: |\n"));
+    assert!(predicate::str::is_match(
+        r"\| \[Day 1\]\(https://adventofcode.com/2022/day/1\) \| ⭐ \| ⭐ \| [0-9.]+[µm]?s \|\n"
+    )?
+    .eval(&readme));
+    assert!(readme.contains("| [Day 3](https://adventofcode.com/2022/day/3) |   |   | - |\n"));
+    Ok(())
+}
+
+#[test]
+fn timed_out_parts() -> Result<(), Box<dyn std::error::Error>> {
+    // The first part of day 16 is quick, the second one is not
+    let dir = workspace("timeout")?;
+    std::fs::create_dir_all(dir.join("day16"))?;
+    std::fs::copy("../day16/puzzle.txt", dir.join("day16").join("puzzle.txt"))?;
+    let answers = std::fs::read_to_string(dir.join("answers.toml"))?;
+    std::fs::write(
+        dir.join("answers.toml"),
+        answers + "\n[day16.puzzle]\npart1 = 2181\npart2 = 2824\n",
+    )?;
+
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["stars", "--timeout", "2", "--dir"]).arg(&dir);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Day 16 part 2: timed out"))
+        .stdout(predicate::str::contains("4 of 50 stars verified"));
+
+    let readme = std::fs::read_to_string(dir.join("README.md"))?;
+    assert!(readme.contains("| [Day 16](https://adventofcode.com/2022/day/16) | ⭐ |   |\n"));
+    Ok(())
+}
+
+#[test]
+fn missing_table() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("missing")?;
+    std::fs::write(dir.join("README.md"), "# Results\n")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["stars", "--dir"]).arg(&dir);
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("has no table between two"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("README.md"))?,
+        "# Results\n"
+    );
+    Ok(())
+}
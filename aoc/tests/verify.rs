@@ -0,0 +1,107 @@
+use assert_cmd::prelude::*;
+use predicates::prelude::*;
+use std::{path::PathBuf, process::Command};
+
+fn answers_file(name: &str, content: &str) -> Result<PathBuf, std::io::Error> {
+    let path = std::env::temp_dir().join(format!("aoc-{}-{}.toml", name, std::process::id()));
+    std::fs::write(&path, content)?;
+    Ok(path)
+}
+
+#[test]
+fn recorded_answers() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "5", "--dir", ".."]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            r"\| 5   \| puzzle        \| ok     \| ok     \|",
+        )?)
+        .stdout(predicate::str::is_match(
+            r"\| 5   \| simple_puzzle \| ok     \| ok     \|",
+        )?)
+        .stdout(predicate::str::contains(
+            "Verified 2 inputs: 0 mismatches, 0 missing answers",
+        ));
+    Ok(())
+}
+
+#[test]
+fn mismatch() -> Result<(), Box<dyn std::error::Error>> {
+    let answers = answers_file("mismatch", "[day01.puzzle]\npart1 = 1\npart2 = 199628\n")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "1", "--dir", ".."])
+        .arg("--answers")
+        .arg(&answers);
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("expected 1, got 67633"))
+        .stdout(predicate::str::contains("1 mismatches"));
+    Ok(())
+}
+
+#[test]
+fn missing_answers() -> Result<(), Box<dyn std::error::Error>> {
+    let answers = answers_file("missing", "[day02.puzzle]\npart1 = 14069\n")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "2", "--dir", ".."])
+        .arg("--answers")
+        .arg(&answers);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("missing, got 12411"))
+        .stdout(predicate::str::contains("3 missing answers"))
+        .stdout(predicate::str::contains(
+            "[day02.puzzle]\npart2 = 12411\ntime_ms = ",
+        ))
+        .stdout(predicate::str::contains(
+            "[day02.simple_puzzle]\npart1 = 15\npart2 = 12\n",
+        ));
+    Ok(())
+}
+
+#[test]
+fn timing_regression() -> Result<(), Box<dyn std::error::Error>> {
+    let answers = answers_file(
+        "regression",
+        "[day11.puzzle]\npart1 = 112815\npart2 = 25738411485\ntime_ms = 0.001\n",
+    )?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "11", "--dir", ".."])
+        .arg("--answers")
+        .arg(&answers);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("regressed from 1.00µs"))
+        .stdout(predicate::str::contains("1 timing regressions"))
+        .stdout(predicate::str::contains(
+            "warning: timing regressions only fail with --strict-timing",
+        ));
+
+    // Only fails when asked to, as the recorded times depend on the machine
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "11", "--dir", "..", "--strict-timing"])
+        .arg("--answers")
+        .arg(&answers);
+    cmd.assert()
+        .code(1)
+        .stdout(predicate::str::contains("1 timing regressions"))
+        .stdout(predicate::str::contains("warning").not());
+    Ok(())
+}
+
+#[test]
+fn invalid_registry() -> Result<(), Box<dyn std::error::Error>> {
+    let answers = answers_file("invalid", "[day01.puzzle]\npart3 = 1\n")?;
+    let mut cmd = Command::cargo_bin("aoc")?;
+    cmd.args(["verify", "1", "--dir", ".."])
+        .arg("--answers")
+        .arg(&answers);
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "error: expected part1, part2, time_ms, part1_wrong or part2_wrong, found 'part3'",
+        ))
+        .stderr(predicate::str::contains("--> line 2, column 1"));
+    Ok(())
+}
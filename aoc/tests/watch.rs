@@ -0,0 +1,154 @@
+use assert_cmd::prelude::*;
+use std::{
+    io::Read,
+    os::unix::fs::PermissionsExt,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+// Stands in for cargo: builds unless there is a build_error.txt, which it fails with, and runs
+// by printing the input given after `--input`, which holds the day's JSON, failing with the
+// input as the error if it does not look like JSON
+const FAKE_CARGO: &str = "#!/bin/sh
+if [ \"$1\" = build ]; then
+    [ -f build_error.txt ] && cat build_error.txt >&2 && exit 101
+    exit 0
+fi
+while [ \"$1\" != \"--input\" ]; do shift; done
+case \"$(cat \"$2\")\" in
+    '{'*) cat \"$2\" ;;
+    *) cat \"$2\" >&2; exit 1 ;;
+esac
+";
+
+fn answers(part1: &str, part2: &str) -> String {
+    format!(
+        "{{\"day\":1,\"part1\":{},\"part2\":{},\"timings\":{{\"parse_ms\":0.1,\"part1_ms\":0.1,\"part2_ms\":0.1}}}}\n",
+        part1, part2
+    )
+}
+
+fn workspace(name: &str) -> Result<PathBuf, std::io::Error> {
+    let dir = std::env::temp_dir().join(format!("aoc-watch-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("day01").join("src"))?;
+    std::fs::write(dir.join("day01").join("src").join("lib.rs"), "")?;
+    let cargo = dir.join("cargo.sh");
+    std::fs::write(&cargo, FAKE_CARGO)?;
+    std::fs::set_permissions(&cargo, std::fs::Permissions::from_mode(0o755))?;
+    Ok(dir)
+}
+
+// Watches the workspace, calls `change` once the first run is done and returns what was printed
+// until the watcher is stopped
+fn watch(dir: &Path, change: impl FnOnce()) -> Result<String, Box<dyn std::error::Error>> {
+    let mut child = Command::cargo_bin("aoc")?
+        .args(["watch", "1", "--interval", "0.1", "--dir"])
+        .arg(dir)
+        .env("CARGO", dir.join("cargo.sh"))
+        .stdout(Stdio::piped())
+        .spawn()?;
+    thread::sleep(Duration::from_millis(1000));
+    change();
+    thread::sleep(Duration::from_millis(1000));
+    child.kill()?;
+
+    let mut stdout = String::new();
+    child
+        .stdout
+        .take()
+        .expect("Should capture stdout")
+        .read_to_string(&mut stdout)?;
+    child.wait()?;
+    Ok(stdout)
+}
+
+#[test]
+fn shows_changed_answers() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("changes")?;
+    let day = dir.join("day01");
+    std::fs::write(day.join("simple_puzzle.txt"), answers("24000", "45000"))?;
+    std::fs::write(day.join("puzzle.txt"), answers("1", "null"))?;
+
+    let stdout = watch(&dir, || {
+        std::fs::write(day.join("puzzle.txt"), answers("71924", "null"))
+            .expect("Should write the input");
+    })?;
+    let runs: Vec<&str> = stdout.split("Changed: ").skip(1).collect();
+    assert_eq!(runs.len(), 2, "{}", stdout);
+    assert!(runs[0].contains("simple_puzzle.txt"));
+    assert!(runs[0].contains("src/lib.rs"));
+    assert!(runs[0].find("24000") < runs[0].find("| 1 "), "{}", runs[0]);
+
+    assert!(runs[1].starts_with(&day.join("puzzle.txt").display().to_string()));
+    assert!(runs[1].contains("| 24000 "), "{}", runs[1]);
+    assert!(runs[1].contains("71924 (was 1)"), "{}", runs[1]);
+    assert!(!runs[1].contains("(was -)"), "{}", runs[1]);
+    Ok(())
+}
+
+#[test]
+fn failing_example_skips_puzzle() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("failure")?;
+    let day = dir.join("day01");
+    std::fs::write(day.join("simple_puzzle.txt"), answers("24000", "45000"))?;
+    std::fs::write(day.join("puzzle.txt"), answers("71924", "null"))?;
+
+    let stdout = watch(&dir, || {
+        std::fs::write(
+            day.join("simple_puzzle.txt"),
+            "error: line 1: expected a number",
+        )
+        .expect("Should write the input");
+    })?;
+    let runs: Vec<&str> = stdout.split("Changed: ").skip(1).collect();
+    assert_eq!(runs.len(), 2, "{}", stdout);
+    assert!(runs[0].contains("71924"));
+    assert!(!runs[1].contains("71924"), "{}", runs[1]);
+    assert!(
+        runs[1].contains("simple_puzzle failed:\nerror: line 1: expected a number"),
+        "{}",
+        runs[1]
+    );
+    Ok(())
+}
+
+#[test]
+fn build_failure() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = workspace("build")?;
+    let day = dir.join("day01");
+    std::fs::write(day.join("simple_puzzle.txt"), answers("24000", "45000"))?;
+
+    let stdout = watch(&dir, || {
+        std::fs::write(
+            dir.join("build_error.txt"),
+            "error[E0425]: cannot find value `x`",
+        )
+        .expect("Should write the build error");
+        std::fs::write(day.join("src").join("lib.rs"), "x").expect("Should change the sources");
+    })?;
+    let runs: Vec<&str> = stdout.split("Changed: ").skip(1).collect();
+    assert_eq!(runs.len(), 2, "{}", stdout);
+    assert!(
+        runs[1].contains("Build failed:\nerror[E0425]: cannot find value `x`"),
+        "{}",
+        runs[1]
+    );
+    assert!(!runs[1].contains("simple_puzzle failed"), "{}", runs[1]);
+    assert!(!runs[1].contains("24000"), "{}", runs[1]);
+    Ok(())
+}
+
+#[test]
+fn watch_usage() -> Result<(), Box<dyn std::error::Error>> {
+    for args in [
+        vec!["watch"],
+        vec!["watch", "26"],
+        vec!["watch", "1", "--interval", "0"],
+    ] {
+        Command::cargo_bin("aoc")?.args(args).assert().code(2);
+    }
+    Ok(())
+}
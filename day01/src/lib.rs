@@ -0,0 +1,75 @@
+use utils::{
+    generate::{Generate, Rng},
+    Input, ParseError, Solution,
+};
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: usize = 1;
+
+    type Input = Vec<Vec<u32>>;
+    // Sums of many large calories do not fit the type of a single one
+    type Part1 = u64;
+    type Part2 = u64;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        parse_numbers_in_blocks(text)
+    }
+
+    fn part1(numbers_per_block: &Self::Input) -> Self::Part1 {
+        get_most_calories(numbers_per_block)
+    }
+
+    fn part2(numbers_per_block: &Self::Input) -> Self::Part2 {
+        get_top_three_calories(numbers_per_block)
+    }
+}
+
+// The size is the number of elves
+impl Generate for Day01 {
+    const SIZE: usize = 250;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let elves: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let items = rng.between(1, 15);
+                (0..items)
+                    .map(|_| format!("{}\n", rng.between(1000, 60000)))
+                    .collect()
+            })
+            .collect();
+        elves.join("\n")
+    }
+}
+
+// Blocks are separated by blank lines, surrounding whitespace of the numbers is ignored
+fn parse_numbers_in_blocks(text: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let input = Input::new(text).dedent();
+    let blocks = input
+        .blocks()
+        .map(|(first_line, block)| block.numbers().map_err(|e| e.shifted(first_line)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if blocks.is_empty() {
+        return Err(ParseError::missing(text, "the calories of an elf"));
+    }
+    Ok(blocks)
+}
+
+fn get_most_calories(numbers_per_block: &[Vec<u32>]) -> u64 {
+    numbers_per_block
+        .iter()
+        .map(|vec| vec.iter().map(|&n| u64::from(n)).sum())
+        .max()
+        .expect("Calory list should not be empty")
+}
+
+fn get_top_three_calories(numbers_per_block: &[Vec<u32>]) -> u64 {
+    let mut calories: Vec<u64> = numbers_per_block
+        .iter()
+        .map(|vec| vec.iter().map(|&n| u64::from(n)).sum())
+        .collect();
+
+    calories.sort();
+    calories.into_iter().rev().take(3).sum()
+}
@@ -48,3 +48,19 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn large_calories() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day01")?;
+    cmd.arg("4000000000\n4000000000\n\n1");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Most calories that an elve is carrying: 8000000000",
+        ))
+        .stdout(predicate::str::contains(
+            "Calories by top three elves are: 8000000001",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+use std::str::FromStr;
+
+use utils::{
+    generate::{Generate, Rng},
+    Input, ParseError, Solution,
+};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: usize = 2;
+
+    type Input = Vec<Row>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Input::new(text).dedent().parse_lines()
+    }
+
+    fn part1(rows: &Self::Input) -> Self::Part1 {
+        calculate_score(split_in_rounds(rows))
+    }
+
+    fn part2(rows: &Self::Input) -> Self::Part2 {
+        calculate_score(split_in_rounds_alt(rows))
+    }
+}
+
+// The size is the number of rounds
+impl Generate for Day02 {
+    const SIZE: usize = 2500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|_| {
+                let opp = rng.choose(&["A", "B", "C"]);
+                let own = rng.choose(&["X", "Y", "Z"]);
+                format!("{} {}\n", opp, own)
+            })
+            .collect()
+    }
+}
+
+fn split_in_rounds(rows: &[Row]) -> Vec<Round> {
+    rows.iter().map(Round::new).collect()
+}
+
+fn split_in_rounds_alt(rows: &[Row]) -> Vec<Round> {
+    rows.iter().map(Round::new_alt).collect()
+}
+
+fn calculate_score(rounds: Vec<Round>) -> u32 {
+    rounds.into_iter().map(|r| r.outcome()).sum()
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Choice {
+    Paper,
+    Scissors,
+    Rock,
+}
+
+impl Choice {
+    fn new(encoding: &str, symbols: [&str; 3]) -> Option<Self> {
+        match encoding {
+            x if x == symbols[0] => Some(Self::Rock),
+            x if x == symbols[1] => Some(Self::Paper),
+            x if x == symbols[2] => Some(Self::Scissors),
+            _ => None,
+        }
+    }
+
+    fn score(&self) -> u32 {
+        match *self {
+            Choice::Rock => 1,
+            Choice::Paper => 2,
+            Choice::Scissors => 3,
+        }
+    }
+
+    fn winning_against(&self) -> Self {
+        match *self {
+            Choice::Paper => Choice::Rock,
+            Choice::Scissors => Choice::Paper,
+            Choice::Rock => Choice::Scissors,
+        }
+    }
+
+    fn losing_against(&self) -> Self {
+        match *self {
+            Choice::Paper => Choice::Scissors,
+            Choice::Scissors => Choice::Rock,
+            Choice::Rock => Choice::Paper,
+        }
+    }
+}
+
+// The second column is interpreted differently in both parts,
+// so it is kept as the choice it stands for in the first part
+#[derive(Debug)]
+pub struct Row {
+    opp_choice: Choice,
+    second_column: Choice,
+}
+
+impl FromStr for Row {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut symbols = s.split_whitespace();
+        let opp = symbols
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "A, B or C"))?;
+        let own = symbols
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "X, Y or Z"))?;
+        if let Some(extra) = symbols.next() {
+            return Err(ParseError::new(s, extra, "the end of the line"));
+        }
+
+        Ok(Row {
+            opp_choice: Choice::new(opp, ["A", "B", "C"])
+                .ok_or_else(|| ParseError::new(s, opp, "A, B or C"))?,
+            second_column: Choice::new(own, ["X", "Y", "Z"])
+                .ok_or_else(|| ParseError::new(s, own, "X, Y or Z"))?,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Round {
+    own_choice: Choice,
+    opp_choice: Choice,
+}
+
+impl Round {
+    fn new(row: &Row) -> Self {
+        Self {
+            own_choice: row.second_column.clone(),
+            opp_choice: row.opp_choice.clone(),
+        }
+    }
+
+    // X (Rock) means we need to lose, Y (Paper) a draw and Z (Scissors) to win
+    fn new_alt(row: &Row) -> Self {
+        let opp_choice = row.opp_choice.clone();
+        let own_choice = match row.second_column {
+            Choice::Rock => opp_choice.winning_against(),
+            Choice::Paper => opp_choice.clone(),
+            Choice::Scissors => opp_choice.losing_against(),
+        };
+        Self {
+            own_choice,
+            opp_choice,
+        }
+    }
+
+    fn outcome(&self) -> u32 {
+        let own_score = self.own_choice.score();
+        let round_score = match (&self.own_choice, &self.opp_choice) {
+            (x, y) if y == &x.winning_against() => 6,
+            (x, y) if y == &x.losing_against() => 0,
+            (x, y) if y == x => 3,
+            _ => panic!("Should never have a situation where we don't have DRAW, WIN or LOSS"),
+        };
+        own_score + round_score
+    }
+}
@@ -0,0 +1,141 @@
+use std::collections::HashSet;
+
+use utils::{
+    generate::{Generate, Rng},
+    Input, ParseError, Solution,
+};
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: usize = 3;
+
+    type Input = Vec<String>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        let input = Input::new(text).dedent();
+        let lines: Vec<&str> = input.lines().collect();
+        let rucksacks: Vec<String> = lines
+            .iter()
+            .map(|line| parse_rucksack(input.text(), line))
+            .collect::<Result<_, _>>()?;
+        if !rucksacks.len().is_multiple_of(3) {
+            return Err(ParseError::missing(text, "groups of three rucksacks"));
+        }
+        // Both parts rely on exactly one item being shared
+        for (group, first) in rucksacks.chunks(3).zip(lines.iter().step_by(3)) {
+            if common_items(group).len() != 1 {
+                let expected = "a group sharing exactly one item";
+                return Err(ParseError::new(input.text(), first, expected));
+            }
+        }
+        Ok(rucksacks)
+    }
+
+    fn part1(rucksacks: &Self::Input) -> Self::Part1 {
+        let items = get_items(rucksacks);
+        sum_priorities(&items)
+    }
+
+    fn part2(rucksacks: &Self::Input) -> Self::Part2 {
+        let group_badges = get_common_symbol(rucksacks);
+        sum_priorities(&group_badges)
+    }
+}
+
+// The size is the number of groups of three elves
+impl Generate for Day03 {
+    const SIZE: usize = 100;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut lines = vec![];
+        for _ in 0..size.max(1) {
+            let mut letters: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+            rng.shuffle(&mut letters);
+            // Only the badge is shared between the rucksacks of a group
+            let badge = letters[0];
+            for pool in letters[1..49].chunks(16) {
+                lines.push(generate_rucksack(rng, badge, pool));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+// The first letter of the pool is the item in both compartments
+fn generate_rucksack(rng: &mut Rng, badge: char, pool: &[char]) -> String {
+    let (left_pool, right_pool) = pool[1..].split_at(pool.len() / 2);
+    let len = rng.between(4, 16) as usize;
+    let mut left: Vec<char> = (0..len).map(|_| *rng.choose(left_pool)).collect();
+    let mut right: Vec<char> = (0..len).map(|_| *rng.choose(right_pool)).collect();
+    left[0] = pool[0];
+    right[0] = pool[0];
+    if rng.chance(0.5) {
+        left[1] = badge;
+    } else {
+        right[1] = badge;
+    }
+    rng.shuffle(&mut left);
+    rng.shuffle(&mut right);
+    left.into_iter().chain(right).collect()
+}
+
+fn parse_rucksack(text: &str, line: &str) -> Result<String, ParseError> {
+    if let Some(other) = line.matches(|c: char| !c.is_ascii_alphabetic()).next() {
+        return Err(ParseError::new(text, other, "a letter"));
+    }
+    if !line.len().is_multiple_of(2) {
+        return Err(ParseError::new(
+            text,
+            line,
+            "two compartments of the same size",
+        ));
+    }
+    let (left, right) = line.split_at(line.len() / 2);
+    if common_items(&[left, right]).len() != 1 {
+        let expected = "compartments sharing exactly one item";
+        return Err(ParseError::new(text, line, expected));
+    }
+    Ok(line.to_string())
+}
+
+// The items found in all of the given item lists
+fn common_items(lists: &[impl AsRef<str>]) -> Vec<char> {
+    let mut common: HashSet<char> = lists[0].as_ref().chars().collect();
+    for list in &lists[1..] {
+        let items: HashSet<char> = list.as_ref().chars().collect();
+        common.retain(|c| items.contains(c));
+    }
+    common.into_iter().collect()
+}
+
+fn determine_item(line: &str) -> char {
+    let (left, right) = line.split_at(line.len() / 2);
+    common_items(&[left, right])[0]
+}
+
+fn get_items(rucksacks: &[String]) -> Vec<char> {
+    rucksacks.iter().map(|r| determine_item(r)).collect()
+}
+
+fn intersect_strings(x: &[String]) -> char {
+    common_items(x)[0]
+}
+
+fn get_common_symbol(rucksacks: &[String]) -> Vec<char> {
+    rucksacks.chunks(3).map(intersect_strings).collect()
+}
+
+fn calculate_priority(item: char) -> u32 {
+    if item.is_lowercase() {
+        (item as u32) - ('a' as u32) + 1
+    } else {
+        (item as u32) - ('A' as u32) + 27
+    }
+}
+
+fn sum_priorities(items: &[char]) -> u32 {
+    items.iter().map(|&c| calculate_priority(c)).sum()
+}
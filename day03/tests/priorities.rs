@@ -36,3 +36,33 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn non_ascii_item() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day03")?;
+    cmd.arg("vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLéGLrsFMfFZSrLrFZsS");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("expected a letter, found 'é'"))
+        .stderr(predicate::str::contains("--> line 2, column 15"));
+    Ok(())
+}
+
+#[test]
+fn no_shared_item() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day03")?;
+    cmd.arg("vJrwpWtwJgWrhcsFMMfFFhFp\nabcdef\nPmmdzqPrVvPwwTWBwg");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected compartments sharing exactly one item, found 'abcdef'",
+        ))
+        .stderr(predicate::str::contains("--> line 2, column 1"));
+
+    let mut cmd = Command::cargo_bin("day03")?;
+    cmd.arg("vJrwpWtwJgWrhcsFMMfFFhFp\naBcdBf\nPmmdzqPrVvPwwTWBwg");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "expected a group sharing exactly one item",
+    ));
+    Ok(())
+}
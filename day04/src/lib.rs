@@ -0,0 +1,99 @@
+use utils::{
+    generate::{Generate, Rng},
+    parse_number, Input, ParseError, Solution,
+};
+
+#[derive(Debug, Clone)]
+pub struct Range {
+    begin: u32,
+    end: u32,
+}
+
+impl Range {
+    fn includes(&self, other: &Range) -> bool {
+        other.begin >= self.begin && other.end <= self.end
+    }
+
+    fn overlaps(&self, other: &Range) -> bool {
+        other.begin <= self.end && other.end >= self.begin
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: usize = 4;
+
+    type Input = Vec<(Range, Range)>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        create_range_groups(text)
+    }
+
+    fn part1(range_groups: &Self::Input) -> Self::Part1 {
+        get_num_total_overlaps(range_groups)
+    }
+
+    fn part2(range_groups: &Self::Input) -> Self::Part2 {
+        get_num_overlaps(range_groups)
+    }
+}
+
+// The size is the number of pairs of elves
+impl Generate for Day04 {
+    const SIZE: usize = 1000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut range = || {
+            let begin = rng.between(1, 99);
+            format!("{}-{}", begin, rng.between(begin, 99))
+        };
+        (0..size.max(1))
+            .map(|_| format!("{},{}\n", range(), range()))
+            .collect()
+    }
+}
+
+fn create_range(text: &str, r_str: &str) -> Result<Range, ParseError> {
+    let (begin, end) = r_str
+        .split_once('-')
+        .ok_or_else(|| ParseError::new(text, r_str, "a range like 2-4"))?;
+    Ok(Range {
+        begin: parse_number(text, begin.trim())?,
+        end: parse_number(text, end.trim())?,
+    })
+}
+
+fn split_single_line(text: &str, line: &str) -> Result<(Range, Range), ParseError> {
+    let (left, right) = line
+        .split_once(',')
+        .ok_or_else(|| ParseError::new(text, line, "two ranges separated by a comma"))?;
+    Ok((
+        create_range(text, left.trim())?,
+        create_range(text, right.trim())?,
+    ))
+}
+
+fn create_range_groups(text: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    let input = Input::new(text).dedent();
+    input
+        .lines()
+        .map(|line| split_single_line(input.text(), line))
+        .collect()
+}
+
+fn get_num_total_overlaps(ranges: &[(Range, Range)]) -> u32 {
+    ranges
+        .iter()
+        .map(|(x, y)| (x.includes(y) || y.includes(x)) as u32)
+        .sum()
+}
+
+fn get_num_overlaps(ranges: &[(Range, Range)]) -> u32 {
+    ranges
+        .iter()
+        .map(|(x, y)| (x.overlaps(y) || y.overlaps(x)) as u32)
+        .sum()
+}
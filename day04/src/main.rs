@@ -1,66 +1,24 @@
-use utils::parse_text;
-
-#[derive(Debug, Clone)]
-struct Range {
-    begin: u32,
-    end: u32,
-}
-
-impl Range {
-    fn includes(&self, other: &Range) -> bool {
-        other.begin >= self.begin && other.end <= self.end
-    }
-
-    fn overlaps(&self, other: &Range) -> bool {
-        other.begin <= self.end && other.end >= self.begin
-    }
-}
+use day04::Day04;
+use utils::{Args, Format, Part, Solution};
 
 fn main() {
-    let text = parse_text();
-    let text = text.replace(' ', "");
-    let range_groups = create_range_groups(&text);
-    let total_overlaps = get_num_total_overlaps(&range_groups);
-    let overlaps = get_num_overlaps(&range_groups);
-    println!(
-        "The number of completely overlapping ranges is {}",
-        total_overlaps
-    );
-    println!("The number of overlapping ranges is {}", overlaps);
-}
-
-fn create_range(r_str: &str) -> Range {
-    let endpoints: Vec<u32> = r_str
-        .split('-')
-        .map(|x| x.parse::<u32>().expect("Endpoints should be numbers"))
-        .collect();
-    assert_eq!(endpoints.len(), 2);
-    Range {
-        begin: endpoints[0],
-        end: endpoints[1],
+    let args = Args::parse::<Day04>();
+    if args.format == Format::Json {
+        args.print_json::<Day04>();
+        return;
     }
-}
-
-fn split_single_line(line: &str) -> (Range, Range) {
-    let ranges: Vec<Range> = line.split(',').map(create_range).collect();
-    assert_eq!(ranges.len(), 2);
-    (ranges[0].clone(), ranges[1].clone())
-}
-
-fn create_range_groups(text: &str) -> Vec<(Range, Range)> {
-    text.split('\n').map(split_single_line).collect()
-}
 
-fn get_num_total_overlaps(ranges: &[(Range, Range)]) -> u32 {
-    ranges
-        .iter()
-        .map(|(x, y)| (x.includes(y) || y.includes(x)) as u32)
-        .sum()
-}
+    let range_groups = args.input::<Day04>();
 
-fn get_num_overlaps(ranges: &[(Range, Range)]) -> u32 {
-    ranges
-        .iter()
-        .map(|(x, y)| (x.overlaps(y) || y.overlaps(x)) as u32)
-        .sum()
+    if args.runs(Part::One) {
+        let total_overlaps = Day04::part1(&range_groups);
+        println!(
+            "The number of completely overlapping ranges is {}",
+            total_overlaps
+        );
+    }
+    if args.runs(Part::Two) {
+        let overlaps = Day04::part2(&range_groups);
+        println!("The number of overlapping ranges is {}", overlaps);
+    }
 }
@@ -0,0 +1,248 @@
+use std::str::FromStr;
+
+use utils::{
+    generate::{Generate, Rng},
+    parse_number, Input, ParseError, Solution,
+};
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: usize = 5;
+
+    type Input = (Problem, Vec<Instruction>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        // The crates are aligned with spaces, so the input is not dedented
+        let input = Input::new(text);
+        let mut blocks = input.blocks();
+        let (Some((_, problem)), Some((first_line, instructions))) = (blocks.next(), blocks.next())
+        else {
+            return Err(ParseError::missing(
+                text,
+                "an empty line between the stacks and the instructions",
+            ));
+        };
+        if let Some((_, extra)) = blocks.next() {
+            let expected = "no empty line between the instructions";
+            return Err(ParseError::new(input.text(), extra.text(), expected));
+        }
+
+        let problem: Problem = problem.text().parse()?;
+        let instructions =
+            get_instructions(instructions, &problem).map_err(|e| e.shifted(first_line))?;
+        Ok((problem, instructions))
+    }
+
+    fn part1((problem, instructions): &Self::Input) -> Self::Part1 {
+        let mut problem = problem.clone();
+        problem.execute_instructions(instructions);
+        problem.output()
+    }
+
+    fn part2((problem, instructions): &Self::Input) -> Self::Part2 {
+        let mut problem = problem.clone();
+        problem.execute_instructions_alt(instructions);
+        problem.output()
+    }
+}
+
+// The size is the number of moves, which never empty a stack
+impl Generate for Day05 {
+    const SIZE: usize = 500;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        const STACKS: usize = 9;
+        let crates: Vec<Vec<char>> = (0..STACKS)
+            .map(|_| {
+                // With more crates than stacks, there is always one to move
+                let height = rng.between(2, 8);
+                (0..height)
+                    .map(|_| (b'A' + rng.below(26) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let max_height = crates.iter().map(Vec::len).max().unwrap_or_default();
+
+        let mut text = String::new();
+        for level in (0..max_height).rev() {
+            let row: Vec<String> = crates
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => String::from("   "),
+                })
+                .collect();
+            text += &row.join(" ");
+            text.push('\n');
+        }
+        let numbering: Vec<String> = (1..=STACKS).map(|i| format!(" {} ", i)).collect();
+        text += &numbering.join(" ");
+        text += "\n\n";
+
+        let mut heights: Vec<usize> = crates.iter().map(Vec::len).collect();
+        for _ in 0..size {
+            let from = loop {
+                let from = rng.below(STACKS);
+                if heights[from] > 1 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.below(STACKS - 1)) % STACKS;
+            let amount = rng.between(1, (heights[from] - 1).min(12) as i64) as usize;
+            heights[from] -= amount;
+            heights[to] += amount;
+            text += &format!("move {} from {} to {}\n", amount, from + 1, to + 1);
+        }
+        text
+    }
+}
+
+// Both parts move the same number of crates between the same stacks, so the heights of the
+// stacks are followed to reject moves of more crates than there are
+fn get_instructions(
+    instructions: Input,
+    problem: &Problem,
+) -> Result<Vec<Instruction>, ParseError> {
+    let num_stacks = problem.stacks.len();
+    let mut heights: Vec<usize> = problem.stacks.iter().map(Vec::len).collect();
+    instructions
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let instruction: Instruction = line.parse().map_err(|e: ParseError| e.shifted(i))?;
+            if instruction.from >= num_stacks || instruction.to >= num_stacks {
+                let expected = format!("stacks between 1 and {}", num_stacks);
+                return Err(ParseError::new(line, line.trim(), expected).shifted(i));
+            }
+            if instruction.amount > heights[instruction.from] {
+                let amount = line.split_whitespace().nth(1).unwrap_or(line);
+                let expected = format!(
+                    "at most the {} crates on stack {}",
+                    heights[instruction.from],
+                    instruction.from + 1
+                );
+                return Err(ParseError::new(line, amount, expected).shifted(i));
+            }
+            heights[instruction.from] -= instruction.amount;
+            heights[instruction.to] += instruction.amount;
+            Ok(instruction)
+        })
+        .collect()
+}
+
+pub struct Instruction {
+    from: usize,
+    to: usize,
+    amount: usize,
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens: Vec<&str> = s.split_whitespace().collect();
+        let ["move", amount, "from", from, "to", to] = tokens[..] else {
+            return Err(ParseError::new(
+                s,
+                s.trim(),
+                "move <amount> from <stack> to <stack>",
+            ));
+        };
+
+        // Problem is 1-indexed, here we are 0-indexed
+        let stack = |token: &str| match parse_number::<usize>(s, token)? {
+            0 => Err(ParseError::new(s, token, "a stack number starting at 1")),
+            n => Ok(n - 1),
+        };
+        Ok(Instruction {
+            from: stack(from)?,
+            to: stack(to)?,
+            amount: parse_number(s, amount)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    stacks: Vec<Vec<char>>,
+}
+
+impl FromStr for Problem {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The last line numbers the stacks and is the only one without trimmed trailing spaces
+        let numbering = s
+            .lines()
+            .last()
+            .ok_or_else(|| ParseError::missing(s, "the stacks of crates"))?;
+        let num_stacks = numbering.split_whitespace().count();
+        let mut stacks: Vec<Vec<char>> = vec![Vec::new(); num_stacks];
+
+        for line in s.lines().rev().skip(1) {
+            // Crates sit at fixed byte offsets, which only line up with the letters in ASCII
+            if let Some((start, c)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+                let content = &line[start..start + c.len_utf8()];
+                return Err(ParseError::new(s, content, "a crate letter"));
+            }
+            for (i, stack) in stacks.iter_mut().enumerate() {
+                let index = 4 * i + 1;
+                match line.get(index..index + 1) {
+                    Some(" ") | None => (),
+                    Some(content) if content.chars().all(|c| c.is_ascii_alphabetic()) => {
+                        stack.push(content.chars().next().expect("Content is not empty"))
+                    }
+                    Some(content) => return Err(ParseError::new(s, content, "a crate letter")),
+                }
+            }
+        }
+        Ok(Problem { stacks })
+    }
+}
+
+impl Problem {
+    fn execute_instruction(&mut self, instruction: &Instruction) {
+        let iters = instruction.amount;
+        let from = instruction.from;
+        let to = instruction.to;
+
+        for _ in 0..iters {
+            let val = self.stacks[from].pop().expect("This should be possible");
+            self.stacks[to].push(val);
+        }
+    }
+
+    fn execute_instruction_alt(&mut self, instruction: &Instruction) {
+        let amount = instruction.amount;
+        let from = instruction.from;
+        let to = instruction.to;
+
+        let end_index = self.stacks[from].len() - amount;
+
+        let mut items = self.stacks[from].drain(end_index..).collect();
+        self.stacks[to].append(&mut items);
+    }
+
+    fn execute_instructions(&mut self, instructions: &[Instruction]) {
+        for instruction in instructions {
+            self.execute_instruction(instruction);
+        }
+    }
+
+    fn execute_instructions_alt(&mut self, instructions: &[Instruction]) {
+        for instruction in instructions {
+            self.execute_instruction_alt(instruction);
+        }
+    }
+
+    // Empty stacks have no crate on top and are left out
+    fn output(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .collect()
+    }
+}
@@ -39,3 +39,87 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn invalid_instruction() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day05")?;
+    cmd.arg(
+        "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from one to 3",
+    );
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("expected a number, found 'one'"))
+        .stderr(predicate::str::contains("--> line 7, column 13"))
+        .stderr(predicate::str::contains("panicked").not());
+
+    Ok(())
+}
+
+#[test]
+fn too_many_crates() -> Result<(), Box<dyn std::error::Error>> {
+    // After the first move, there are only two crates left on the second stack
+    let mut cmd = Command::cargo_bin("day05")?;
+    cmd.arg(
+        "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 2 to 3",
+    );
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected at most the 2 crates on stack 2, found '3'",
+        ))
+        .stderr(predicate::str::contains("--> line 7, column 6"))
+        .stderr(predicate::str::contains("panicked").not());
+
+    // Stacks may end up empty
+    let mut cmd = Command::cargo_bin("day05")?;
+    cmd.arg(
+        "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 3 from 2 to 3",
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The crates on top are NM"))
+        .stdout(predicate::str::contains(
+            "The alternative crates on top are ND",
+        ));
+    Ok(())
+}
+
+#[test]
+fn non_ascii_crates() -> Result<(), Box<dyn std::error::Error>> {
+    // A letter of two bytes would shift the crates after it out of their columns
+    let mut cmd = Command::cargo_bin("day05")?;
+    cmd.arg(
+        "    [D]    
+[N] [C]    
+[Ä] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1",
+    );
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a crate letter, found 'Ä'",
+        ))
+        .stderr(predicate::str::contains("--> line 3, column 2"))
+        .stderr(predicate::str::contains("panicked").not());
+
+    Ok(())
+}
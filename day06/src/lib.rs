@@ -0,0 +1,70 @@
+use std::collections::HashSet;
+
+use utils::{
+    generate::{Generate, Rng},
+    Input, ParseError, Solution,
+};
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: usize = 6;
+
+    type Input = String;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        let stream = Input::new(text).text().trim();
+        if stream.is_empty() {
+            return Err(ParseError::missing(text, "a datastream"));
+        }
+        Ok(stream.to_string())
+    }
+
+    fn part1(text: &Self::Input) -> Self::Part1 {
+        find_marker_pos(text, 4)
+    }
+
+    fn part2(text: &Self::Input) -> Self::Part2 {
+        find_marker_pos(text, 14)
+    }
+}
+
+// The size is the length of the datastream
+impl Generate for Day06 {
+    const SIZE: usize = 4096;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let alphabet: Vec<char> = ('a'..='z').collect();
+        // Too few letters for a message marker, which is put in at a random position
+        let mut stream: Vec<char> = (0..size.max(14))
+            .map(|_| *rng.choose(&alphabet[..13]))
+            .collect();
+        let mut marker = alphabet;
+        rng.shuffle(&mut marker);
+        let start = rng.below(stream.len() - 13);
+        stream[start..start + 14].copy_from_slice(&marker[..14]);
+        stream.into_iter().collect()
+    }
+}
+
+fn is_unique(sequence: impl Iterator<Item = char>) -> bool {
+    let mut already_seen: HashSet<char> = HashSet::new();
+    for c in sequence {
+        if already_seen.contains(&c) {
+            return false;
+        }
+        already_seen.insert(c);
+    }
+    true
+}
+
+// `None` if the datastream has no marker
+fn find_marker_pos(text: &str, num_distinct: usize) -> Option<usize> {
+    text.chars()
+        .collect::<Vec<char>>()
+        .windows(num_distinct)
+        .position(|chunk| is_unique(chunk.iter().cloned()))
+        .map(|pos| pos + num_distinct)
+}
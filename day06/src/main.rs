@@ -1,36 +1,30 @@
-use std::collections::HashSet;
-
-use utils::parse_text;
+use day06::Day06;
+use utils::{Args, Format, Part, Solution};
 
 fn main() {
-    let text = parse_text();
-    let packet_pos = find_marker_pos(&text, 4);
-    let message_pos = find_marker_pos(&text, 14);
-    println!("The first position after a packet marker is {}", packet_pos);
-    println!(
-        "The first position after a message marker is {}",
-        message_pos
-    );
-}
+    let args = Args::parse::<Day06>();
+    if args.format == Format::Json {
+        args.print_json::<Day06>();
+        return;
+    }
+
+    let text = args.input::<Day06>();
 
-fn is_unique(sequence: impl Iterator<Item = char>) -> bool {
-    let mut already_seen: HashSet<char> = HashSet::new();
-    for c in sequence {
-        if already_seen.contains(&c) {
-            return false;
+    if args.runs(Part::One) {
+        match Day06::part1(&text) {
+            Some(packet_pos) => {
+                println!("The first position after a packet marker is {}", packet_pos)
+            }
+            None => println!("There is no packet marker"),
+        }
+    }
+    if args.runs(Part::Two) {
+        match Day06::part2(&text) {
+            Some(message_pos) => println!(
+                "The first position after a message marker is {}",
+                message_pos
+            ),
+            None => println!("There is no message marker"),
         }
-        already_seen.insert(c);
     }
-    true
-}
-
-fn find_marker_pos(text: &str, num_distinct: usize) -> usize {
-    text.chars()
-        .collect::<Vec<char>>()
-        .windows(num_distinct)
-        .enumerate()
-        .find(|(_, chunk)| is_unique(chunk.iter().cloned()))
-        .expect("There should be a start marker")
-        .0
-        + num_distinct
 }
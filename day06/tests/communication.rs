@@ -92,3 +92,16 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn no_marker() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day06")?;
+    cmd.arg("abcabcabcd");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The first position after a packet marker is 10",
+        ))
+        .stdout(predicate::str::contains("There is no message marker"));
+    Ok(())
+}
@@ -0,0 +1,308 @@
+use std::{cell::RefCell, cmp::min, collections::HashSet, rc::Rc, str::FromStr};
+
+use utils::{
+    generate::{Generate, Rng},
+    parse_number, Input, ParseError, Solution,
+};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: usize = 7;
+
+    type Input = Rc<RefCell<Directory>>;
+    type Part1 = usize;
+    type Part2 = Option<usize>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        let root = Rc::new(RefCell::new(Directory {
+            name: String::from("/"),
+            parent: None,
+            files: Vec::new(),
+            dirs: Vec::new(),
+        }));
+
+        create_structure(root.clone(), text)?;
+        Ok(root)
+    }
+
+    fn part1(root: &Self::Input) -> Self::Part1 {
+        sum_tree(root.clone())
+    }
+
+    fn part2(root: &Self::Input) -> Self::Part2 {
+        // Nothing has to be deleted if enough space is free already
+        let required = (NEEDED_SPACE + sum_size(root.clone())).checked_sub(DISK_SPACE)?;
+        Some(find_smallest_delete(required, usize::MAX, root.clone()))
+    }
+}
+
+const DISK_SPACE: usize = 70000000;
+const NEEDED_SPACE: usize = 30000000;
+
+// The size is the number of directories. More than 40000000 is always in use, so that
+// a directory has to be deleted in the second part
+impl Generate for Day07 {
+    const SIZE: usize = 180;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let num_dirs = size.max(1);
+        let mut children = vec![vec![]; num_dirs];
+        for dir in 1..num_dirs {
+            children[rng.below(dir)].push(dir);
+        }
+
+        // The root holds at least one file, so that something is in use
+        let weights: Vec<Vec<usize>> = (0..num_dirs)
+            .map(|dir| {
+                let files = rng.between((dir == 0) as i64, 4);
+                (0..files).map(|_| rng.between(1, 100) as usize).collect()
+            })
+            .collect();
+        let total_weight: usize = weights.iter().flatten().sum::<usize>().max(1);
+        let used = rng.between(42_000_000, 50_000_000) as usize;
+        let file_sizes: Vec<Vec<usize>> = weights
+            .iter()
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|w| (w * used / total_weight).max(1))
+                    .collect()
+            })
+            .collect();
+
+        let mut text = String::from("$ cd /\n");
+        write_directory(rng, 0, &children, &file_sizes, &mut text);
+        text
+    }
+}
+
+fn write_directory(
+    rng: &mut Rng,
+    dir: usize,
+    children: &[Vec<usize>],
+    file_sizes: &[Vec<usize>],
+    text: &mut String,
+) {
+    let mut names = HashSet::new();
+    let mut name = |rng: &mut Rng, extension: bool| loop {
+        let mut name: String = (0..rng.between(3, 8))
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect();
+        if extension && rng.chance(0.5) {
+            name.push('.');
+            name.extend((0..3).map(|_| (b'a' + rng.below(26) as u8) as char));
+        }
+        if names.insert(name.clone()) {
+            break name;
+        }
+    };
+
+    let mut entries: Vec<(Option<usize>, String)> = vec![];
+    for &child in &children[dir] {
+        entries.push((Some(child), name(rng, false)));
+    }
+    for &file_size in &file_sizes[dir] {
+        entries.push((None, format!("{} {}", file_size, name(rng, true))));
+    }
+    rng.shuffle(&mut entries);
+
+    text.push_str("$ ls\n");
+    for (child, name) in &entries {
+        match child {
+            Some(_) => text.push_str(&format!("dir {}\n", name)),
+            None => text.push_str(&format!("{}\n", name)),
+        }
+    }
+    for (child, name) in entries {
+        if let Some(child) = child {
+            text.push_str(&format!("$ cd {}\n", name));
+            write_directory(rng, child, children, file_sizes, text);
+            text.push_str("$ cd ..\n");
+        }
+    }
+}
+
+fn create_structure(root: Rc<RefCell<Directory>>, text: &str) -> Result<(), ParseError> {
+    let mut current_dir = root.clone();
+    let mut used = 0;
+
+    for (i, line) in Input::new(text).dedent().lines().enumerate() {
+        let invalid = |expected| ParseError::new(line, line, expected).shifted(i);
+        match line.parse::<Line>().map_err(|e| e.shifted(i))? {
+            Line::Command(Command::ChangeUp) => {
+                let parent = current_dir
+                    .borrow()
+                    .parent
+                    .clone()
+                    .ok_or_else(|| invalid("a directory with a parent"))?;
+                current_dir = parent;
+            }
+            Line::Command(Command::ChangeDown(x)) => {
+                current_dir = {
+                    let c = &current_dir.borrow().dirs;
+                    let changed_dir = c
+                        .iter()
+                        .find(|&y| y.borrow().name == x)
+                        .ok_or_else(|| invalid("a directory that was listed before"))?;
+                    changed_dir.clone()
+                }
+            }
+            Line::Command(Command::ChangeRoot) => current_dir = root.clone(),
+            Line::Command(Command::List) => (),
+            Line::File(file) => {
+                if file.size > DISK_SPACE - used {
+                    return Err(invalid("files that fit on the disk"));
+                }
+                used += file.size;
+                current_dir.borrow_mut().files.push(file)
+            }
+            Line::Directory(mut dir) => {
+                dir.parent = Some(Rc::clone(&current_dir));
+                current_dir
+                    .borrow_mut()
+                    .dirs
+                    .push(Rc::new(RefCell::new(dir)));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn sum_size(root: Rc<RefCell<Directory>>) -> usize {
+    let mut own_size = root.borrow().files.iter().map(|f| f.size).sum();
+    if root.borrow().dirs.is_empty() {
+        own_size
+    } else {
+        for dir in &root.borrow().dirs {
+            own_size += sum_size(dir.clone());
+        }
+        own_size
+    }
+}
+
+fn sum_tree(root: Rc<RefCell<Directory>>) -> usize {
+    let own_size = sum_size(root.clone());
+    let mut result = 0;
+    for dir in &root.borrow().dirs {
+        result += sum_tree(dir.clone());
+    }
+    if own_size > 100000 {
+        result
+    } else {
+        result + own_size
+    }
+}
+
+fn find_smallest_delete(
+    min_requried_size: usize,
+    current_min: usize,
+    root: Rc<RefCell<Directory>>,
+) -> usize {
+    let own_size = sum_size(root.clone());
+
+    if own_size < min_requried_size {
+        return current_min;
+    }
+
+    if root.borrow().dirs.is_empty() {
+        min(own_size, current_min)
+    } else {
+        let mut new_min = usize::MAX;
+        for dir in &root.borrow().dirs {
+            let child_min =
+                find_smallest_delete(min_requried_size, min(current_min, own_size), dir.clone());
+            new_min = min(new_min, child_min);
+        }
+        new_min
+    }
+}
+
+// A single line of the terminal output
+enum Line {
+    Command(Command),
+    File(File),
+    Directory(Directory),
+}
+
+impl FromStr for Line {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("$ ") {
+            s.parse().map(Line::Command)
+        } else if s.starts_with("dir ") {
+            s.parse().map(Line::Directory)
+        } else {
+            s.parse().map(Line::File)
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Command {
+    ChangeUp,
+    ChangeDown(String),
+    ChangeRoot,
+    List,
+}
+
+impl FromStr for Command {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let args: Vec<&str> = s.trim_start_matches("$ ").split_whitespace().collect();
+        match args[..] {
+            ["ls"] => Ok(Command::List),
+            ["cd", "/"] => Ok(Command::ChangeRoot),
+            ["cd", ".."] => Ok(Command::ChangeUp),
+            ["cd", x] => Ok(Command::ChangeDown(String::from(x))),
+            ["cd"] => Err(ParseError::missing(s, "a directory to change into")),
+            _ => Err(ParseError::new(s, s, "the command ls or cd <directory>")),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct File {
+    size: usize,
+    _name: String,
+}
+
+impl FromStr for File {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (size, name) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(s, s, "a file like 1234 name.txt"))?;
+        Ok(File {
+            size: parse_number(s, size)?,
+            _name: String::from(name),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct Directory {
+    name: String,
+    parent: Option<Rc<RefCell<Directory>>>,
+    files: Vec<File>,
+    dirs: Vec<Rc<RefCell<Directory>>>,
+}
+
+impl FromStr for Directory {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s
+            .strip_prefix("dir ")
+            .ok_or_else(|| ParseError::new(s, s, "a directory like dir name"))?;
+        Ok(Directory {
+            name: String::from(name),
+            parent: None,
+            files: Vec::new(),
+            dirs: Vec::new(),
+        })
+    }
+}
@@ -51,3 +51,26 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn disk_space() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day07")?;
+    cmd.arg("$ cd /\n$ ls\n1000 a.txt");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The sum of directories is 1000"))
+        .stdout(predicate::str::contains(
+            "There is enough free space without a delete",
+        ));
+
+    let mut cmd = Command::cargo_bin("day07")?;
+    cmd.arg("$ cd /\n$ ls\n40000000 a.txt\n40000000 b.txt");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected files that fit on the disk, found '40000000 b.txt'",
+        ))
+        .stderr(predicate::str::contains("--> line 4, column 1"));
+
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::{cmp::max, ops::ControlFlow, str::FromStr};
+
+use utils::{
+    generate::{Generate, Rng},
+    grid::Grid,
+    Input, ParseError, Solution,
+};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: usize = 8;
+
+    type Input = Forest;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        text.parse::<Forest>()
+    }
+
+    fn part1(forest: &Self::Input) -> Self::Part1 {
+        forest.get_number_visible()
+    }
+
+    fn part2(forest: &Self::Input) -> Self::Part2 {
+        forest.max_scenic_score()
+    }
+}
+
+// The size is the width and height of the forest
+impl Generate for Day08 {
+    const SIZE: usize = 99;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        (0..size)
+            .map(|_| {
+                let mut row: String = (0..size)
+                    .map(|_| (b'0' + rng.below(10) as u8) as char)
+                    .collect();
+                row.push('\n');
+                row
+            })
+            .collect()
+    }
+}
+
+// Tree heights, addressed as (x, y)
+pub struct Forest {
+    trees: Grid<u8>,
+}
+
+impl FromStr for Forest {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trees = Input::new(s)
+            .dedent()
+            .grid("a digit", |c| c.to_digit(10).map(|height| height as u8))?;
+        Ok(Forest { trees })
+    }
+}
+
+impl Forest {
+    fn is_visible(&self, x: usize, y: usize) -> bool {
+        let own_value = self.trees[(x, y)];
+
+        let trees = self.get_directional_trees(x, y);
+
+        trees.iter().any(|x| x.iter().all(|&h| h < own_value))
+    }
+
+    // The trees left, right, above and below the given one, each ordered away from it
+    fn get_directional_trees(&self, x: usize, y: usize) -> [Vec<u8>; 4] {
+        let row = self.trees.row(y);
+        let left = row[..x].iter().rev().copied().collect();
+        let right = row[x + 1..].to_vec();
+        let top = self.trees.column(x).take(y).rev().copied().collect();
+        let bottom = self.trees.column(x).skip(y + 1).copied().collect();
+
+        [left, right, top, bottom]
+    }
+
+    fn scenic_score(&self, x: usize, y: usize) -> usize {
+        let own_value = self.trees[(x, y)];
+
+        let trees = self.get_directional_trees(x, y);
+
+        let folded = trees.iter().map(|x| {
+            x.iter().try_fold(0, |acc, cur| {
+                if cur < &own_value {
+                    ControlFlow::Continue(acc + 1)
+                } else {
+                    ControlFlow::Break(acc + 1)
+                }
+            })
+        });
+
+        folded
+            .map(|x| match x {
+                ControlFlow::Continue(a) => a,
+                ControlFlow::Break(a) => a,
+            })
+            .product()
+    }
+
+    fn max_scenic_score(&self) -> usize {
+        let mut result = 0;
+        for x in 1..(self.trees.width() - 1) {
+            for y in 1..(self.trees.height() - 1) {
+                result = max(result, self.scenic_score(x, y));
+            }
+        }
+        result
+    }
+
+    fn get_number_visible(&self) -> usize {
+        let (width, height) = (self.trees.width(), self.trees.height());
+        let mut result = 2 * (height + width) - 4;
+        for x in 1..(width - 1) {
+            for y in 1..(height - 1) {
+                result += self.is_visible(x, y) as usize;
+            }
+        }
+        result
+    }
+}
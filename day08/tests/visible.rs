@@ -37,3 +37,17 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn json() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day08")?;
+    cmd.args(["--format", "json", "30373\n25512\n65332\n33549\n35390"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "{\"day\":8,\"part1\":21,\"part2\":8,\"timings\":{\"parse_ms\":",
+        ))
+        .stdout(predicate::str::contains("The maximum scenic score").not());
+
+    Ok(())
+}
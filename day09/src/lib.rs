@@ -0,0 +1,129 @@
+use std::{collections::HashSet, str::FromStr};
+
+use utils::{
+    generate::{Generate, Rng},
+    geom::{Direction, Point2},
+    parse_number, Input, ParseError, Solution,
+};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: usize = 9;
+
+    type Input = Vec<Instruction>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Input::new(text).dedent().parse_lines()
+    }
+
+    fn part1(instructions: &Self::Input) -> Self::Part1 {
+        let mut problem = Problem::new(instructions.clone(), 2);
+        problem.execute_instructions();
+        problem.get_unique_pos()
+    }
+
+    fn part2(instructions: &Self::Input) -> Self::Part2 {
+        let mut problem = Problem::new(instructions.clone(), 10);
+        problem.execute_instructions();
+        problem.get_unique_pos()
+    }
+}
+
+// The size is the number of moves of the head
+impl Generate for Day09 {
+    const SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.choose(&["U", "D", "L", "R"]),
+                    rng.between(1, 20)
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    direction: Direction,
+    steps: usize,
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, steps) = s
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| ParseError::new(s, s.trim(), "a direction and a step count"))?;
+        let direction = match direction {
+            "L" => Direction::Left,
+            "R" => Direction::Right,
+            "U" => Direction::Up,
+            "D" => Direction::Down,
+            _ => return Err(ParseError::new(s, direction, "one of L, R, U or D")),
+        };
+
+        Ok(Self {
+            direction,
+            steps: parse_number(s, steps)?,
+        })
+    }
+}
+
+type Point = Point2<isize>;
+
+#[derive(Clone)]
+struct Problem {
+    instructions: Vec<Instruction>,
+    pos_visited: HashSet<Point>,
+    positions: Vec<Point>,
+}
+
+impl Problem {
+    fn new(instructions: Vec<Instruction>, num_knots: usize) -> Self {
+        Self {
+            instructions,
+            pos_visited: HashSet::from([Point::default()]),
+            positions: vec![Point::default(); num_knots],
+        }
+    }
+
+    fn step(&mut self, direction: Direction) {
+        let head = &mut self.positions[0];
+        *head = head.step(direction);
+
+        let mut prev = *head;
+        for knot in self.positions.iter_mut().skip(1) {
+            // A knot that is no longer touching the previous one follows it by one step,
+            // diagonally if they are not in the same row or column
+            let diff = *knot - prev;
+            if diff.chebyshev(Point::default()) > 1 {
+                *knot -= Point::new(diff.x.signum(), diff.y.signum());
+            }
+            prev = *knot;
+        }
+
+        self.pos_visited
+            .insert(*self.positions.last().expect("Must be set"));
+    }
+
+    fn execute_instructions(&mut self) {
+        for instruction in self.instructions.clone() {
+            for _ in 0..instruction.steps {
+                self.step(instruction.direction);
+            }
+        }
+    }
+
+    fn get_unique_pos(&self) -> usize {
+        self.pos_visited.len()
+    }
+}
@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+use utils::{
+    animate::{Animate, Frame},
+    generate::{Generate, Rng},
+    parse_number, Input, ParseError, Part, Solution,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
+    const ANIMATED: bool = true;
+
+    type Input = Vec<Instruction>;
+    type Part1 = isize;
+    type Part2 = String;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        let mut instructions: Vec<Instruction> = Input::new(text).dedent().parse_lines()?;
+        instructions.reverse();
+        Ok(instructions)
+    }
+
+    fn part1(instructions: &Self::Input) -> Self::Part1 {
+        let mut clock = Clock::new(instructions.clone());
+        clock.execute_instructions();
+        clock.get_signal_strength()
+    }
+
+    fn part2(instructions: &Self::Input) -> Self::Part2 {
+        let mut clock = Clock::new(instructions.clone());
+        clock.execute_instructions();
+        clock.get_image()
+    }
+}
+
+impl Animate for Day10 {
+    type Frame = Screen;
+
+    // Both parts run the same program, the image is only the answer of the second one
+    fn frames(instructions: &Self::Input, _part: Part) -> impl Iterator<Item = Screen> + '_ {
+        let mut clock = Clock::new(instructions.clone());
+        std::iter::from_fn(move || {
+            clock.tick().then(|| Screen {
+                image: clock.img_str.clone(),
+                cycle: clock.cycle,
+                register: clock.register,
+            })
+        })
+    }
+}
+
+// The size is the number of instructions, which keep the sprite around the screen
+impl Generate for Day10 {
+    const SIZE: usize = 140;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut register = 1;
+        let mut text = String::new();
+        for _ in 0..size.max(1) {
+            if rng.chance(0.3) {
+                text.push_str("noop\n");
+                continue;
+            }
+            // Adding 0 would not take two cycles
+            let value = loop {
+                let value = rng.between(-10, 10);
+                if value != 0 && (-1..=40).contains(&(register + value)) {
+                    break value;
+                }
+            };
+            register += value;
+            text.push_str(&format!("addx {}\n", value));
+        }
+        text
+    }
+}
+
+/// The CRT after drawing the pixel of a cycle.
+pub struct Screen {
+    image: String,
+    cycle: usize,
+    register: isize,
+}
+
+impl Frame for Screen {
+    fn draw(&self) -> String {
+        self.image.clone()
+    }
+
+    fn status(&self) -> String {
+        format!("cycle {}, X = {}", self.cycle, self.register)
+    }
+}
+
+#[derive(Clone)]
+pub enum Instruction {
+    NoOp,
+    AddX(i32),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let args: Vec<&str> = s.split_whitespace().collect();
+        match args[..] {
+            ["noop"] => Ok(Self::NoOp),
+            ["addx", number] => Ok(Self::AddX(parse_number(s, number)?)),
+            ["addx"] => Err(ParseError::missing(s, "a number to add")),
+            _ => Err(ParseError::new(s, s.trim(), "noop or addx <number>")),
+        }
+    }
+}
+
+struct Clock {
+    instructions: Vec<Instruction>,
+    register: isize,
+    cycle: usize,
+    buffer: isize,
+    signal_strength: isize,
+    img_str: String,
+}
+
+impl Clock {
+    fn new(instructions: Vec<Instruction>) -> Self {
+        Self {
+            instructions,
+            register: 1,
+            cycle: 1,
+            buffer: 0,
+            signal_strength: 0,
+            img_str: String::new(),
+        }
+    }
+
+    fn execute_instruction(&mut self, instruction: Instruction) {
+        if let Instruction::AddX(a) = instruction {
+            self.buffer = a as isize;
+        }
+        self.cycle += 1;
+    }
+
+    fn check_cycle(&mut self) {
+        let current_pos = (self.cycle - 1) as isize % 40;
+        if self.register.abs_diff(current_pos) < 2 {
+            self.img_str.push('#')
+        } else {
+            self.img_str.push('.');
+        }
+        if self.cycle.is_multiple_of(40) {
+            self.img_str.push('\n');
+        }
+        (20..=220).step_by(40).for_each(|i| {
+            if self.cycle == i {
+                self.signal_strength += self.register * (i as isize);
+            }
+        })
+    }
+
+    // Draws the pixel of the current cycle and advances to the next one, returning false
+    // once the program has ended
+    fn tick(&mut self) -> bool {
+        if self.instructions.is_empty() {
+            return false;
+        }
+        self.check_cycle();
+        if self.buffer != 0 {
+            self.cycle += 1;
+            self.register += self.buffer;
+            self.buffer = 0;
+        } else {
+            let instruction = self.instructions.pop().expect("Should never be empty here");
+            self.execute_instruction(instruction);
+        }
+        true
+    }
+
+    fn execute_instructions(&mut self) {
+        while self.tick() {}
+    }
+
+    fn get_signal_strength(&self) -> isize {
+        self.signal_strength
+    }
+
+    fn get_image(self) -> String {
+        self.img_str
+    }
+}
@@ -201,3 +201,54 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn export() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("day10-{}.png", std::process::id()));
+    let mut cmd = Command::cargo_bin("day10")?;
+    cmd.args(["-i", "../day10/puzzle.txt", "--scale", "2", "--export"])
+        .arg(&path);
+    cmd.assert().success().stdout(predicate::str::is_empty());
+
+    // The image holds the 40x6 pixels of the CRT, each drawn as 2x2 pixels
+    let png = std::fs::read(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    assert_eq!(&png[12..16], b"IHDR");
+    assert_eq!(&png[16..24], [0, 0, 0, 80, 0, 0, 0, 12]);
+    Ok(())
+}
+
+#[test]
+fn export_errors() -> Result<(), Box<dyn std::error::Error>> {
+    for (args, error) in [
+        (vec!["--export", "crt.bmp"], "use a .png or .gif file"),
+        (
+            vec!["--scale", "2"],
+            "--scale and --palette require --export",
+        ),
+        (
+            vec!["--export", "crt.png", "--palette", "#=white"],
+            "'white' is not a hex colour",
+        ),
+    ] {
+        let mut cmd = Command::cargo_bin("day10")?;
+        cmd.args(["-i", "../day10/puzzle.txt"]).args(args);
+        cmd.assert().code(2).stderr(predicate::str::contains(error));
+    }
+    Ok(())
+}
+
+#[test]
+fn large_values() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day10")?;
+    cmd.arg("addx 15\naddx -15555555555555555555\nnoop");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "expected a number, found '-15555555555555555555'",
+    ));
+
+    let mut cmd = Command::cargo_bin("day10")?;
+    cmd.arg(format!("{}noop", "addx 2000000000\n".repeat(110)));
+    cmd.assert().success();
+    Ok(())
+}
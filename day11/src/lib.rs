@@ -0,0 +1,433 @@
+use std::{collections::VecDeque, rc::Rc};
+
+use utils::{
+    generate::{Generate, Rng},
+    parse_number,
+    reference::Reference,
+    Input, Param, Params, ParseError, Solution,
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: usize = 11;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "rounds",
+            help: "Number of rounds in the first part (default: 20)",
+            min: 0,
+        },
+        Param {
+            name: "rounds-alt",
+            help: "Number of rounds in the second part (default: 10000)",
+            min: 0,
+        },
+    ];
+
+    // The monkeys and the number of rounds of both parts
+    type Input = (Vec<Monkey>, usize, usize);
+    // The first part keeps the whole worry levels, so there is no answer if they overflow
+    type Part1 = Option<usize>;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let rounds = params.get("rounds").map_or(20, |r| r as usize);
+        let rounds_alt = params.get("rounds-alt").map_or(10000, |r| r as usize);
+        Ok((create_monkeys(text)?, rounds, rounds_alt))
+    }
+
+    fn part1((monkeys, rounds, _): &Self::Input) -> Self::Part1 {
+        let mut monkeys = monkeys.clone();
+        for _ in 0..*rounds {
+            execute_turn(&mut monkeys, true)?;
+        }
+        Some(get_monkey_business(monkeys))
+    }
+
+    fn part2((monkeys, _, rounds_alt): &Self::Input) -> Self::Part2 {
+        // Only the remainders are kept, so the starting items and operands may be reduced as well
+        let mut monkeys: Vec<Monkey> = monkeys.iter().map(Monkey::reduced).collect();
+        (0..*rounds_alt).for_each(|_| {
+            execute_turn(&mut monkeys, false)
+                .expect("Worry levels below the product of the divisors should not overflow")
+        });
+        get_monkey_business(monkeys)
+    }
+}
+
+// The product of the divisors has to fit in 32 bits for the worry levels to be squared,
+// so there are at most nine monkeys with a prime each. Above that, the size is the
+// number of items a monkey may start with. Like in the puzzle, the worry levels of the
+// first part have to fit into 64 bits, which rarely happens with only two monkeys passing
+// every item through the squaring one, so there are at least three
+impl Generate for Day11 {
+    const SIZE: usize = 8;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        loop {
+            let text = generate_notes(rng, size);
+            let monkeys = create_monkeys(&text).expect("Generated notes should be valid");
+            if play_exactly(&monkeys, 20).is_some() {
+                return text;
+            }
+        }
+    }
+}
+
+fn generate_notes(rng: &mut Rng, size: usize) -> String {
+    let num_monkeys = size.clamp(3, 9);
+    let mut divisors = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+    rng.shuffle(&mut divisors);
+    let squaring = rng.below(num_monkeys);
+
+    let mut monkeys = vec![];
+    for (monkey, divisor) in divisors.iter().take(num_monkeys).enumerate() {
+        let items: Vec<String> = (0..rng.between(1, size.max(8) as i64))
+            .map(|_| rng.between(50, 99).to_string())
+            .collect();
+        let operation = if monkey == squaring {
+            String::from("old * old")
+        } else if rng.chance(0.3) {
+            format!("old * {}", rng.between(2, 19))
+        } else {
+            format!("old + {}", rng.between(1, 8))
+        };
+        let mut others: Vec<usize> = (0..num_monkeys).filter(|&m| m != monkey).collect();
+        rng.shuffle(&mut others);
+        let (if_case, else_case) = (others[0], others[others.len() - 1]);
+        monkeys.push(format!(
+            "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  \
+             Test: divisible by {}\n    If true: throw to monkey {}\n    \
+             If false: throw to monkey {}\n",
+            monkey,
+            items.join(", "),
+            operation,
+            divisor,
+            if_case,
+            else_case
+        ));
+    }
+    monkeys.join("\n")
+}
+
+// Keeps the whole worry levels in the first part, and the remainders for every divisor on
+// their own in the second
+impl Reference for Day11 {
+    fn reference_part1((monkeys, rounds, _): &Self::Input) -> Self::Part1 {
+        play_exactly(monkeys, *rounds)
+    }
+
+    fn reference_part2((monkeys, _, rounds_alt): &Self::Input) -> Option<Self::Part2> {
+        let divisors: Vec<usize> = monkeys.iter().map(|m| m.test_cond).collect();
+        let mut items: Vec<Vec<Vec<usize>>> = monkeys
+            .iter()
+            .map(|m| {
+                m.items
+                    .iter()
+                    .map(|&x| divisors.iter().map(|d| x % d).collect())
+                    .collect()
+            })
+            .collect();
+        let mut inspections = vec![0; monkeys.len()];
+        for _ in 0..*rounds_alt {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                for old in std::mem::take(&mut items[i]) {
+                    let new: Vec<usize> = old
+                        .iter()
+                        .zip(&divisors)
+                        .map(|(&x, d)| {
+                            let operand = monkey.operand.map_or(x, |val| val % d);
+                            match monkey.operator {
+                                '+' => (x + operand) % d,
+                                _ => (x * operand) % d,
+                            }
+                        })
+                        .collect();
+                    let to = if new[i] == 0 {
+                        monkey.targets.0
+                    } else {
+                        monkey.targets.1
+                    };
+                    items[to].push(new);
+                    inspections[i] += 1;
+                }
+            }
+        }
+        inspections.sort_unstable();
+        Some(inspections.iter().rev().take(2).product())
+    }
+}
+
+// The monkey business after playing with the whole worry levels, or None if they overflow
+fn play_exactly(monkeys: &[Monkey], rounds: usize) -> Option<usize> {
+    let mut items: Vec<Vec<usize>> = monkeys
+        .iter()
+        .map(|m| m.items.iter().copied().collect())
+        .collect();
+    let mut inspections = vec![0; monkeys.len()];
+    for _ in 0..rounds {
+        for (i, monkey) in monkeys.iter().enumerate() {
+            for old in std::mem::take(&mut items[i]) {
+                let operand = monkey.operand.unwrap_or(old);
+                let new = match monkey.operator {
+                    '+' => old.checked_add(operand)?,
+                    _ => old.checked_mul(operand)?,
+                } / 3;
+                let to = if new % monkey.test_cond == 0 {
+                    monkey.targets.0
+                } else {
+                    monkey.targets.1
+                };
+                items[to].push(new);
+                inspections[i] += 1;
+            }
+        }
+    }
+    inspections.sort_unstable();
+    Some(inspections.iter().rev().take(2).product())
+}
+
+fn create_monkeys(text: &str) -> Result<Vec<Monkey>, ParseError> {
+    let blocks: Vec<(usize, Input)> = Input::new(text).dedent().blocks().collect();
+    if blocks.is_empty() {
+        return Err(ParseError::missing(text, "six lines describing a monkey"));
+    }
+    let mut all_notes = Vec::with_capacity(blocks.len());
+    // Product of all numbers in the test statements, which has to fit into 32 bits for the
+    // worry levels to be squared
+    let mut divis_prod: usize = 1;
+    for &(first_line, block) in blocks.iter() {
+        let block = block.text();
+        let notes = Notes::parse(block, blocks.len()).map_err(|e| e.shifted(first_line))?;
+        divis_prod = match divis_prod.checked_mul(notes.test_cond) {
+            Some(product) if product <= u32::MAX as usize => product,
+            _ => {
+                let divisor = last_token(block.lines().nth(3).unwrap_or_default());
+                let expected = "divisors with a product that fits into 32 bits";
+                return Err(ParseError::new(block, divisor, expected).shifted(first_line));
+            }
+        };
+        all_notes.push(notes);
+    }
+
+    Ok(all_notes
+        .into_iter()
+        .map(|n| Monkey::new(n, divis_prod))
+        .collect())
+}
+
+// Returns None if a worry level overflows
+fn execute_turn(monkeys: &mut [Monkey], worry_decrease: bool) -> Option<()> {
+    for i in 0..monkeys.len() {
+        let monkey = &mut monkeys[i];
+        let mut new_locs: Vec<(usize, usize)> = vec![];
+
+        while !monkey.items.is_empty() {
+            let mut new_val =
+                (monkey.op)(monkey.items.pop_front().expect("Should be able to remove"))?;
+            // (x + a) [x * a] is divisible by t iff ((x % t) + a) [(x % t) * a] is divisible by t
+            // Because items are shifted between monkeys with different test conditions, we
+            // need to choose t as the product of all numbers in the test cases. This does not
+            // hold for the division by three, so the first part keeps the whole worry level
+            if worry_decrease {
+                new_val /= 3;
+            } else {
+                new_val %= monkey.divis_prod;
+            }
+            let to_monkey = (monkey.test)(new_val);
+            new_locs.push((to_monkey, new_val));
+            monkey.num_inspections += 1;
+        }
+        for (to, val) in new_locs {
+            monkeys[to].items.push_back(val);
+        }
+    }
+    Some(())
+}
+
+fn get_monkey_business(mut monkeys: Vec<Monkey>) -> usize {
+    monkeys.sort_by_key(|m| m.num_inspections);
+    monkeys
+        .iter()
+        .rev()
+        .take(2)
+        .map(|x| x.num_inspections)
+        .product()
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: VecDeque<usize>,
+    // None if the new worry level overflows
+    op: Rc<dyn Fn(usize) -> Option<usize>>,
+    test: Rc<dyn Fn(usize) -> usize>,
+    divis_prod: usize,
+    num_inspections: usize,
+    // The notes again, for the reference solver
+    operator: char,
+    operand: Option<usize>,
+    test_cond: usize,
+    targets: (usize, usize),
+}
+
+// The description of a single monkey, as given in the puzzle
+struct Notes {
+    items: VecDeque<usize>,
+    operator: char,
+    // None stands for the old value itself
+    operand: Option<usize>,
+    test_cond: usize,
+    if_case: usize,
+    else_case: usize,
+}
+
+impl Notes {
+    fn parse(s: &str, num_monkeys: usize) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = s.lines().collect();
+        let [_, item_line, op_line, test_line, if_line, else_line] = lines[..] else {
+            return match lines.get(6) {
+                Some(extra) => Err(ParseError::new(s, extra, "an empty line after each monkey")),
+                None => Err(ParseError::missing(s, "six lines describing a monkey")),
+            };
+        };
+
+        let (_, items) = item_line
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(s, item_line, "Starting items: <numbers>"))?;
+        let items = items
+            .split(',')
+            .map(str::trim)
+            .filter(|x| !x.is_empty())
+            .map(|x| parse_number(s, x))
+            .collect::<Result<VecDeque<_>, _>>()?;
+
+        let (_, expression) = op_line
+            .split_once('=')
+            .ok_or_else(|| ParseError::new(s, op_line, "Operation: new = <expression>"))?;
+        let op_args: Vec<&str> = expression.split_whitespace().collect();
+        let ["old", operator, operand] = op_args[..] else {
+            let expected = "old + <number or old> or old * <number or old>";
+            return Err(ParseError::new(s, expression.trim(), expected));
+        };
+        let operator = match operator {
+            "+" => '+',
+            "*" => '*',
+            _ => return Err(ParseError::new(s, operator, "+ or *")),
+        };
+        let operand = match operand {
+            "old" => None,
+            val => Some(parse_number(s, val)?),
+        };
+
+        let test_cond = parse_number(s, last_token(test_line))?;
+        if test_cond == 0 {
+            return Err(ParseError::new(
+                s,
+                last_token(test_line),
+                "a divisor above 0",
+            ));
+        }
+        let target = |line: &str| {
+            let token = last_token(line);
+            match parse_number(s, token)? {
+                monkey if monkey < num_monkeys => Ok(monkey),
+                _ => {
+                    let expected = format!("a monkey below {}", num_monkeys);
+                    Err(ParseError::new(s, token, expected))
+                }
+            }
+        };
+
+        Ok(Notes {
+            items,
+            operator,
+            operand,
+            test_cond,
+            if_case: target(if_line)?,
+            else_case: target(else_line)?,
+        })
+    }
+}
+
+fn last_token(line: &str) -> &str {
+    line.split_whitespace().last().unwrap_or(line)
+}
+
+impl Monkey {
+    fn new(notes: Notes, divis_prod: usize) -> Self {
+        let Notes {
+            test_cond,
+            if_case,
+            else_case,
+            ..
+        } = notes;
+        let test = Rc::new(move |x: usize| {
+            if x.is_multiple_of(test_cond) {
+                if_case
+            } else {
+                else_case
+            }
+        });
+
+        let template_monkey = Monkey {
+            items: notes.items,
+            test,
+            op: Rc::new(Some),
+            divis_prod,
+            num_inspections: 0,
+            operator: notes.operator,
+            operand: notes.operand,
+            test_cond,
+            targets: (if_case, else_case),
+        };
+        match (notes.operator, notes.operand) {
+            ('+', None) => Monkey {
+                op: Rc::new(move |x| x.checked_add(x)),
+                ..template_monkey
+            },
+            ('*', None) => Monkey {
+                op: Rc::new(move |x| x.checked_mul(x)),
+                ..template_monkey
+            },
+            ('+', Some(val)) => Monkey {
+                op: Rc::new(move |x| x.checked_add(val)),
+                ..template_monkey
+            },
+            ('*', Some(val)) => Monkey {
+                op: Rc::new(move |x| x.checked_mul(val)),
+                ..template_monkey
+            },
+            _ => unreachable!("Operators are checked while parsing"),
+        }
+    }
+
+    // The same monkey with its items and operand taken modulo the product of the divisors,
+    // so that a worry level below it never overflows when it is squared or multiplied
+    fn reduced(&self) -> Self {
+        let notes = Notes {
+            items: self.items.iter().map(|x| x % self.divis_prod).collect(),
+            operator: self.operator,
+            operand: self.operand.map(|val| val % self.divis_prod),
+            test_cond: self.test_cond,
+            if_case: self.targets.0,
+            else_case: self.targets.1,
+        };
+        Monkey::new(notes, self.divis_prod)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::reference;
+
+    #[test]
+    fn agrees_with_reference() {
+        reference::check::<Day11>(0..20, 4);
+    }
+}
@@ -61,3 +61,133 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn custom_rounds() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day11")?;
+    cmd.arg(
+        "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1",
+    );
+    cmd.args(["--rounds-alt", "1000", "--part", "2"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The monkey business after 1000 rounds is 27019168",
+        ))
+        .stdout(predicate::str::contains("after 20 rounds").not());
+
+    Ok(())
+}
+
+#[test]
+fn large_divisors() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day11")?;
+    cmd.arg(
+        "Monkey 0:
+  Starting items: 79
+  Operation: new = old * old
+  Test: divisible by 65537
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 54
+  Operation: new = old + 3
+  Test: divisible by 65539
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+    );
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected divisors with a product that fits into 32 bits, found '65539'",
+        ))
+        .stderr(predicate::str::contains("--> line 11, column 22"));
+    Ok(())
+}
+
+#[test]
+fn overflowing_worry_levels() -> Result<(), Box<dyn std::error::Error>> {
+    // The first part keeps the whole worry levels, which outgrow 64 bits in the example
+    let mut cmd = Command::cargo_bin("day11")?;
+    cmd.args(["-i", "../day11/simple_puzzle.txt", "--rounds", "100"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The worry levels do not fit into 64 bits within 100 rounds",
+        ))
+        .stdout(predicate::str::contains(
+            "The monkey business after 10000 rounds is 2713310158",
+        ));
+
+    // The second part only keeps the remainders, also of large starting items
+    let mut cmd = Command::cargo_bin("day11")?;
+    cmd.arg(
+        "Monkey 0:
+  Starting items: 4294967296
+  Operation: new = old * old
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 1
+  Operation: new = old + 1
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+    );
+    cmd.args(["--part", "2"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "The monkey business after 10000 rounds is 399980000",
+    ));
+
+    // And of operands that overflow as soon as they multiply a worry level
+    let mut cmd = Command::cargo_bin("day11")?;
+    cmd.arg(
+        "Monkey 0:
+  Starting items: 1
+  Operation: new = old * 18446744073709551615
+  Test: divisible by 2
+    If true: throw to monkey 1
+    If false: throw to monkey 1
+
+Monkey 1:
+  Starting items: 2
+  Operation: new = old + 1
+  Test: divisible by 3
+    If true: throw to monkey 0
+    If false: throw to monkey 0",
+    );
+    cmd.args(["--part", "2"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "The monkey business after 10000 rounds is 399980000",
+    ));
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::str::FromStr;
+
+use utils::{
+    generate::{Generate, Rng},
+    grid::Grid,
+    search::bfs_multi,
+    Input, ParseError, Solution,
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+
+    type Input = Map;
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        text.parse::<Map>()
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        shortest_path(map, vec![map.start])
+    }
+
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        shortest_path(map, map.get_possible_starts())
+    }
+}
+
+// The size is the width of the map, which is a quarter as high
+impl Generate for Day12 {
+    const SIZE: usize = 160;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = size.max(50);
+        let height = width / 4;
+        let random_y = |rng: &mut Rng| rng.below(height);
+        let end = (width - 1 - rng.below(width / 4), random_y(rng));
+        let start = (rng.below(width / 4), random_y(rng));
+        let distance = |(x, y): Position, (px, py): Position| x.abs_diff(px) + y.abs_diff(py);
+
+        // Hills whose heights fall by one every few steps, the highest one at the goal
+        let mut hills = vec![(end, 25, 2)];
+        for _ in 0..width / 20 {
+            let peak = (rng.below(width), random_y(rng));
+            hills.push((
+                peak,
+                rng.between(5, 24) as usize,
+                rng.between(1, 3) as usize,
+            ));
+        }
+
+        // Neighbouring heights differ by at most one, so every position can reach the goal
+        let mut text = String::new();
+        for y in 0..height {
+            for x in 0..width {
+                let pos = (x, y);
+                let hill = hills
+                    .iter()
+                    .map(|&(peak, top, spread)| top.saturating_sub(distance(pos, peak) / spread))
+                    .max()
+                    .unwrap_or_default();
+                let height = hill.min(distance(pos, start));
+                text.push(match pos {
+                    _ if pos == start => 'S',
+                    _ if pos == end => 'E',
+                    _ => (b'a' + height as u8) as char,
+                });
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+// `None` if the goal cannot be reached from any of the starts
+fn shortest_path(map: &Map, starts: Vec<Position>) -> Option<usize> {
+    bfs_multi(starts, |&pos| map.reachable(pos), |&pos| pos == map.end).map(|(steps, _)| steps)
+}
+
+// (x, y) within the height map
+type Position = (usize, usize);
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    heights: Grid<usize>,
+    start: Position,
+    end: Position,
+}
+
+impl Map {
+    fn get_possible_starts(&self) -> Vec<Position> {
+        self.heights
+            .iter()
+            .filter_map(|(pos, &height)| (height == 0).then_some(pos))
+            .collect()
+    }
+
+    // Neighbours that are at most one higher than the position
+    fn reachable(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        let max_height = self.heights[pos] + 1;
+        self.heights
+            .neighbours4(pos)
+            .filter(move |&n| self.heights[n] <= max_height)
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let map = Input::new(s).dedent().grid("a-z, S or E", |c| {
+            matches!(c, 'a'..='z' | 'S' | 'E').then_some(c)
+        })?;
+        let start = map
+            .position(|&c| c == 'S')
+            .ok_or_else(|| ParseError::missing(s, "a start position S"))?;
+        let end = map
+            .position(|&c| c == 'E')
+            .ok_or_else(|| ParseError::missing(s, "a goal position E"))?;
+        let heights = map.map(|&c| match c {
+            'S' => 0,
+            'E' => 25,
+            c => c as usize - 'a' as usize,
+        });
+
+        Ok(Map {
+            heights,
+            start,
+            end,
+        })
+    }
+}
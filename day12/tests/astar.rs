@@ -39,3 +39,18 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn unreachable_goal() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day12")?;
+    cmd.arg("SazE\nabzz");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "There is no path from the start to the goal",
+        ))
+        .stdout(predicate::str::contains(
+            "There is no path from any start point to the goal",
+        ));
+    Ok(())
+}
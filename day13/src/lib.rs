@@ -0,0 +1,257 @@
+use std::{cmp::Ordering, fmt::Display};
+use utils::{
+    generate::{Generate, Rng},
+    parse_number, Input, ParseError, Solution,
+};
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+
+    type Input = Vec<List>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        create_lists(text)
+    }
+
+    fn part1(lists: &Self::Input) -> Self::Part1 {
+        sum_ordered_indices(create_pairs(lists))
+    }
+
+    fn part2(lists: &Self::Input) -> Self::Part2 {
+        order_and_find(lists.clone())
+    }
+}
+
+// The size is the number of pairs of packets
+impl Generate for Day13 {
+    const SIZE: usize = 150;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let pairs: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let left = generate_packet(rng, 0);
+                format!("{}\n{}\n", left, generate_packet(rng, 0))
+            })
+            .collect();
+        pairs.join("\n")
+    }
+}
+
+fn generate_packet(rng: &mut Rng, depth: usize) -> String {
+    let len = rng.below(6);
+    let items: Vec<String> = (0..len)
+        .map(|_| {
+            if depth < 4 && rng.chance(0.3) {
+                generate_packet(rng, depth + 1)
+            } else {
+                rng.between(0, 10).to_string()
+            }
+        })
+        .collect();
+    format!("[{}]", items.join(","))
+}
+
+fn order_and_find(mut lists: Vec<List>) -> usize {
+    let two = parse_list("[[2]]", "[[2]]").expect("Divider packets should be valid");
+    let six = parse_list("[[6]]", "[[6]]").expect("Divider packets should be valid");
+
+    lists.push(two.clone());
+    lists.push(six.clone());
+    lists.sort();
+    lists
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| (x == &two || x == &six).then_some(i + 1))
+        .product()
+}
+
+fn sum_ordered_indices(pairs: Vec<Pair>) -> usize {
+    pairs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| (l.0 <= l.1).then_some(i + 1))
+        .sum()
+}
+
+// Comparing, printing and dropping lists recurses into them, so that deeper lists could
+// overflow the stack
+const MAX_DEPTH: usize = 100;
+
+fn create_lists(text: &str) -> Result<Vec<List>, ParseError> {
+    let lists = Input::new(text)
+        .lines()
+        .map(|l| l.trim())
+        .filter(|&l| !l.is_empty())
+        .map(|l| check_depth(text, l).and_then(|_| parse_list(text, l)))
+        .collect::<Result<Vec<_>, _>>()?;
+    if !lists.len().is_multiple_of(2) {
+        return Err(ParseError::missing(text, "a second list for the last pair"));
+    }
+    Ok(lists)
+}
+
+fn check_depth(source: &str, text: &str) -> Result<(), ParseError> {
+    let mut depth = 0;
+    for (i, cur_char) in text.char_indices() {
+        match cur_char {
+            '[' if depth == MAX_DEPTH => {
+                let expected = format!("lists nested at most {} deep", MAX_DEPTH);
+                return Err(ParseError::new(source, &text[i..i + 1], expected));
+            }
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn create_pairs(lists: &[List]) -> Vec<Pair> {
+    lists
+        .chunks(2)
+        .map(|x| Pair(x[0].clone(), x[1].clone()))
+        .collect()
+}
+
+// `text` is a slice of `source`, which is only needed to locate errors
+fn parse_list(source: &str, text: &str) -> Result<List, ParseError> {
+    let inner = text
+        .strip_prefix('[')
+        .and_then(|t| t.strip_suffix(']'))
+        .ok_or_else(|| ParseError::new(source, text, "a list in brackets"))?;
+    if inner.is_empty() {
+        return Ok(List {
+            items: vec![ListNode::Empty],
+        });
+    }
+
+    let mut items = vec![];
+    let mut unclosed_brackets = 0;
+    let mut item_start = 0;
+    for (i, cur_char) in inner.char_indices() {
+        match cur_char {
+            ',' if unclosed_brackets == 0 => {
+                items.push(parse_node(source, &inner[item_start..i], &inner[i..i + 1])?);
+                item_start = i + 1;
+            }
+            '[' => unclosed_brackets += 1,
+            ']' if unclosed_brackets == 0 => {
+                return Err(ParseError::new(
+                    source,
+                    &inner[i..i + 1],
+                    "a matching opening [",
+                ))
+            }
+            ']' => unclosed_brackets -= 1,
+            _ => (),
+        }
+    }
+    if unclosed_brackets != 0 {
+        return Err(ParseError::new(
+            source,
+            &text[text.len() - 1..],
+            "a closing ]",
+        ));
+    }
+
+    items.push(parse_node(
+        source,
+        &inner[item_start..],
+        &text[text.len() - 1..],
+    )?);
+    Ok(List { items })
+}
+
+// Only an empty list has an empty item, elsewhere the error points at the `separator` after it
+fn parse_node(source: &str, s: &str, separator: &str) -> Result<ListNode, ParseError> {
+    if s.is_empty() {
+        Err(ParseError::new(source, separator, "a number or a list"))
+    } else if s.starts_with('[') {
+        Ok(ListNode::Body(Box::new(parse_list(source, s)?)))
+    } else {
+        Ok(ListNode::Integer(parse_number(source, s)?))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct List {
+    items: Vec<ListNode>,
+}
+
+#[derive(Debug)]
+struct Pair(List, List);
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum ListNode {
+    Integer(usize),
+    Empty,
+    Body(Box<List>),
+}
+
+impl Display for ListNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ListNode::Integer(a) => write!(f, "{}", a),
+            ListNode::Empty => write!(f, ""),
+            ListNode::Body(l) => write!(f, "[{}]", l),
+        }
+    }
+}
+
+impl PartialOrd for List {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for List {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (i, (ln0, ln1)) in self.items.iter().zip(other.items.iter()).enumerate() {
+            let result = match (ln0, ln1) {
+                (ListNode::Integer(a), ListNode::Integer(b)) => a.cmp(b),
+                (ListNode::Body(a), ListNode::Body(b)) => a.cmp(b),
+                (ListNode::Empty, ListNode::Empty) => Ordering::Equal,
+                (_, ListNode::Empty) => Ordering::Greater,
+                (ListNode::Empty, _) => Ordering::Less,
+                (ListNode::Integer(a), _) => List {
+                    items: vec![ListNode::Body(Box::new(List {
+                        items: vec![ListNode::Integer(*a)],
+                    }))],
+                }
+                .cmp(&List {
+                    items: other.items[i..].to_vec(),
+                }),
+                (_, ListNode::Integer(a)) => List {
+                    items: self.items[i..].to_vec(),
+                }
+                .cmp(&List {
+                    items: vec![ListNode::Body(Box::new(List {
+                        items: vec![ListNode::Integer(*a)],
+                    }))],
+                }),
+            };
+            if result != Ordering::Equal {
+                return result;
+            }
+        }
+        self.items.len().cmp(&other.items.len())
+    }
+}
+
+impl Display for List {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::from("[");
+        for (i, item) in self.items.iter().enumerate() {
+            if i == self.items.len() - 1 {
+                s = format!("{}{}", s, item);
+            } else {
+                s = format!("{},{}", s, item);
+            }
+        }
+        write!(f, "{}]", s)
+    }
+}
@@ -55,3 +55,39 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn deeply_nested() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day13")?;
+    let deep = "[".repeat(1000) + &"]".repeat(1000);
+    cmd.arg(format!("[1]\n{}", deep));
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected lists nested at most 100 deep, found '['",
+        ))
+        .stderr(predicate::str::contains("--> line 2, column 101"));
+    Ok(())
+}
+
+#[test]
+fn empty_items() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day13")?;
+    cmd.arg("[1]\n[1,]");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a number or a list, found ']'",
+        ))
+        .stderr(predicate::str::contains("--> line 2, column 4"));
+
+    let mut cmd = Command::cargo_bin("day13")?;
+    cmd.arg("[[],,3]\n[1]");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a number or a list, found ','",
+        ))
+        .stderr(predicate::str::contains("--> line 1, column 5"));
+    Ok(())
+}
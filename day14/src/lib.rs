@@ -0,0 +1,291 @@
+use std::{cmp::max, fmt::Display, str::FromStr};
+
+use log::{debug, trace};
+use utils::{
+    animate::{Animate, Frame},
+    generate::{Generate, Rng},
+    geom::{Direction, Point2},
+    grid::Grid,
+    parse_number, Input, Param, Params, ParseError, Part, Solution,
+};
+
+// These defaults determine the range of the "infinite floor"
+// such that we can still have a readable printing of the Map
+// These values worked for my puzzle, other puzzles can adjust them with --min-x and --x-margin
+// Remember that sand spawns at height 0, so we do not need a constant MIN_Y.
+// Per instructions, the MAX_Y is 2 + the maxium y value of any rock formation.
+const DEFAULT_MIN_X: usize = 300;
+const DEFAULT_X_MARGIN: usize = 100;
+// The map holds every tile up to the rocks, so they have to stay reasonably close
+const MAX_COORDINATE: usize = 10000;
+// Where the sand comes from, at height 0
+const SOURCE_X: usize = 500;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+    const ANIMATED: bool = true;
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "min-x",
+            help: "Smallest x coordinate of the map (default: 300)",
+            min: 0,
+        },
+        Param {
+            name: "x-margin",
+            help: "Columns of the map right of the rightmost rock (default: 100)",
+            min: 1,
+        },
+    ];
+
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let min_x = params.get("min-x").map_or(DEFAULT_MIN_X, |x| x as usize);
+        let x_margin = params
+            .get("x-margin")
+            .map_or(DEFAULT_X_MARGIN, |x| x as usize);
+        let input = Input::new(text).dedent();
+        // Rocks left of the map would not fit into it
+        let too_far_left = input
+            .lines()
+            .flat_map(|line| line.split("->"))
+            .find_map(|p| {
+                let x = p.split_once(',')?.0.trim();
+                (x.parse::<usize>().ok()? < min_x).then_some(x)
+            });
+        if let Some(x) = too_far_left {
+            return Err(ParseError::new(
+                text,
+                x,
+                format!("an x coordinate of at least {} (see --min-x)", min_x),
+            ));
+        }
+        let shapes = input.parse_lines::<Shape>()?;
+        if shapes.is_empty() {
+            return Err(ParseError::missing(text, "a path of rock"));
+        }
+        Ok(Map::new(&shapes, min_x, x_margin))
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        get_number_rests(&mut map.clone(), false)
+    }
+
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        get_number_rests(&mut map.clone(), true)
+    }
+}
+
+impl Animate for Day14 {
+    type Frame = Map;
+
+    fn frames(map: &Self::Input, part: Part) -> impl Iterator<Item = Self::Frame> + '_ {
+        let part2 = part == Part::Two;
+        std::iter::successors(Some(map.clone()), move |map| {
+            let mut next = map.clone();
+            next.spawn_and_execute(part2).then_some(next)
+        })
+    }
+}
+
+// The size is the number of rock paths. They stay within y 12..=90 and x 440..=560, so that
+// the default map is wide enough for the sand of the second part
+impl Generate for Day14 {
+    const SIZE: usize = 100;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|path| {
+                // The map ends at the rightmost rock plus the margin, so one is right of the source
+                let low_x = if path == 0 { 500 } else { 440 };
+                let (mut x, mut y) = (rng.between(low_x, 560), rng.between(12, 90));
+                let mut points = vec![format!("{},{}", x, y)];
+                for segment in 0..rng.between(1, 6) {
+                    let length = rng.between(-8, 8);
+                    if segment % 2 == 0 {
+                        x = (x + length).clamp(440, 560);
+                    } else {
+                        y = (y + length).clamp(12, 90);
+                    }
+                    points.push(format!("{},{}", x, y));
+                }
+                points.join(" -> ") + "\n"
+            })
+            .collect()
+    }
+}
+
+fn get_number_rests(map: &mut Map, part2: bool) -> usize {
+    let mut counter = 0;
+    while map.spawn_and_execute(part2) {
+        counter += 1;
+        trace!("The map after {} sands is\n{}", counter, map);
+    }
+    debug!("{} sands came to rest, the map is\n{}", counter, map);
+    counter
+}
+
+type Point = Point2<usize>;
+
+struct Shape {
+    moves: Vec<Point>,
+}
+
+impl FromStr for Shape {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut moves: Vec<Point> = Vec::new();
+        for p in s.split("->").map(str::trim) {
+            let (x, y) = p
+                .split_once(',')
+                .ok_or_else(|| ParseError::new(s, p, "a point like 498,4"))?;
+            let coordinate = |token: &str| match parse_number(s, token)? {
+                c if c < MAX_COORDINATE => Ok(c),
+                _ => {
+                    let expected = format!("a coordinate below {}", MAX_COORDINATE);
+                    Err(ParseError::new(s, token, expected))
+                }
+            };
+            let point = Point::new(coordinate(x)?, coordinate(y)?);
+            if let Some(prev) = moves.last() {
+                if prev.x != point.x && prev.y != point.y {
+                    return Err(ParseError::new(s, p, "a horizontal or vertical line"));
+                }
+            }
+            moves.push(point);
+        }
+
+        Ok(Shape { moves })
+    }
+}
+
+#[derive(Clone)]
+pub struct Map {
+    min_x: usize,
+    grid: Grid<char>,
+}
+
+impl Map {
+    fn new(shapes: &[Shape], min_x: usize, x_margin: usize) -> Self {
+        let (max_x, max_y) = shapes
+            .iter()
+            .flat_map(|x| x.moves.iter())
+            .fold((usize::MIN, usize::MIN), |(max_x, max_y), cur| {
+                (max(max_x, cur.x), max(max_y, cur.y))
+            });
+
+        // Given by Part 2 (at + 2 is the floor)
+        let height = max_y + 3;
+
+        // As part of Part 2, we have an "infinite" floor. However, for printing
+        // We restrict the range to [min_x, x_margin + max x-value in rock formation],
+        // widened if the sand piling up below the source would not fit into it
+        let min_x = min_x.min(SOURCE_X.saturating_sub(height));
+        let max_x = (x_margin + max_x).max(SOURCE_X + height);
+
+        // width/height = difference + 1, e.g. [0, 3] is range of size 4
+        let width = max_x - min_x + 1;
+        let mut grid = Grid::new(width, height, '.');
+
+        for shape in shapes.iter() {
+            for (p1, p2) in shape.moves.iter().zip(shape.moves.iter().skip(1)) {
+                let (x1, y1) = (p1.x - min_x, p1.y);
+                let (x2, y2) = (p2.x - min_x, p2.y);
+                if x1 == x2 {
+                    (y1.min(y2)..=y1.max(y2)).for_each(|y| {
+                        grid[(x1, y)] = '#';
+                    });
+                } else {
+                    (x1.min(x2)..=x1.max(x2)).for_each(|x| {
+                        grid[(x, y1)] = '#';
+                    });
+                }
+            }
+        }
+        Map { min_x, grid }
+    }
+
+    fn get(&self, p: Point) -> char {
+        let x_norm = p.x - self.min_x;
+        self.grid[(x_norm, p.y)]
+    }
+
+    fn set(&mut self, p: Point) {
+        let x_norm = p.x - self.min_x;
+        self.grid[(x_norm, p.y)] = 'o';
+    }
+
+    fn is_clear(&self, p: Point) -> bool {
+        self.get(p) == '.'
+    }
+
+    fn drop_sand(&self, sand_pos: Point, part2: bool) -> Option<Point> {
+        // At self.grid.height() - 1 is the floor,
+        // so self.grid.height() - 2 indicates the last row above the floor
+        if part2 && sand_pos.y >= self.grid.height() - 2 {
+            return None;
+        }
+        if !part2
+            && (sand_pos.x < self.min_x
+            || sand_pos.x >= self.min_x + self.grid.width() - 1
+            // self.grid.height() - 3 is the height of the problem in Part 1
+            // Part 2 added 2 additional rows
+            || sand_pos.y >= self.grid.height() - 3)
+        {
+            return None;
+        }
+
+        let down = sand_pos.step(Direction::Down);
+        [
+            down,
+            down.step(Direction::Left),
+            down.step(Direction::Right),
+        ]
+        .into_iter()
+        .find(|&p| self.is_clear(p))
+    }
+
+    fn spawn_and_execute(&mut self, part2: bool) -> bool {
+        let source = Point::new(SOURCE_X, 0);
+        let mut sand_pos = source;
+        while let Some(p) = self.drop_sand(sand_pos, part2) {
+            sand_pos = p;
+        }
+        if !part2 && sand_pos.y == self.grid.height() - 3 {
+            return false;
+        }
+        if sand_pos == source && !self.is_clear(sand_pos) {
+            return false;
+        }
+        self.set(sand_pos);
+        true
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+        // The last row is the floor
+        for row in self.grid.rows().take(self.grid.height() - 1) {
+            s.extend(row);
+            s.push('\n');
+        }
+        s.push_str("#".repeat(self.grid.width()).as_str());
+        write!(f, "{}", s)
+    }
+}
+
+impl Frame for Map {
+    fn draw(&self) -> String {
+        self.to_string()
+    }
+}
@@ -30,3 +30,71 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn animation() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day14")?;
+    cmd.args(["--animate", "--fps", "1000", "--part", "2"]).arg(
+        "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9",
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("\x1b[H"))
+        .stdout(predicate::str::contains("Frame 94 | 1000 fps | done"))
+        .stdout(predicate::str::contains("The number of rests").not());
+    Ok(())
+}
+
+#[test]
+fn fps_without_animation() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day14")?;
+    cmd.args(["--fps", "5", "-i", "../day14/puzzle.txt"]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "--fps requires --animate or --export",
+    ));
+    Ok(())
+}
+
+#[test]
+fn narrow_map() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day14")?;
+    cmd.args(["--min-x", "497"]).arg(
+        "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9",
+    );
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "expected an x coordinate of at least 497 (see --min-x), found '496'",
+    ));
+    Ok(())
+}
+
+#[test]
+fn distant_rock() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day14")?;
+    cmd.arg("498,4 -> 498,18446744073709551615");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "expected a coordinate below 10000, found '18446744073709551615'",
+    ));
+
+    let mut cmd = Command::cargo_bin("day14")?;
+    cmd.arg("");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("expected a path of rock"));
+    Ok(())
+}
+
+#[test]
+fn wide_pile() -> Result<(), Box<dyn std::error::Error>> {
+    // The sand of the second part piles up wider than the default map
+    let mut cmd = Command::cargo_bin("day14")?;
+    cmd.arg("498,464 -> 499,464");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The number of rested sand is 0"))
+        .stdout(predicate::str::contains(
+            "The number of rests needed for stopping is 217154",
+        ));
+    Ok(())
+}
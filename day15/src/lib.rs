@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+use std::{cmp::max, str::FromStr};
+
+use utils::{
+    generate::{Generate, Rng},
+    geom::Point2,
+    parse_number, Input, Param, Params, ParseError, Solution,
+};
+
+const DEFAULT_ROW: isize = 2_000_000;
+const DEFAULT_SEARCH_MAX: isize = 4_000_000;
+// Given by the puzzle, independent of the size of the search area
+const TUNING_MULTIPLIER: isize = 4_000_000;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+
+    const PARAMS: &'static [Param] = &[
+        Param {
+            name: "row",
+            help: "Row in which the blocked places are counted (default: 2000000)",
+            min: i64::MIN,
+        },
+        Param {
+            name: "search-max",
+            help: "Largest x and y coordinate of the distress beacon (default: 4000000)",
+            min: 0,
+        },
+    ];
+
+    // The sensors, the row of the first part and the largest coordinate searched in the second
+    type Input = (Vec<Sensor>, isize, isize);
+    type Part1 = usize;
+    // There is no distress beacon if the search area is covered by the sensors
+    type Part2 = Option<isize>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let sensors = Input::new(text).dedent().parse_lines::<Sensor>()?;
+        let row = params.get("row").map_or(DEFAULT_ROW, |row| row as isize);
+        let search_max = params
+            .get("search-max")
+            .map_or(DEFAULT_SEARCH_MAX, |max| max as isize);
+        Ok((sensors, row, search_max))
+    }
+
+    fn part1((sensors, row, _): &Self::Input) -> Self::Part1 {
+        get_number_blocked_in_row(sensors, *row)
+    }
+
+    fn part2((sensors, _, search_max): &Self::Input) -> Self::Part2 {
+        find_tuning_frequency(sensors, *search_max)
+    }
+}
+
+// The size is the number of sensors along a side of the search area, at least 4 so that the
+// row of the first part is about as long as in the real input. The sensors sit on a jittered
+// lattice whose diamonds cover the whole area, except for the sensors around the distress
+// beacon, which leave exactly its position uncovered
+impl Generate for Day15 {
+    const SIZE: usize = 5;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let area = DEFAULT_SEARCH_MAX;
+        let steps = size.max(4) as isize;
+        let spacing = area / steps;
+        let jitter = spacing / 4;
+        let hidden = Point::new(
+            rng.between(0, area as i64) as isize,
+            rng.between(0, area as i64) as isize,
+        );
+
+        let mut sensors = vec![];
+        for i in 0..=steps {
+            for j in 0..=steps {
+                if (i + j) % 2 != 0 {
+                    continue;
+                }
+                let mut offset = || rng.between(-jitter as i64 / 2, jitter as i64 / 2) as isize;
+                let origin = Point::new(i * spacing + offset(), j * spacing + offset());
+                let radius = spacing + jitter;
+                if origin.manhattan(hidden) > radius {
+                    sensors.push((origin, radius));
+                }
+            }
+        }
+        // Four sensors diagonal to the beacon cover everything near it except the beacon itself
+        let diagonal = spacing + jitter + 1;
+        for (dx, dy) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+            let origin = Point::new(hidden.x + dx * diagonal, hidden.y + dy * diagonal);
+            sensors.push((origin, 2 * diagonal - 1));
+        }
+        rng.shuffle(&mut sensors);
+
+        sensors
+            .into_iter()
+            .map(|(origin, radius)| {
+                let dx = rng.between(0, radius as i64) as isize;
+                let (sx, sy) = (*rng.choose(&[-1, 1]), *rng.choose(&[-1, 1]));
+                let beacon = Point::new(origin.x + sx * dx, origin.y + sy * (radius - dx));
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                    origin.x, origin.y, beacon.x, beacon.y
+                )
+            })
+            .collect()
+    }
+}
+
+fn get_number_blocked_in_row(sensors: &[Sensor], row: isize) -> usize {
+    let mut intervals = vec![];
+    let mut blocked = HashSet::new();
+    create_intervals(sensors, &mut blocked, &mut intervals, row);
+    intervals.sort();
+
+    let mut qualified: Vec<Interval> = vec![];
+    refine_intervals(&mut qualified, &mut intervals);
+    get_number_blocked(&mut qualified, &blocked)
+}
+
+fn find_tuning_frequency(sensors: &[Sensor], search_max: isize) -> Option<isize> {
+    for cur_row in 0..=search_max {
+        let mut intervals = vec![];
+        let mut blocked = HashSet::new();
+        create_intervals(sensors, &mut blocked, &mut intervals, cur_row);
+        intervals.sort();
+
+        let mut qualified: Vec<Interval> = vec![];
+        refine_intervals(&mut qualified, &mut intervals);
+        if let Some(val) = check_score(&mut qualified, cur_row, search_max) {
+            return Some(val);
+        }
+    }
+    None
+}
+
+fn check_score(qualified: &mut [Interval], current_row: isize, search_max: isize) -> Option<isize> {
+    let mut x = 0;
+    for interval in qualified.iter() {
+        if x < interval.low {
+            return Some(x * TUNING_MULTIPLIER + current_row);
+        }
+        x = max(x, interval.high + 1);
+        if x > search_max {
+            return None;
+        }
+    }
+    None
+}
+
+fn get_number_blocked(qualified: &mut [Interval], blocked: &HashSet<isize>) -> usize {
+    let mut result = HashSet::new();
+    for interval in qualified.iter() {
+        for x in interval.low..=interval.high {
+            result.insert(x);
+        }
+    }
+    result.difference(blocked).count()
+}
+
+fn refine_intervals(qualified: &mut Vec<Interval>, intervals: &mut [Interval]) {
+    for interval in intervals.iter() {
+        if qualified.is_empty() {
+            qualified.push(*interval);
+            continue;
+        }
+
+        let max_interval = qualified.last().unwrap();
+        let (_max_lo, max_hi) = (max_interval.low, max_interval.high);
+
+        if interval.low > max_hi + 1 {
+            qualified.push(*interval);
+        }
+
+        let last = qualified.last_mut().unwrap();
+        last.high = max(max_hi, interval.high);
+    }
+}
+
+fn create_intervals(
+    sensors: &[Sensor],
+    blocked: &mut HashSet<isize>,
+    intervals: &mut Vec<Interval>,
+    row_count: isize,
+) {
+    for sensor in sensors.iter() {
+        let overlap = sensor.nearest_beacon_dist - sensor.origin.y.abs_diff(row_count) as isize;
+        if overlap < 0 {
+            continue;
+        }
+        let low = sensor.origin.x - overlap;
+        let high = sensor.origin.x + overlap;
+        intervals.push(Interval { low, high });
+
+        if sensor.beacon.y == row_count {
+            blocked.insert(sensor.beacon.x);
+        }
+    }
+}
+
+type Point = Point2<isize>;
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+struct Interval {
+    low: isize,
+    high: isize,
+}
+
+pub struct Sensor {
+    origin: Point,
+    beacon: Point,
+    nearest_beacon_dist: isize,
+}
+
+impl FromStr for Sensor {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let coords = s
+            .split('=')
+            .skip(1)
+            .map(|part| {
+                let len = part
+                    .find(|c: char| c != '-' && !c.is_ascii_digit())
+                    .unwrap_or(part.len());
+                parse_number::<isize>(s, &part[..len])
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if coords.len() != 4 {
+            return Err(ParseError::new(
+                s,
+                s,
+                "a sensor like 'Sensor at x=2, y=18: closest beacon is at x=-2, y=15'",
+            ));
+        }
+
+        let (origin, beacon) = (
+            Point::new(coords[0], coords[1]),
+            Point::new(coords[2], coords[3]),
+        );
+        let nearest_beacon_dist = origin.manhattan(beacon);
+
+        Ok(Sensor {
+            origin,
+            beacon,
+            nearest_beacon_dist,
+        })
+    }
+}
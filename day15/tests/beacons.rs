@@ -5,7 +5,7 @@ use std::process::Command;
 #[test]
 fn simple() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("day15")?;
-    cmd.arg(
+    cmd.args(["--row", "10", "--search-max", "20"]).arg(
         "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
 Sensor at x=9, y=16: closest beacon is at x=10, y=16
 Sensor at x=13, y=2: closest beacon is at x=15, y=3
@@ -47,3 +47,101 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn explicit_row() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day15")?;
+    cmd.args([
+        "-i",
+        "../day15/puzzle.txt",
+        "--row",
+        "2000000",
+        "--part",
+        "1",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The number of blocked places is 4886370",
+        ))
+        .stdout(predicate::str::contains("tuning frequency").not());
+    Ok(())
+}
+
+#[test]
+fn covered_search_area() -> Result<(), Box<dyn std::error::Error>> {
+    // The sensors of the example cover every position up to 10
+    let mut cmd = Command::cargo_bin("day15")?;
+    cmd.args(["-i", "../day15/simple_puzzle.txt", "--search-max", "10"]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "There is no distress beacon in the search area",
+    ));
+    Ok(())
+}
+
+#[test]
+fn configured_example() -> Result<(), Box<dyn std::error::Error>> {
+    // aoc.toml in the root of the repository sets the row and search area of the example
+    let mut cmd = Command::cargo_bin("day15")?;
+    cmd.args(["-i", "../day15/simple_puzzle.txt"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The number of blocked places is 26",
+        ))
+        .stdout(predicate::str::contains(
+            "The tuning frequency of the distress beacon is 56000011",
+        ));
+
+    // Values on the command line take precedence
+    let mut cmd = Command::cargo_bin("day15")?;
+    cmd.args([
+        "-i",
+        "../day15/simple_puzzle.txt",
+        "--row",
+        "11",
+        "--part",
+        "1",
+    ]);
+    cmd.assert().success().stdout(predicate::str::contains(
+        "The number of blocked places is 28",
+    ));
+    Ok(())
+}
+
+#[test]
+fn invalid_config() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::temp_dir().join(format!("day15-{}.toml", std::process::id()));
+    for (config, error) in [
+        (
+            "[day15]\nrow = \"ten\"\n",
+            "expected an integer, found '\"ten\"'",
+        ),
+        (
+            "[day15]\nrow = 1__0\n",
+            "expected valid TOML (`_` may only go between digits",
+        ),
+        (
+            "[day15]\nrows = 10\n",
+            "unknown parameter 'rows' in [day15]",
+        ),
+        (
+            "[day15.puzzle]\nsearch-max = -1\n",
+            "'search-max' in [day15.puzzle]: expected at least 0, found -1",
+        ),
+    ] {
+        std::fs::write(&path, config)?;
+        let mut cmd = Command::cargo_bin("day15")?;
+        cmd.args(["-i", "../day15/puzzle.txt", "--config"])
+            .arg(&path);
+        cmd.assert().code(1).stderr(predicate::str::contains(error));
+    }
+    std::fs::remove_file(&path)?;
+
+    let mut cmd = Command::cargo_bin("day15")?;
+    cmd.args(["-i", "../day15/puzzle.txt", "--search-max", "-5"]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "invalid value for --search-max: expected at least 0, found -5",
+    ));
+    Ok(())
+}
@@ -0,0 +1,355 @@
+use std::{
+    cmp::{max, min},
+    collections::HashMap,
+};
+
+use utils::{
+    generate::{Generate, Rng},
+    parse_number,
+    reference::Reference,
+    Input, ParseError, Solution,
+};
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: usize = 16;
+
+    type Input = Network;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        let (flows, connections) = parse_flows_and_connections(text)?;
+        let dist = all_shortest_costs(&connections);
+        let to_visit = get_relevant_valves(&flows);
+        Ok(Network {
+            dist,
+            flows,
+            to_visit,
+        })
+    }
+
+    fn part1(network: &Self::Input) -> Self::Part1 {
+        find_max_flow(
+            0,
+            30,
+            &network.dist,
+            &network.flows,
+            network.to_visit.clone(),
+        )
+    }
+
+    fn part2(network: &Self::Input) -> Self::Part2 {
+        find_alt_max_flow(&network.dist, &network.flows, network.to_visit.clone())
+    }
+}
+
+// The size is the number of valves with a flow, which are connected by corridors of valves
+// without one. It is at most 100, as there are not enough names for more valves
+impl Generate for Day16 {
+    const SIZE: usize = 15;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let num_flows = size.clamp(1, 100);
+        // AA is valve 0, followed by the valves with a flow and then the corridors
+        let mut tunnels = vec![vec![]; num_flows + 1];
+        for valve in 1..=num_flows {
+            let other = rng.below(valve);
+            let corridor = rng.below(4);
+            connect(&mut tunnels, valve, other, corridor);
+        }
+        for _ in 0..num_flows / 3 {
+            let (a, b) = (rng.below(num_flows + 1), rng.below(num_flows + 1));
+            if a != b {
+                let corridor = rng.between(1, 3) as usize;
+                connect(&mut tunnels, a, b, corridor);
+            }
+        }
+
+        let mut names: Vec<String> = ('A'..='Z')
+            .flat_map(|a| ('A'..='Z').map(move |b| format!("{}{}", a, b)))
+            .skip(1)
+            .collect();
+        rng.shuffle(&mut names);
+        names.insert(0, String::from("AA"));
+
+        let mut lines: Vec<String> = tunnels
+            .iter()
+            .enumerate()
+            .map(|(valve, tunnels)| {
+                let flow = match valve {
+                    1.. if valve <= num_flows => rng.between(3, 25),
+                    _ => 0,
+                };
+                let targets: Vec<&str> = tunnels.iter().map(|&t| names[t].as_str()).collect();
+                let tunnels = match targets[..] {
+                    [single] => format!("tunnel leads to valve {}", single),
+                    _ => format!("tunnels lead to valves {}", targets.join(", ")),
+                };
+                format!(
+                    "Valve {} has flow rate={}; {}\n",
+                    names[valve], flow, tunnels
+                )
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        lines.concat()
+    }
+}
+
+// Tries every move of every minute, remembering the best flow from each state. The open
+// valves are the bits of a number, which is enough for the small inputs this is meant for
+impl Reference for Day16 {
+    fn reference_part1(network: &Self::Input) -> Self::Part1 {
+        let mut known = HashMap::new();
+        release_alone(network, 30, 0, 0, &mut known)
+    }
+
+    fn reference_part2(network: &Self::Input) -> Option<Self::Part2> {
+        let mut known = HashMap::new();
+        Some(release_together(network, 26, (0, 0), 0, &mut known))
+    }
+}
+
+// The moves of a single minute at a valve: opening it (with the flow released until the end)
+// or walking to one of its neighbours or staying
+fn moves(network: &Network, time: usize, valve: usize, open: u64) -> Vec<(usize, usize, u64)> {
+    let mut moves = vec![(valve, 0, open)];
+    if network.flows[valve] > 0 && open & (1 << valve) == 0 {
+        moves.push((
+            valve,
+            network.flows[valve] * (time - 1),
+            open | (1 << valve),
+        ));
+    }
+    for (next, &dist) in network.dist[valve].iter().enumerate() {
+        if dist == 1 {
+            moves.push((next, 0, open));
+        }
+    }
+    moves
+}
+
+fn release_alone(
+    network: &Network,
+    time: usize,
+    valve: usize,
+    open: u64,
+    known: &mut HashMap<(usize, usize, u64), usize>,
+) -> usize {
+    if time == 0 {
+        return 0;
+    }
+    if let Some(&best) = known.get(&(time, valve, open)) {
+        return best;
+    }
+    let best = moves(network, time, valve, open)
+        .into_iter()
+        .map(|(next, released, open)| {
+            released + release_alone(network, time - 1, next, open, known)
+        })
+        .max()
+        .unwrap_or(0);
+    known.insert((time, valve, open), best);
+    best
+}
+
+fn release_together(
+    network: &Network,
+    time: usize,
+    (you, elephant): (usize, usize),
+    open: u64,
+    known: &mut HashMap<(usize, (usize, usize), u64), usize>,
+) -> usize {
+    if time == 0 {
+        return 0;
+    }
+    if let Some(&best) = known.get(&(time, (you, elephant), open)) {
+        return best;
+    }
+    let mut best = 0;
+    for (you, released, open) in moves(network, time, you, open) {
+        for (elephant, also_released, open) in moves(network, time, elephant, open) {
+            let rest = release_together(network, time - 1, (you, elephant), open, known);
+            best = max(best, released + also_released + rest);
+        }
+    }
+    known.insert((time, (you, elephant), open), best);
+    best
+}
+
+// Adds a path between two valves through new valves without a flow
+fn connect(tunnels: &mut Vec<Vec<usize>>, from: usize, to: usize, corridor: usize) {
+    let mut previous = from;
+    for _ in 0..corridor {
+        let next = tunnels.len();
+        tunnels.push(vec![previous]);
+        tunnels[previous].push(next);
+        previous = next;
+    }
+    tunnels[previous].push(to);
+    tunnels[to].push(previous);
+}
+
+// Valves are identified by their line index, with AA being 0
+pub struct Network {
+    dist: Vec<Vec<usize>>,
+    flows: Vec<usize>,
+    to_visit: Vec<usize>,
+}
+
+fn find_max_flow(
+    current: usize,
+    time: usize,
+    dist: &Vec<Vec<usize>>,
+    flows: &Vec<usize>,
+    to_visit: Vec<usize>,
+) -> usize {
+    let mut max_value = usize::MIN;
+
+    for i in 0..to_visit.len() {
+        let mut to_visit = to_visit.clone();
+        let selected = to_visit.remove(i);
+
+        if dist[current][selected] < time {
+            let remaining_time = time - dist[current][selected] - 1;
+            let new_val = flows[selected] * remaining_time
+                + find_max_flow(selected, remaining_time, dist, flows, to_visit);
+            max_value = max(max_value, new_val);
+        }
+    }
+    max_value
+}
+
+// Approach for Part 2 inspired by https://github.com/pauldraper/advent-of-code-2022/blob/main/problems/day-16/part_2.py
+// Look at all pairwise disjoint sets of valves that need to be visited
+// and determine weather doing each one indpendent (you + the elephant) yields together a higher value as encountered before
+fn find_alt_max_flow(dist: &Vec<Vec<usize>>, flows: &Vec<usize>, to_visit: Vec<usize>) -> usize {
+    let mut best = usize::MIN;
+    // Without valves to open there is still the partition of nothing
+    for partition in 0..1 << to_visit.len().saturating_sub(1) {
+        let a = to_visit
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &n)| (partition & (1 << i) != 0).then_some(n))
+            .collect();
+        let b = to_visit
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &n)| (partition & (1 << i) == 0).then_some(n))
+            .collect();
+        best = max(
+            best,
+            find_max_flow(0, 26, dist, flows, a) + find_max_flow(0, 26, dist, flows, b),
+        );
+    }
+    best
+}
+
+fn get_relevant_valves(flows: &[usize]) -> Vec<usize> {
+    flows
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &f)| (f > 0).then_some(i))
+        .collect()
+}
+
+struct Valve<'a> {
+    name: &'a str,
+    flow: usize,
+    tunnels: Vec<&'a str>,
+}
+
+// Parses a line like "Valve BB has flow rate=13; tunnels lead to valves CC, AA"
+fn parse_valve(line: &str) -> Result<Valve<'_>, ParseError> {
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    let (name, rate, tunnels) = match tokens.as_slice() {
+        ["Valve", name, "has", "flow", rate, _, _, "to", _, tunnels @ ..] => {
+            (*name, *rate, tunnels)
+        }
+        _ => {
+            return Err(ParseError::new(
+                line,
+                line,
+                "a valve like 'Valve BB has flow rate=13; tunnels lead to valves CC, AA'",
+            ))
+        }
+    };
+    let rate = rate
+        .strip_prefix("rate=")
+        .and_then(|r| r.strip_suffix(';'))
+        .ok_or_else(|| ParseError::new(line, rate, "a flow rate like rate=13;"))?;
+
+    Ok(Valve {
+        name,
+        flow: parse_number(line, rate)?,
+        tunnels: tunnels.iter().map(|t| t.trim_end_matches(',')).collect(),
+    })
+}
+
+// Valves are numbered in alphabetical order, which makes AA valve 0
+fn parse_flows_and_connections(text: &str) -> Result<(Vec<usize>, Vec<Vec<usize>>), ParseError> {
+    let mut valves = Input::new(text)
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_valve(line).map_err(|e| e.shifted(i)))
+        .collect::<Result<Vec<_>, _>>()?;
+    valves.sort_by_key(|v| v.name);
+    if valves.first().is_none_or(|v| v.name != "AA") {
+        return Err(ParseError::missing(text, "a line for valve AA"));
+    }
+
+    let indices = valves
+        .iter()
+        .enumerate()
+        .map(|(i, v)| (v.name, i))
+        .collect::<HashMap<_, _>>();
+    let flows = valves.iter().map(|v| v.flow).collect();
+    let connections = valves
+        .iter()
+        .map(|v| {
+            v.tunnels
+                .iter()
+                .map(|t| {
+                    indices
+                        .get(t)
+                        .copied()
+                        .ok_or_else(|| ParseError::new(text, t, "the name of a valve"))
+                })
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+    Ok((flows, connections))
+}
+
+fn all_shortest_costs(connections: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let n = connections.len();
+    let mut dist = vec![vec![usize::MAX; n]; n];
+
+    for (i, vec) in connections.iter().enumerate() {
+        for &j in vec {
+            dist[i][j] = 1;
+        }
+    }
+
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                dist[i][j] = min(dist[i][j], dist[i][k].saturating_add(dist[k][j]));
+            }
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::reference;
+
+    #[test]
+    fn agrees_with_reference() {
+        reference::check::<Day16>(0..20, 5);
+    }
+}
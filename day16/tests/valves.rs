@@ -42,3 +42,16 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn no_flow() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day16")?;
+    cmd.arg("Valve AA has flow rate=0; tunnels lead to valves BB\nValve BB has flow rate=0; tunnels lead to valves AA");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The maximal achievable flow is 0"))
+        .stdout(predicate::str::contains(
+            "The maximal achievable flow with elephant is 0",
+        ));
+    Ok(())
+}
@@ -0,0 +1,502 @@
+use std::{cmp::max, fmt::Display};
+
+use log::{debug, trace};
+use utils::{
+    animate::{Animate, Frame},
+    cycle::{self, Cycle},
+    generate::{Generate, Rng},
+    geom::{Direction, Point2},
+    grid::Grid,
+    reference::Reference,
+    Input, Param, Params, ParseError, Part, Solution,
+};
+
+const NUM_ROUNDS: usize = 1000000000000;
+const INTERMEDIATE_STEP: usize = 2022;
+// As we detect the cycle in the tower and therefore do not really stack to the height of
+// NUM_ROUNDS, I chose a still managable size dependent on the INTERMEDIATE_STEP
+// The chamber grows when the tower reaches its top, which --map-height can avoid for inputs
+// with a later cycle
+const DEFAULT_MAP_HEIGHT: usize = INTERMEDIATE_STEP * 1000;
+// The number of rows at the top of the tower that are shown while animating
+const VISIBLE_ROWS: usize = 40;
+// A rock appears three rows above the tower and is up to four rows high
+const SPAWN_ROWS: usize = 7;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+    const ANIMATED: bool = true;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "map-height",
+        help: "Number of rows of the chamber allocated up front (default: 2022000)",
+        min: VISIBLE_ROWS as i64,
+    }];
+
+    // The jet directions and the number of rows of the chamber
+    type Input = (Vec<Direction>, usize);
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let map_height = params
+            .get("map-height")
+            .map_or(DEFAULT_MAP_HEIGHT, |h| h as usize);
+        Ok((parse_directions(text)?, map_height))
+    }
+
+    fn part1((directions, map_height): &Self::Input) -> Self::Part1 {
+        execute_drops(directions, *map_height, INTERMEDIATE_STEP)
+    }
+
+    fn part2((directions, map_height): &Self::Input) -> Self::Part2 {
+        execute_drops(directions, *map_height, NUM_ROUNDS)
+    }
+}
+
+impl Animate for Day17 {
+    type Frame = Tower;
+
+    fn frames(
+        (directions, map_height): &Self::Input,
+        part: Part,
+    ) -> impl Iterator<Item = Self::Frame> + '_ {
+        // The second part only repeats the cycle of the tower, so it is shown until the
+        // tower has gone through it once
+        let rounds = match part {
+            Part::One => INTERMEDIATE_STEP,
+            Part::Two => {
+                let (_, cycle) = find_cycle(directions, *map_height);
+                INTERMEDIATE_STEP + cycle.start + cycle.length
+            }
+        };
+        let mut chamber = Chamber::new(directions, *map_height);
+        std::iter::once(chamber.tower()).chain((0..rounds).map(move |_| {
+            chamber.drop_rock();
+            chamber.tower()
+        }))
+    }
+}
+
+// The size is the number of jets
+impl Generate for Day17 {
+    const SIZE: usize = 10091;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        (0..size.max(1))
+            .map(|_| if rng.chance(0.5) { '<' } else { '>' })
+            .collect()
+    }
+}
+
+// Drops every rock instead of skipping the cycle, which is too slow for the second part
+impl Reference for Day17 {
+    fn reference_part1((directions, map_height): &Self::Input) -> Self::Part1 {
+        drop_all(directions, *map_height, INTERMEDIATE_STEP)
+    }
+
+    fn reference_part2(_: &Self::Input) -> Option<Self::Part2> {
+        None
+    }
+}
+
+fn drop_all(directions: &[Direction], map_height: usize, rounds: usize) -> usize {
+    let mut chamber = Chamber::new(directions, map_height);
+    (0..rounds).for_each(|_| chamber.drop_rock());
+    chamber.height()
+}
+
+fn execute_drops(directions: &[Direction], map_height: usize, rounds: usize) -> usize {
+    // The first rounds are dropped one by one, so that the first part does not depend on
+    // the signature of the tower being a good enough summary of it
+    if rounds <= INTERMEDIATE_STEP {
+        return drop_all(directions, map_height, rounds);
+    }
+    let (heights, cycle) = find_cycle(directions, map_height);
+    debug!(
+        "The tower grows by {} rows every {} rocks after {} rocks",
+        heights[cycle.start + cycle.length] - heights[cycle.start],
+        cycle.length,
+        INTERMEDIATE_STEP + cycle.start
+    );
+    cycle.extrapolate(rounds - INTERMEDIATE_STEP, &heights)
+}
+
+// The cycle of the tower after the first INTERMEDIATE_STEP rocks, with the heights of the
+// tower after every round from there on to extrapolate the height after any number of rounds
+fn find_cycle(directions: &[Direction], map_height: usize) -> (Vec<usize>, Cycle) {
+    let mut chamber = Chamber::new(directions, map_height);
+    (0..INTERMEDIATE_STEP).for_each(|_| chamber.drop_rock());
+    let mut heights = vec![chamber.height()];
+    // Approach for Part 2 inspired by https://github.com/jonathanpaulson/AdventOfCode/blob/master/2022/17.py
+    let cycle = cycle::hashed(
+        chamber,
+        |chamber| {
+            chamber.drop_rock();
+            heights.push(chamber.height());
+        },
+        Chamber::signature,
+    );
+    (heights, cycle)
+}
+
+// The state of the chamber after `round` rocks came to rest
+struct Chamber<'a> {
+    map: Map,
+    directions: &'a [Direction],
+    round: usize,
+    direction_idx: usize,
+    height: isize,
+}
+
+impl<'a> Chamber<'a> {
+    fn new(directions: &'a [Direction], map_height: usize) -> Self {
+        Chamber {
+            map: Map::new(map_height),
+            directions,
+            round: 0,
+            direction_idx: 0,
+            height: -1,
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        self.map.reserve(self.height() + SPAWN_ROWS);
+        let (new_height, new_direction_idx) = spawn_and_drop(
+            select_shape(self.round),
+            self.directions,
+            self.height,
+            self.direction_idx,
+            &mut self.map,
+        );
+        self.height = max(new_height, self.height);
+        self.direction_idx = new_direction_idx;
+        self.round += 1;
+        trace!(
+            "Rock {} came to rest, the tower is {} high",
+            self.round,
+            self.height()
+        );
+    }
+
+    fn height(&self) -> usize {
+        (self.height + 1) as usize
+    }
+
+    fn tower(&self) -> Tower {
+        let height = self.height();
+        let bottom = height.saturating_sub(VISIBLE_ROWS);
+        Tower {
+            rows: (bottom..height.max(VISIBLE_ROWS))
+                .map(|y| self.map.grid.row(y).to_vec())
+                .collect(),
+            bottom,
+            height,
+            round: self.round,
+        }
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::new(
+            self.direction_idx,
+            self.height(),
+            &self.map,
+            select_shape(self.round),
+        )
+    }
+}
+
+fn select_shape(idx: usize) -> Shape {
+    match idx % 5 {
+        0 => Shape::Horizontal,
+        1 => Shape::Cross,
+        2 => Shape::Angle,
+        3 => Shape::Vertical,
+        4 => Shape::Block,
+        _ => unreachable!(),
+    }
+}
+
+// Return (height, new_direction_idx)
+fn spawn_and_drop(
+    shape: Shape,
+    directions: &[Direction],
+    height: isize,
+    direction_idx: usize,
+    map: &mut Map,
+) -> (isize, usize) {
+    let piece = match shape {
+        Shape::Horizontal => Rock {
+            center: Point::new(2, height + 4),
+            shape,
+        },
+        Shape::Cross => Rock {
+            center: Point::new(3, height + 5),
+            shape,
+        },
+        Shape::Angle => Rock {
+            center: Point::new(4, height + 4),
+            shape,
+        },
+        Shape::Vertical => Rock {
+            center: Point::new(2, height + 4),
+            shape,
+        },
+        Shape::Block => Rock {
+            center: Point::new(2, height + 4),
+            shape,
+        },
+    };
+    let mut idx = direction_idx;
+    let mut landed = false;
+    let mut last_rock = piece;
+    while !landed {
+        let direction = directions[idx];
+        idx = (idx + 1) % directions.len();
+        let new_pos = map.move_piece(last_rock, direction);
+        if let Some(new_piece) = map.drop_piece(new_pos) {
+            last_rock = new_piece;
+        } else {
+            landed = true;
+        }
+    }
+    (last_rock.max_height(), idx)
+}
+
+fn parse_directions(text: &str) -> Result<Vec<Direction>, ParseError> {
+    let pattern = Input::new(text).text().trim();
+    if pattern.is_empty() {
+        return Err(ParseError::missing(text, "a jet pattern of < and >"));
+    }
+    pattern
+        .char_indices()
+        .map(|(i, c)| match c {
+            '<' => Ok(Direction::Left),
+            '>' => Ok(Direction::Right),
+            _ => Err(ParseError::new(
+                text,
+                &pattern[i..i + c.len_utf8()],
+                "< or >",
+            )),
+        })
+        .collect()
+}
+
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+enum Shape {
+    Horizontal, // Center is leftmost block
+    Cross,      // Center is center block
+    Angle,      // Center is corner block
+    Vertical,   // Center is lowest block
+    Block,      // Center is corner left down block
+}
+
+// Unlike on screen, y is the height in the chamber and grows upwards
+type Point = Point2<isize>;
+
+#[derive(Copy, Clone, Debug)]
+struct Rock {
+    center: Point,
+    shape: Shape,
+}
+
+impl Rock {
+    fn get_positions(&self) -> Option<Vec<Point>> {
+        let offsets: &[(isize, isize)] = match self.shape {
+            Shape::Horizontal => &[(0, 0), (1, 0), (2, 0), (3, 0)],
+            Shape::Cross => &[(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)],
+            Shape::Angle => &[(0, 0), (-1, 0), (-2, 0), (0, 1), (0, 2)],
+            Shape::Vertical => &[(0, 0), (0, 1), (0, 2), (0, 3)],
+            Shape::Block => &[(0, 0), (1, 0), (0, 1), (1, 1)],
+        };
+        let preliminary: Vec<Point> = offsets
+            .iter()
+            .map(|&(x, y)| self.center + Point::new(x, y))
+            .collect();
+        preliminary
+            .iter()
+            .all(|p| (0..=6).contains(&p.x) && p.y >= 0)
+            .then_some(preliminary)
+    }
+    fn max_height(&self) -> isize {
+        let positions = self.get_positions().expect("This should be valid");
+        positions
+            .iter()
+            .map(|p| p.y)
+            .max()
+            .expect("Should have max element")
+    }
+}
+
+// The top of the tower after `round` rocks, drawn like in the puzzle description
+pub struct Tower {
+    rows: Vec<Vec<char>>,
+    // The height of the lowest row in `rows`
+    bottom: usize,
+    height: usize,
+    round: usize,
+}
+
+impl Frame for Tower {
+    fn draw(&self) -> String {
+        let mut s = String::new();
+        for row in self.rows.iter().rev() {
+            s.push('|');
+            s.extend(row);
+            s.push_str("|\n");
+        }
+        if self.bottom == 0 {
+            s.push_str("+-------+\n");
+        }
+        s
+    }
+
+    fn status(&self) -> String {
+        format!("{} rocks, height {}", self.round, self.height)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Signature {
+    direction_idx: usize,
+    upper_grid: Vec<char>,
+    shape: Shape,
+}
+
+impl Signature {
+    fn new(direction_idx: usize, max_height: usize, map: &Map, shape: Shape) -> Self {
+        let upper_grid = map
+            .grid
+            .rows()
+            .skip(max_height.saturating_sub(30))
+            .take(30)
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        Self {
+            direction_idx,
+            upper_grid,
+            shape,
+        }
+    }
+}
+
+#[derive(Debug, Hash)]
+struct Map {
+    grid: Grid<char>,
+}
+
+impl Map {
+    fn new(upper_bound: usize) -> Self {
+        Self {
+            grid: Grid::new(7, upper_bound, '.'),
+        }
+    }
+
+    // Grows the map to at least `rows` rows
+    fn reserve(&mut self, rows: usize) {
+        let height = self.grid.height();
+        if rows > height {
+            let rows = rows.max(2 * height);
+            trace!("Growing the chamber from {} to {} rows", height, rows);
+            self.grid.grow(rows, '.');
+        }
+    }
+
+    fn move_piece(&self, piece: Rock, direction: Direction) -> Rock {
+        let new_piece = Rock {
+            center: piece.center.step(direction),
+            ..piece
+        };
+        let positions = new_piece.get_positions();
+        match positions {
+            Some(pos) => {
+                if self.check_positions(&pos) {
+                    new_piece
+                } else {
+                    piece
+                }
+            }
+            None => piece,
+        }
+    }
+
+    fn check_positions(&self, positions: &[Point]) -> bool {
+        positions
+            .iter()
+            .all(|p| self.grid[(p.x as usize, p.y as usize)] == '.')
+    }
+
+    fn set_positions(&mut self, piece: Rock) {
+        let positions = piece
+            .get_positions()
+            .expect("Setting a piece requries that the piece is valid");
+        positions.iter().for_each(|p| {
+            self.grid[(p.x as usize, p.y as usize)] = '#';
+        })
+    }
+
+    fn drop_piece(&mut self, piece: Rock) -> Option<Rock> {
+        let new_piece = Rock {
+            center: piece.center - Point::new(0, 1),
+            ..piece
+        };
+        let positions = new_piece.get_positions();
+        if let Some(vec) = positions {
+            if self.check_positions(&vec) {
+                Some(new_piece)
+            } else {
+                self.set_positions(piece);
+                None
+            }
+        } else {
+            self.set_positions(piece);
+            None
+        }
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut s = String::new();
+        for row in self.grid.rows().rev() {
+            s.push_str(row.iter().collect::<String>().as_str());
+            s.push('\n');
+        }
+        writeln!(f, "{}", s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::{generate, reference};
+
+    #[test]
+    fn agrees_with_reference() {
+        reference::check::<Day17>(0..20, 40);
+    }
+
+    #[test]
+    fn skips_cycles_like_dropping_every_rock() {
+        for seed in 0..10 {
+            let text = generate::input::<Day17>(seed, Some(30));
+            let (directions, _) = Day17::parse(&text).expect("Generated jets should be valid");
+            for rounds in [0, 1, 5, 99, 1234, 5000, 7919] {
+                assert_eq!(
+                    execute_drops(&directions, VISIBLE_ROWS, rounds),
+                    drop_all(&directions, 20000, rounds),
+                    "{} rounds with jets {}",
+                    rounds,
+                    text
+                );
+            }
+        }
+    }
+}
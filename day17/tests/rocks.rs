@@ -27,3 +27,47 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn small_chamber() -> Result<(), Box<dyn std::error::Error>> {
+    // The chamber grows when the tower reaches its top
+    let mut cmd = Command::cargo_bin("day17")?;
+    cmd.args([
+        "--map-height",
+        "40",
+        ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The maximum height is 3068"))
+        .stdout(predicate::str::contains(
+            "The maximum height for the ridiculous amount of rounds is 1514285714288",
+        ));
+
+    let mut cmd = Command::cargo_bin("day17")?;
+    cmd.args(["--map-height", "40", "--animate", "--fps", "1000"])
+        .arg(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("2022 rocks, height 3068"));
+
+    // The second part ends once the tower has repeated itself
+    let mut cmd = Command::cargo_bin("day17")?;
+    cmd.args(["--map-height", "40", "--animate", "--fps", "100000"])
+        .args(["--part", "2", ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("2057 rocks, height 3121"));
+
+    // The animation shows the top 40 rows
+    let mut cmd = Command::cargo_bin("day17")?;
+    cmd.args([
+        "--map-height",
+        "10",
+        ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>",
+    ]);
+    cmd.assert().code(2).stderr(predicate::str::contains(
+        "invalid value for --map-height: expected at least 40, found 10",
+    ));
+    Ok(())
+}
@@ -0,0 +1,242 @@
+use std::collections::{HashSet, VecDeque};
+
+use utils::{
+    generate::{Generate, Rng},
+    geom::Point3,
+    parse_number,
+    reference::Reference,
+    Input, ParseError, Solution,
+};
+
+pub struct Day18;
+
+type Cube = Point3<isize>;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+
+    type Input = Vec<Cube>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        parse_points(text)
+    }
+
+    fn part1(points: &Self::Input) -> Self::Part1 {
+        let points_set = create_hashset(points);
+        get_total_free_sides(points, &points_set)
+    }
+
+    fn part2(points: &Self::Input) -> Self::Part2 {
+        let points_set = create_hashset(points);
+        get_number_exposed(&points_set)
+    }
+}
+
+// The size is the number of cubes, which fill most of a ball and leave air pockets inside
+impl Generate for Day18 {
+    const SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        let radius = (size as f64 / 0.8 / (4.0 / 3.0 * std::f64::consts::PI)).cbrt() + 1.0;
+        let r = radius.ceil() as isize;
+        let mut cubes = vec![];
+        for x in -r..=r {
+            for y in -r..=r {
+                for z in -r..=r {
+                    if ((x * x + y * y + z * z) as f64).sqrt() <= radius {
+                        cubes.push((x + r + 1, y + r + 1, z + r + 1));
+                    }
+                }
+            }
+        }
+        rng.shuffle(&mut cubes);
+        cubes
+            .into_iter()
+            .take(size)
+            .map(|(x, y, z)| format!("{},{},{}\n", x, y, z))
+            .collect()
+    }
+}
+
+// Compares every pair of cubes for the first part, and floods the air around the droplet
+// for the second
+impl Reference for Day18 {
+    fn reference_part1(points: &Self::Input) -> Self::Part1 {
+        let points: Vec<Cube> = create_hashset(points).into_iter().collect();
+        let mut touching = 0;
+        for (i, &a) in points.iter().enumerate() {
+            for &b in &points[i + 1..] {
+                if a.manhattan(b) == 1 {
+                    touching += 1;
+                }
+            }
+        }
+        6 * points.len() - 2 * touching
+    }
+
+    fn reference_part2(points: &Self::Input) -> Option<Self::Part2> {
+        let points_set = create_hashset(points);
+        let (low, high) = bounding_box(&points_set);
+        let (low, high) = (low - Point3::new(1, 1, 1), high + Point3::new(1, 1, 1));
+        let mut air = HashSet::from([low]);
+        let mut to_inspect = vec![low];
+        let mut exposed = 0;
+        while let Some(point) = to_inspect.pop() {
+            for next in point.neighbours6() {
+                if points_set.contains(&next) {
+                    exposed += 1;
+                } else if !is_outside(next, (low, high)) && air.insert(next) {
+                    to_inspect.push(next);
+                }
+            }
+        }
+        Some(exposed)
+    }
+}
+
+fn parse_points(text: &str) -> Result<Vec<Cube>, ParseError> {
+    let input = Input::new(text).dedent();
+    let text = input.text();
+    input
+        .lines()
+        .map(
+            |line| match line.split(',').collect::<Vec<_>>().as_slice() {
+                [x, y, z] => Ok(Point3::new(
+                    parse_number(text, x)?,
+                    parse_number(text, y)?,
+                    parse_number(text, z)?,
+                )),
+                _ => Err(ParseError::new(text, line, "a point like 2,2,2")),
+            },
+        )
+        .collect()
+}
+
+fn create_hashset(points: &[Cube]) -> HashSet<Cube> {
+    let mut points_set = HashSet::new();
+    points.iter().for_each(|&p| {
+        points_set.insert(p);
+    });
+    points_set
+}
+
+fn get_number_free_sides(point: Cube, points_set: &HashSet<Cube>) -> usize {
+    let blocked_sides: usize = point
+        .neighbours6()
+        .map(|p| points_set.contains(&p) as usize)
+        .sum();
+    6 - blocked_sides
+}
+
+fn get_total_free_sides(points: &[Cube], points_set: &HashSet<Cube>) -> usize {
+    points
+        .iter()
+        .map(|&p| get_number_free_sides(p, points_set))
+        .sum()
+}
+
+// Approach for Part 2 inspired by https://github.com/jonathanpaulson/AdventOfCode/blob/master/2022/18.py
+fn reaches_outside(
+    point: Cube,
+    point_set: &HashSet<Cube>,
+    bounds: (Cube, Cube),
+    outside: &mut HashSet<Cube>,
+    inside: &mut HashSet<Cube>,
+) -> bool {
+    let mut seen = HashSet::new();
+    if outside.contains(&point) {
+        return true;
+    }
+    if inside.contains(&point) {
+        return false;
+    }
+
+    let mut to_inspect = VecDeque::new();
+    to_inspect.push_back(point);
+
+    while !to_inspect.is_empty() {
+        let new_point = to_inspect.pop_front().expect("Must exist");
+        if point_set.contains(&new_point) || seen.contains(&new_point) {
+            continue;
+        }
+        seen.insert(new_point);
+        if is_outside(new_point, bounds) {
+            for point in seen.iter() {
+                outside.insert(*point);
+            }
+            return true;
+        }
+        to_inspect.extend(new_point.neighbours6());
+    }
+    for point in seen.iter() {
+        inside.insert(*point);
+    }
+    false
+}
+
+// The smallest and largest coordinates of the cubes, as corners of a box around them
+fn bounding_box(points_set: &HashSet<Cube>) -> (Cube, Cube) {
+    let low = |f: fn(&Cube) -> isize| points_set.iter().map(f).min().unwrap_or(0);
+    let high = |f: fn(&Cube) -> isize| points_set.iter().map(f).max().unwrap_or(0);
+    (
+        Point3::new(low(|p| p.x), low(|p| p.y), low(|p| p.z)),
+        Point3::new(high(|p| p.x), high(|p| p.y), high(|p| p.z)),
+    )
+}
+
+fn is_outside(point: Cube, (low, high): (Cube, Cube)) -> bool {
+    point.x < low.x
+        || point.y < low.y
+        || point.z < low.z
+        || point.x > high.x
+        || point.y > high.y
+        || point.z > high.z
+}
+
+fn get_number_exposed(points_set: &HashSet<Cube>) -> usize {
+    let bounds = bounding_box(points_set);
+    let mut result = 0;
+    let mut outside = HashSet::new();
+    let mut inside = HashSet::new();
+
+    for point in points_set.iter() {
+        point.neighbours6().for_each(|p| {
+            if reaches_outside(p, points_set, bounds, &mut outside, &mut inside) {
+                result += 1;
+            }
+        })
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::reference;
+
+    #[test]
+    fn agrees_with_reference() {
+        reference::check::<Day18>(0..20, 300);
+    }
+
+    #[test]
+    fn large_air_pocket_is_inside() {
+        // The shell of a cube with a side of 20 holds more air than any droplet of the puzzle
+        let mut text = String::new();
+        for x in 0..20 {
+            for y in 0..20 {
+                for z in 0..20 {
+                    if [x, y, z].iter().any(|&c| c == 0 || c == 19) {
+                        text.push_str(&format!("{},{},{}\n", x, y, z));
+                    }
+                }
+            }
+        }
+        reference::check_input::<Day18>(&text, "hollow cube");
+        let points = Day18::parse(&text).expect("Cubes should be valid");
+        assert_eq!(Day18::part2(&points), 6 * 20 * 20);
+    }
+}
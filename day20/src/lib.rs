@@ -0,0 +1,132 @@
+use std::collections::VecDeque;
+
+use utils::{
+    generate::{Generate, Rng},
+    parse_number, Input, Param, Params, ParseError, Solution,
+};
+
+const DEFAULT_DECRYPTION_KEY: isize = 811589153;
+// Both parts add up three numbers, which has to fit even after applying the decryption key
+const MAX_NUMBER: usize = isize::MAX as usize / 3;
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: usize = 20;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "decryption-key",
+        help: "Factor applied to the numbers in the second part (default: 811589153)",
+        min: i64::MIN,
+    }];
+
+    // The numbers with their original positions and the decryption key
+    type Input = (VecDeque<(usize, isize)>, isize);
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let key = params
+            .get("decryption-key")
+            .map_or(DEFAULT_DECRYPTION_KEY, |key| key as isize);
+        Ok((parse_numbers(text, key)?, key))
+    }
+
+    fn part1((numbers, _): &Self::Input) -> Self::Part1 {
+        let mut numbers = numbers.clone();
+        mix(&mut numbers);
+        get_grove_coords(&numbers)
+    }
+
+    fn part2((numbers, key): &Self::Input) -> Self::Part2 {
+        let mut numbers_alt: VecDeque<(usize, isize)> =
+            numbers.iter().map(|(i, x)| (*i, *x * key)).collect();
+        mix_alt(&mut numbers_alt);
+        get_grove_coords(&numbers_alt)
+    }
+}
+
+// The size is the number of numbers, exactly one of which is 0
+impl Generate for Day20 {
+    const SIZE: usize = 5000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let zero = rng.below(size.max(2));
+        (0..size.max(2))
+            .map(|i| {
+                let number = match i {
+                    _ if i == zero => 0,
+                    _ => loop {
+                        let number = rng.between(-10000, 10000);
+                        if number != 0 {
+                            break number;
+                        }
+                    },
+                };
+                format!("{}\n", number)
+            })
+            .collect()
+    }
+}
+
+fn parse_numbers(text: &str, key: isize) -> Result<VecDeque<(usize, isize)>, ParseError> {
+    let input = Input::new(text);
+    let mut numbers = VecDeque::new();
+    for word in input.text().split_whitespace() {
+        let number: isize = parse_number(input.text(), word)?;
+        let fits = |n: isize| n.unsigned_abs() <= MAX_NUMBER;
+        if !fits(number) || !number.checked_mul(key).is_some_and(fits) {
+            let expected = format!(
+                "a number of at most {} with and without the decryption key",
+                MAX_NUMBER
+            );
+            return Err(ParseError::new(input.text(), word, expected));
+        }
+        numbers.push_back((numbers.len(), number));
+    }
+    // The grove coordinates are counted from the 0
+    if numbers.iter().all(|&(_, number)| number != 0) {
+        return Err(ParseError::missing(text, "the number 0"));
+    }
+    Ok(numbers)
+}
+
+fn mix_item(item: usize, numbers: &mut VecDeque<(usize, isize)>) {
+    while numbers.front().unwrap().0 != item {
+        let popped = numbers.pop_front().unwrap();
+        numbers.push_back(popped);
+    }
+    let item_entry = numbers.pop_front().unwrap();
+    // A single number has nowhere to move
+    let num_away = item_entry.1.rem_euclid(numbers.len().max(1) as isize);
+    (0..num_away).for_each(|_| {
+        let popped = numbers.pop_front().unwrap();
+        numbers.push_back(popped);
+    });
+    numbers.push_back(item_entry);
+}
+
+fn mix(numbers: &mut VecDeque<(usize, isize)>) {
+    (0..numbers.len()).for_each(|i| mix_item(i, numbers));
+}
+
+fn mix_alt(numbers: &mut VecDeque<(usize, isize)>) {
+    (0..10).for_each(|_| mix(numbers));
+}
+
+fn get_grove_coords(numbers: &VecDeque<(usize, isize)>) -> isize {
+    let zero_idx = numbers
+        .iter()
+        .enumerate()
+        .find(|(_, (_, x))| *x == 0)
+        .expect("Should exist")
+        .0;
+    let first = (zero_idx + 1000) % numbers.len();
+    let second = (zero_idx + 2000) % numbers.len();
+    let third = (zero_idx + 3000) % numbers.len();
+
+    numbers[first].1 + numbers[second].1 + numbers[third].1
+}
@@ -35,3 +35,28 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn invalid_numbers() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day20")?;
+    cmd.arg("1\n2\n3");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("expected the number 0"));
+
+    let mut cmd = Command::cargo_bin("day20")?;
+    cmd.arg("0\n20000000000");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a number of at most 3074457345618258602 with and without the decryption key, found '20000000000'",
+        ))
+        .stderr(predicate::str::contains("--> line 2, column 1"));
+
+    let mut cmd = Command::cargo_bin("day20")?;
+    cmd.arg("0");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The grove coordinates are 0"));
+    Ok(())
+}
@@ -0,0 +1,561 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use utils::{
+    generate::{Generate, Rng},
+    reference::Reference,
+    Input, ParseError, Solution,
+};
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+
+    type Input = HashMap<String, Monkey>;
+    type Part1 = Option<i64>;
+    type Part2 = Option<i64>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        let input = Input::new(text).dedent();
+        let monkeys = input.parse_lines::<Monkey>()?;
+        check_references(input, &monkeys)?;
+        Ok(create_monkey_table(&monkeys))
+    }
+
+    fn part1(monkey_table: &Self::Input) -> Self::Part1 {
+        let root_monkey = monkey_table.get("root").expect("Must exist");
+        // A division by zero yells an infinite number, which is no answer
+        let result = root_monkey.evaluate(monkey_table);
+        (result.is_finite() && result.abs() < i64::MAX as f64).then_some(result as i64)
+    }
+
+    fn part2(monkey_table: &Self::Input) -> Self::Part2 {
+        let root_monkey = monkey_table.get("root").expect("Must exist");
+        find_human_res(monkey_table, root_monkey)
+    }
+}
+
+// Values on the way to root stay below this, so that they are exact as floats
+const GENERATED_LIMIT: i128 = 10_000_000_000_000;
+
+// The size is roughly the number of monkeys. On the path from humn to root, values are only
+// divided where that is exact, both for the answer and the number humn shouts in the input,
+// and a change of humn changes root's side by at least as much, so that both parts find
+// integers
+impl Generate for Day21 {
+    const SIZE: usize = 2000;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let mut riddle = Riddle::default();
+        let steps = size / 25 + 1;
+
+        let answer = rng.between(1_000, 1_000_000_000_000);
+        let shouted = answer + 5040 * rng.between(1, 1000);
+        riddle.lines.push(format!("humn: {}", shouted));
+        // The value of the path for the answer, how much larger it is for the shouted number and
+        // how much it changes per step of humn as a fraction
+        let (mut value, mut change) = (answer as i128, (shouted - answer) as i128);
+        let (mut numerator, mut denominator) = (1i128, 1i128);
+        let mut human_side = String::from("humn");
+        for _ in 0..steps {
+            let (constant, c) = riddle.constant(rng);
+            let c = c as i128;
+            let fits = |v: i128, change: i128| {
+                v.abs() < GENERATED_LIMIT && (v + change).abs() < GENERATED_LIMIT
+            };
+            let mut options = vec![];
+            if fits(value + c, change) {
+                options.extend(["x + c", "c + x"]);
+            }
+            if fits(value - c, change) {
+                options.push("x - c");
+            }
+            if fits(c - value, -change) {
+                options.push("c - x");
+            }
+            if fits(value * c, change * c) {
+                options.extend(["x * c", "c * x"]);
+            }
+            if value % c == 0 && change % c == 0 && numerator.abs() >= denominator * c {
+                options.push("x / c");
+            }
+            let operation = options
+                .get(rng.below(options.len().max(1)))
+                .unwrap_or(&"x + c");
+            match *operation {
+                "x + c" | "c + x" => value += c,
+                "x - c" => value -= c,
+                "c - x" => (value, change, numerator) = (c - value, -change, -numerator),
+                "x * c" | "c * x" => {
+                    (value, change, numerator) = (value * c, change * c, numerator * c)
+                }
+                _ => (value, change, denominator) = (value / c, change / c, denominator * c),
+            }
+            human_side = riddle.monkey(rng, fill_in(operation, &human_side, &constant));
+        }
+
+        let other_side = riddle.equal_to(rng, value, steps);
+        let (left, right) = match rng.chance(0.5) {
+            true => (human_side, other_side),
+            false => (other_side, human_side),
+        };
+        riddle.lines.push(format!("root: {} + {}", left, right));
+        rng.shuffle(&mut riddle.lines);
+        riddle.lines.join("\n") + "\n"
+    }
+}
+
+#[derive(Default)]
+struct Riddle {
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Riddle {
+    fn monkey(&mut self, rng: &mut Rng, job: String) -> String {
+        let name = loop {
+            let name: String = (0..4)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect();
+            if name != "root" && name != "humn" && self.names.insert(name.clone()) {
+                break name;
+            }
+        };
+        self.lines.push(format!("{}: {}", name, job));
+        name
+    }
+
+    // A random tree of monkeys with small numbers, returning its root and its value
+    fn constant(&mut self, rng: &mut Rng) -> (String, i64) {
+        let mut trees: Vec<(String, i64)> = (0..rng.between(1, 12))
+            .map(|_| {
+                let number = rng.between(1, 20);
+                (self.monkey(rng, number.to_string()), number)
+            })
+            .collect();
+        while trees.len() > 1 {
+            let (a_name, a) = trees.swap_remove(rng.below(trees.len()));
+            let (b_name, b) = trees.swap_remove(rng.below(trees.len()));
+            let mut operators = vec!['+'];
+            if a * b <= 1_000_000 {
+                operators.push('*');
+            }
+            if a > b {
+                operators.push('-');
+            }
+            if a % b == 0 {
+                operators.push('/');
+            }
+            let operator = *rng.choose(&operators);
+            let value = match operator {
+                '+' => a + b,
+                '*' => a * b,
+                '-' => a - b,
+                _ => a / b,
+            };
+            let job = format!("{} {} {}", a_name, operator, b_name);
+            trees.push((self.monkey(rng, job), value));
+        }
+        trees.pop().expect("There is at least one number")
+    }
+
+    // A chain of monkeys with constants on the side that evaluates to `target`
+    fn equal_to(&mut self, rng: &mut Rng, mut target: i128, steps: usize) -> String {
+        let name = self.monkey(rng, String::new());
+        let mut pending = self.lines.len() - 1;
+        for _ in 0..steps {
+            let (constant, c) = self.constant(rng);
+            let c = c as i128;
+            let mut options = vec!["x + c", "c + x", "x - c", "c - x"];
+            if target % c == 0 {
+                options.push("x * c");
+            }
+            if (target * c).abs() < GENERATED_LIMIT {
+                options.push("x / c");
+            }
+            let operation = *rng.choose(&options);
+            target = match operation {
+                "x + c" | "c + x" => target - c,
+                "x - c" => target + c,
+                "c - x" => c - target,
+                "x * c" => target / c,
+                _ => target * c,
+            };
+            let operand = self.monkey(rng, String::new());
+            self.lines[pending] += &fill_in(operation, &operand, &constant);
+            pending = self.lines.len() - 1;
+        }
+        self.lines[pending] += &target.to_string();
+        name
+    }
+}
+
+// Replaces x and c in an operation like "c - x" by the names of the monkeys
+fn fill_in(operation: &str, x: &str, c: &str) -> String {
+    let tokens: Vec<&str> = operation
+        .split(' ')
+        .map(|token| match token {
+            "x" => x,
+            "c" => c,
+            operator => operator,
+        })
+        .collect();
+    tokens.join(" ")
+}
+
+// Calculates with exact fractions instead of floats, and undoes the operations on the way
+// from root to humn instead of searching for its number
+impl Reference for Day21 {
+    fn reference_part1(monkey_table: &Self::Input) -> Self::Part1 {
+        as_integer(exact_value(monkey_table, "root"))
+    }
+
+    fn reference_part2(monkey_table: &Self::Input) -> Option<Self::Part2> {
+        let (mut name, mut target) = root_sides(monkey_table);
+        while name != "humn" {
+            let monkey = &monkey_table[name];
+            let (left, right) = (monkey.left.as_ref()?, monkey.right.as_ref()?);
+            let operator = monkey.operator.as_ref()?;
+            (name, target) = if depends_on_human(monkey_table, left) {
+                let known = exact_value(monkey_table, right);
+                let target = match operator {
+                    Operator::Add => subtract(target, known),
+                    Operator::Subtract => add(target, known),
+                    Operator::Multiply => divide(target, known),
+                    Operator::Divide | Operator::Equals => multiply(target, known),
+                };
+                (left, target)
+            } else {
+                let known = exact_value(monkey_table, left);
+                let target = match operator {
+                    Operator::Add => subtract(target, known),
+                    Operator::Subtract => subtract(known, target),
+                    Operator::Multiply => divide(target, known),
+                    Operator::Divide | Operator::Equals => divide(known, target),
+                };
+                (right, target)
+            };
+        }
+        Some(as_integer(target))
+    }
+}
+
+// A fraction as numerator and positive denominator without common divisors
+type Fraction = (i128, i128);
+
+fn fraction(numerator: i128, denominator: i128) -> Fraction {
+    let (mut a, mut b) = (numerator.abs(), denominator.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    let sign = denominator.signum();
+    (sign * numerator / a.max(1), sign * denominator / a.max(1))
+}
+
+fn add((a, b): Fraction, (c, d): Fraction) -> Fraction {
+    fraction(a * d + c * b, b * d)
+}
+
+fn subtract(x: Fraction, (c, d): Fraction) -> Fraction {
+    add(x, (-c, d))
+}
+
+fn multiply((a, b): Fraction, (c, d): Fraction) -> Fraction {
+    fraction(a * c, b * d)
+}
+
+fn divide(x: Fraction, (c, d): Fraction) -> Fraction {
+    multiply(x, (d, c))
+}
+
+// The integer of a fraction, if it is one, which it is not after a division by zero
+fn as_integer((numerator, denominator): Fraction) -> Option<i64> {
+    (denominator == 1).then_some(numerator as i64)
+}
+
+fn exact_value(monkey_table: &HashMap<String, Monkey>, name: &str) -> Fraction {
+    let monkey = &monkey_table[name];
+    if let Some(result) = monkey.result {
+        return fraction(result as i128, 1);
+    }
+    let left = exact_value(
+        monkey_table,
+        monkey.left.as_ref().expect("Should have a left side"),
+    );
+    let right = exact_value(
+        monkey_table,
+        monkey.right.as_ref().expect("Should have a right side"),
+    );
+    match monkey.operator.as_ref().expect("Should have an operator") {
+        Operator::Add => add(left, right),
+        Operator::Subtract => subtract(left, right),
+        Operator::Multiply => multiply(left, right),
+        Operator::Divide => divide(left, right),
+        Operator::Equals => fraction((left == right) as i128, 1),
+    }
+}
+
+fn depends_on_human(monkey_table: &HashMap<String, Monkey>, name: &str) -> bool {
+    let monkey = &monkey_table[name];
+    name == "humn"
+        || [&monkey.left, &monkey.right]
+            .into_iter()
+            .flatten()
+            .any(|side| depends_on_human(monkey_table, side))
+}
+
+// The side of root that depends on humn, and the value of the other one
+fn root_sides(monkey_table: &HashMap<String, Monkey>) -> (&str, Fraction) {
+    let root = &monkey_table["root"];
+    let left = root.left.as_deref().expect("Root should have a left side");
+    let right = root
+        .right
+        .as_deref()
+        .expect("Root should have a right side");
+    if depends_on_human(monkey_table, left) {
+        (left, exact_value(monkey_table, right))
+    } else {
+        (right, exact_value(monkey_table, left))
+    }
+}
+
+// `None` if humn is not on exactly one side of root or no number of it makes them equal
+fn find_human_res(lookup_table: &HashMap<String, Monkey>, root_monkey: &Monkey) -> Option<i64> {
+    // Construct new root monkey with Equals
+    let root_left = root_monkey.left.as_ref().unwrap().clone();
+    let root_right = root_monkey.right.as_ref().unwrap().clone();
+    let new_root = Monkey {
+        name: "root".to_string(),
+        result: None,
+        left: Some(root_left.clone()),
+        right: Some(root_right.clone()),
+        operator: Some(Operator::Equals),
+    };
+
+    // Check whether the left or right hand side of the root monkey is dependent on humn
+    let template_human = Monkey {
+        name: "humn".to_string(),
+        result: Some(0.0),
+        left: None,
+        right: None,
+        operator: None,
+    };
+    let comp_human = Monkey {
+        result: Some(10000.0),
+        ..template_human.clone()
+    };
+    let mut base_lookup = lookup_table.clone();
+    base_lookup.insert("humn".to_string(), template_human.clone());
+    let mut comp_lookup = lookup_table.clone();
+    comp_lookup.insert("humn".to_string(), comp_human);
+
+    let mut to_reach = lookup_table.get(&root_left).unwrap().clone();
+    let mut variable = lookup_table.get(&root_right).unwrap().clone();
+
+    if to_reach.evaluate(&base_lookup) != to_reach.evaluate(&comp_lookup) {
+        (variable, to_reach) = (to_reach, variable);
+    }
+    if variable.evaluate(&base_lookup) == variable.evaluate(&comp_lookup)
+        || to_reach.evaluate(&base_lookup) != to_reach.evaluate(&comp_lookup)
+    {
+        return None;
+    }
+
+    let to_reach = to_reach.evaluate(&base_lookup);
+    // We do not know how changing the value of humn changes the value of the variable monkey that depends on humn
+    // Therefore, we change the way we reduce the search space in our binary search to the opposite in a consecutive run
+    for i in [-1.0, 1.0] {
+        let mut low = 0;
+        let mut high = i64::MAX / 2;
+        while low <= high {
+            let mid = (low + high) / 2;
+            let human = Monkey {
+                result: Some(mid as f64),
+                ..template_human.clone()
+            };
+            base_lookup.insert("humn".to_string(), human);
+            let result = variable.evaluate(&base_lookup);
+
+            if result * i < to_reach * i {
+                high = mid - 1;
+            } else if result * i > to_reach * i {
+                low = mid + 1;
+            } else {
+                // A division by zero can make both sides equal without the test passing
+                return (new_root.evaluate(&base_lookup) == 1.0).then_some(mid);
+            }
+        }
+    }
+    None
+}
+
+fn create_monkey_table(monkeys: &[Monkey]) -> HashMap<String, Monkey> {
+    let mut result = HashMap::new();
+    monkeys.iter().for_each(|m| {
+        result.insert(m.name.clone(), m.clone());
+    });
+    result
+}
+
+// Evaluating the monkeys relies on every name they wait for being known, on root waiting for
+// two monkeys and on no monkey waiting for itself
+fn check_references(input: Input, monkeys: &[Monkey]) -> Result<(), ParseError> {
+    let text = input.text();
+    let lines: HashMap<&str, (&str, &Monkey)> = input
+        .lines()
+        .zip(monkeys)
+        .map(|(line, monkey)| (monkey.name.as_str(), (line, monkey)))
+        .collect();
+    let Some(&(root_line, root)) = lines.get("root") else {
+        return Err(ParseError::missing(text, "a monkey named root"));
+    };
+    if root.operator.is_none() {
+        return Err(ParseError::new(
+            text,
+            root_line,
+            "root waiting for two monkeys",
+        ));
+    }
+    for (line, monkey) in input.lines().zip(monkeys) {
+        for name in [&monkey.left, &monkey.right].into_iter().flatten() {
+            if !lines.contains_key(name.as_str()) {
+                let start = line.rfind(name.as_str()).unwrap_or_default();
+                let offending = &line[start..start + name.len()];
+                return Err(ParseError::new(text, offending, "the name of a monkey"));
+            }
+        }
+    }
+
+    // Depth-first search along the path of monkeys waiting for each other, where a monkey
+    // that is already on the path waits for itself
+    let mut done: HashSet<&str> = HashSet::new();
+    for monkey in monkeys {
+        let mut path = vec![(monkey.name.as_str(), 0)];
+        while let Some((name, side)) = path.pop() {
+            let (line, monkey) = lines[name];
+            match [&monkey.left, &monkey.right]
+                .into_iter()
+                .flatten()
+                .nth(side)
+            {
+                None => {
+                    done.insert(name);
+                }
+                Some(next) if done.contains(next.as_str()) => path.push((name, side + 1)),
+                Some(next) => {
+                    if next == name || path.iter().any(|&(other, _)| other == next) {
+                        let expected = "a monkey that does not wait for itself";
+                        return Err(ParseError::new(text, line, expected));
+                    }
+                    path.push((name, side + 1));
+                    path.push((next, 0));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Equals,
+}
+
+impl Operator {
+    fn new(symbol: &str) -> Option<Self> {
+        match symbol {
+            "+" => Some(Operator::Add),
+            "-" => Some(Operator::Subtract),
+            "*" => Some(Operator::Multiply),
+            "/" => Some(Operator::Divide),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    name: String,
+    result: Option<f64>,
+    left: Option<String>,
+    right: Option<String>,
+    operator: Option<Operator>,
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, rest) = s
+            .split_once(':')
+            .ok_or_else(|| ParseError::new(s, s, "a monkey like 'root: pppw + sjmn'"))?;
+        if let Ok(result) = rest.trim().parse::<f64>() {
+            return Ok(Monkey {
+                name: name.to_string(),
+                result: Some(result),
+                left: None,
+                right: None,
+                operator: None,
+            });
+        }
+        let [left, symbol, right] = rest.split_whitespace().collect::<Vec<_>>()[..] else {
+            return Err(ParseError::new(
+                s,
+                rest.trim(),
+                "a number or an operation like 'pppw + sjmn'",
+            ));
+        };
+        let operator = Operator::new(symbol)
+            .ok_or_else(|| ParseError::new(s, symbol, "an operator (+, -, * or /)"))?;
+        Ok(Monkey {
+            name: name.to_string(),
+            result: None,
+            left: Some(left.to_string()),
+            right: Some(right.to_string()),
+            operator: Some(operator),
+        })
+    }
+}
+
+impl Monkey {
+    fn evaluate(&self, lookup_table: &HashMap<String, Monkey>) -> f64 {
+        if let Some(res) = self.result {
+            return res;
+        }
+        // Use the underlying invariant here:
+        // If result == None, then left, right and operator are not None
+        // Also, all monkeys are contained in the lookup table, therefore we can use unwrap()
+        let left_monkey = lookup_table.get(self.left.as_ref().unwrap()).unwrap();
+        let right_monkey = lookup_table.get(self.right.as_ref().unwrap()).unwrap();
+        let operator = self.operator.as_ref().unwrap();
+
+        let left_res = left_monkey.evaluate(lookup_table);
+        let right_res = right_monkey.evaluate(lookup_table);
+
+        match operator {
+            Operator::Add => left_res + right_res,
+            Operator::Subtract => left_res - right_res,
+            Operator::Multiply => left_res * right_res,
+            Operator::Divide => left_res / right_res,
+            Operator::Equals => (left_res == right_res) as usize as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use utils::reference;
+
+    #[test]
+    fn agrees_with_reference() {
+        reference::check::<Day21>(0..20, 200);
+    }
+}
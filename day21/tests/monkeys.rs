@@ -41,3 +41,86 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         .stdout(predicate::str::contains("We need to yell 3887609741189"));
     Ok(())
 }
+
+#[test]
+fn unknown_monkey() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day21")?;
+    cmd.arg(
+        "root: pppw + sjmn
+pppw: 3",
+    );
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected the name of a monkey, found 'sjmn'",
+        ))
+        .stderr(predicate::str::contains("--> line 1, column 14"));
+    Ok(())
+}
+
+#[test]
+fn monkey_waiting_for_itself() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day21")?;
+    cmd.arg(
+        "root: pppw + sjmn
+pppw: 3
+sjmn: pppw * lgvd
+lgvd: sjmn - humn
+humn: 5",
+    );
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a monkey that does not wait for itself, found 'lgvd: sjmn - humn'",
+        ))
+        .stderr(predicate::str::contains("--> line 4, column 1"));
+
+    let mut cmd = Command::cargo_bin("day21")?;
+    cmd.arg("root: 5");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "expected root waiting for two monkeys, found 'root: 5'",
+    ));
+    Ok(())
+}
+
+#[test]
+fn human_on_both_sides() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day21")?;
+    cmd.arg(
+        "root: pppw + sjmn
+pppw: humn * dvpt
+sjmn: humn + dvpt
+dvpt: 3
+humn: 5",
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The root monkey will yell 23"))
+        .stdout(predicate::str::contains(
+            "There is no number we can yell to pass the test",
+        ));
+    Ok(())
+}
+
+#[test]
+fn division_by_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day21")?;
+    cmd.arg(
+        "root: pppw + sjmn
+pppw: cczh / lfqf
+cczh: humn - dvpt
+lfqf: 0
+dvpt: 3
+sjmn: 5
+humn: 5",
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The root monkey will yell no number, as a monkey divides by zero",
+        ))
+        .stdout(predicate::str::contains(
+            "There is no number we can yell to pass the test",
+        ));
+    Ok(())
+}
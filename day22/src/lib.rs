@@ -0,0 +1,475 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use utils::{
+    animate::{Animate, Frame},
+    generate::{Generate, Rng},
+    geom::Direction,
+    grid::Grid,
+    parse_number, Input, ParseError, Part, Solution,
+};
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+    const ANIMATED: bool = true;
+
+    type Input = (Map, Vec<Instruction>);
+    type Part1 = usize;
+    type Part2 = Option<usize>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        // Not dedented, as the map starts with spaces
+        let input = Input::new(text);
+        let mut blocks = input.blocks();
+        let (Some((_, map)), Some((first_line, instructions))) = (blocks.next(), blocks.next())
+        else {
+            return Err(ParseError::missing(
+                text,
+                "an empty line between the map and the instructions",
+            ));
+        };
+        if let Some((_, extra)) = blocks.next() {
+            let expected = "no empty line after the instructions";
+            return Err(ParseError::new(input.text(), extra.text(), expected));
+        }
+
+        let map = map.text().parse::<Map>()?;
+        let instructions =
+            parse_instructions(instructions.text()).map_err(|e| e.shifted(first_line))?;
+        Ok((map, instructions))
+    }
+
+    fn part1((map, instructions): &Self::Input) -> Self::Part1 {
+        let mut map = map.clone();
+        map.execute_instructions(instructions, false);
+        map.get_final_password()
+    }
+
+    fn part2((map, instructions): &Self::Input) -> Self::Part2 {
+        // Only execute the alternative strategy for the real puzzle input, as it is hardcoded for
+        // Map of size 150 x 200
+        if map.grid.width() != 150 {
+            return None;
+        }
+        let mut map_alt = map.clone();
+        map_alt.execute_instructions(instructions, true);
+        Some(map_alt.get_final_password())
+    }
+}
+
+impl Animate for Day22 {
+    type Frame = Trail;
+
+    fn frames(
+        (map, instructions): &Self::Input,
+        part: Part,
+    ) -> impl Iterator<Item = Self::Frame> + '_ {
+        let part2 = part == Part::Two;
+        // Like the password, the cube wrapping only works for the real puzzle input
+        let instructions = if part2 && map.grid.width() != 150 {
+            &instructions[..0]
+        } else {
+            &instructions[..]
+        };
+        let mut map = map.clone();
+        let mut trail = Trail {
+            grid: map.grid.clone(),
+            instruction: 0,
+        };
+        trail.mark(&map);
+        std::iter::once(trail.clone()).chain(instructions.iter().map(move |instruction| {
+            // Moving tile by tile to leave a mark on every visited tile
+            let (step, times) = match instruction {
+                Instruction::Move(n) => (Instruction::Move(1), *n),
+                Instruction::Rotate(_) => (instruction.clone(), 1),
+            };
+            for _ in 0..times {
+                map.execute_instruction(&step, part2);
+                trail.mark(&map);
+            }
+            trail.instruction += 1;
+            trail.clone()
+        }))
+    }
+}
+
+// The size is the length of the edges of the cube, which is unfolded like the real puzzle
+// input (see `Map::try_move_alt`), so the second part is only solved for 50
+impl Generate for Day22 {
+    const SIZE: usize = 50;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let n = size.max(1);
+        // The columns of faces in each row of faces
+        let layout = [1..3, 1..2, 0..2, 0..1];
+        let mut text = String::new();
+        for (face_row, faces) in layout.into_iter().enumerate() {
+            for y in 0..n {
+                text += &" ".repeat(faces.start * n);
+                for x in 0..faces.len() * n {
+                    let start = face_row == 0 && y == 0 && x == 0;
+                    text.push(if !start && rng.chance(0.1) { '#' } else { '.' });
+                }
+                text.push('\n');
+            }
+        }
+
+        text.push('\n');
+        for i in 0..n * 40 {
+            if i > 0 {
+                text.push(*rng.choose(&['L', 'R']));
+            }
+            text += &rng.between(1, n as i64).to_string();
+        }
+        text + "\n"
+    }
+}
+
+fn parse_instructions(text: &str) -> Result<Vec<Instruction>, ParseError> {
+    let text = text.trim_end();
+    let mut instructions = vec![];
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            let c = rest.chars().next().unwrap_or_default();
+            return Err(ParseError::new(
+                text,
+                &rest[..c.len_utf8()],
+                "a number of tiles",
+            ));
+        }
+        instructions.push(Instruction::Move(parse_number(text, &rest[..digits])?));
+        rest = &rest[digits..];
+
+        match rest.chars().next() {
+            Some(turn @ ('L' | 'R')) => instructions.push(Instruction::Rotate(turn == 'R')),
+            Some(other) => {
+                return Err(ParseError::new(
+                    text,
+                    &rest[..other.len_utf8()],
+                    "a turn (L or R)",
+                ))
+            }
+            None => break,
+        }
+        rest = &rest[1..];
+    }
+    if instructions.is_empty() {
+        return Err(ParseError::missing(text, "a path like 10R5L5"));
+    }
+    Ok(instructions)
+}
+
+#[derive(Clone, Debug)]
+pub enum Instruction {
+    Move(usize),
+    Rotate(bool),
+}
+
+#[derive(Clone)]
+pub struct Map {
+    grid: Grid<char>,
+    position: (usize, usize),
+    facing: Direction,
+}
+
+impl Map {
+    fn execute_instructions(&mut self, instructions: &[Instruction], part2: bool) {
+        instructions
+            .iter()
+            .for_each(|instruction| self.execute_instruction(instruction, part2));
+    }
+
+    fn execute_instruction(&mut self, instruction: &Instruction, part2: bool) {
+        match *instruction {
+            Instruction::Move(n) => {
+                // A wall stops the walk for good, and once it comes back to a tile it has
+                // been on before, only the steps after the last whole lap matter
+                let mut seen = HashMap::from([((self.position, self.facing), 0)]);
+                let mut step = 1;
+                while step <= n {
+                    let before = self.position;
+                    self.step(part2);
+                    if self.position == before {
+                        break;
+                    }
+                    let state = (self.position, self.facing);
+                    if let Some(first) = seen.insert(state, step) {
+                        (0..(n - step) % (step - first)).for_each(|_| self.step(part2));
+                        break;
+                    }
+                    step += 1;
+                }
+            }
+            Instruction::Rotate(true) => self.facing = self.facing.turn_right(),
+            Instruction::Rotate(false) => self.facing = self.facing.turn_left(),
+        }
+    }
+
+    fn step(&mut self, part2: bool) {
+        if part2 {
+            self.try_move_alt();
+        } else {
+            self.try_move();
+        }
+    }
+
+    fn try_move_horizontally(&mut self, new_x: usize) {
+        let content = self.grid[(new_x, self.position.1)];
+        match content {
+            '#' => (),
+            '.' => self.position = (new_x, self.position.1),
+            ' ' => {
+                let wrap_idx = self.find_next();
+                match self.grid[(wrap_idx, self.position.1)] {
+                    '#' => (),
+                    '.' => self.position = (wrap_idx, self.position.1),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn try_move_vertically(&mut self, new_y: usize) {
+        let content = self.grid[(self.position.0, new_y)];
+        match content {
+            '#' => (),
+            '.' => self.position = (self.position.0, new_y),
+            ' ' => {
+                let wrap_idx = self.find_next();
+                match self.grid[(self.position.0, wrap_idx)] {
+                    '#' => (),
+                    '.' => self.position = (self.position.0, wrap_idx),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    // I assume this only works for my puzzle input...
+    // But should also work if your puzzle input has the same shape as mine
+
+    // My puzzle input has the following shape, where I identify the cube sides with IDs:
+    //     11112222
+    //     11112222
+    //     11112222
+    //     11112222
+    //     3333
+    //     3333
+    //     3333
+    //     3333
+    // 44445555
+    // 44445555
+    // 44445555
+    // 44445555
+    // 6666
+    // 6666
+    // 6666
+    // 6666
+    fn try_move_alt(&mut self) {
+        let (new_pos, new_facing) = match self.facing {
+            Direction::Right => match self.position {
+                // 2 -> 5
+                (149, 0..=49) => ((99, 149 - self.position.1), Direction::Left),
+                // 3 -> 2
+                (99, 50..=99) => ((50 + self.position.1, 49), Direction::Up),
+                // 5 -> 2
+                (99, 100..=149) => ((149, 149 - self.position.1), Direction::Left),
+                // 6 -> 5
+                (49, 150..=199) => ((self.position.1 - 100, 149), Direction::Up),
+                _ => ((self.position.0 + 1, self.position.1), Direction::Right),
+            },
+            Direction::Down => match self.position {
+                // 2 -> 3
+                (100..=149, 49) => ((99, self.position.0 - 50), Direction::Left),
+                // 5 -> 6
+                (50..=99, 149) => ((49, 100 + self.position.0), Direction::Left),
+                // 6 -> 2
+                (0..=49, 199) => ((100 + self.position.0, 0), Direction::Down),
+                _ => ((self.position.0, self.position.1 + 1), Direction::Down),
+            },
+            Direction::Left => match self.position {
+                // 1 -> 4
+                (50, 0..=49) => ((0, 149 - self.position.1), Direction::Right),
+                // 3 -> 4
+                (50, 50..=99) => ((self.position.1 - 50, 100), Direction::Down),
+                // 4 -> 1
+                (0, 100..=149) => ((50, 149 - self.position.1), Direction::Right),
+                // 6 -> 1
+                (0, 150..=199) => ((self.position.1 - 100, 0), Direction::Down),
+                _ => ((self.position.0 - 1, self.position.1), Direction::Left),
+            },
+            Direction::Up => match self.position {
+                // 2 -> 6
+                (100..=149, 0) => ((self.position.0 - 100, 199), Direction::Up),
+                // 1 -> 6
+                (50..=99, 0) => ((0, self.position.0 + 100), Direction::Right),
+                // 4 -> 3
+                (0..=49, 100) => ((50, 50 + self.position.0), Direction::Right),
+                _ => ((self.position.0, self.position.1 - 1), Direction::Up),
+            },
+        };
+
+        let content = self.grid[new_pos];
+        match content {
+            '.' => {
+                self.position = new_pos;
+                self.facing = new_facing;
+            }
+            '#' => (),
+            _ => unreachable!(),
+        }
+    }
+
+    fn try_move(&mut self) {
+        match self.facing {
+            Direction::Left => {
+                let new_x = if self.position.0 == 0 {
+                    self.find_next()
+                } else {
+                    self.position.0 - 1
+                };
+                self.try_move_horizontally(new_x);
+            }
+            Direction::Right => {
+                let new_x = if self.position.0 == self.grid.width() - 1 {
+                    self.find_next()
+                } else {
+                    self.position.0 + 1
+                };
+                self.try_move_horizontally(new_x)
+            }
+            Direction::Up => {
+                let new_y = if self.position.1 == 0 {
+                    self.find_next()
+                } else {
+                    self.position.1 - 1
+                };
+                self.try_move_vertically(new_y);
+            }
+
+            Direction::Down => {
+                let new_y = if self.position.1 == self.grid.height() - 1 {
+                    self.find_next()
+                } else {
+                    self.position.1 + 1
+                };
+                self.try_move_vertically(new_y);
+            }
+        }
+    }
+
+    fn find_next(&self) -> usize {
+        match self.facing {
+            Direction::Left => {
+                let rev_idx = self
+                    .grid
+                    .row(self.position.1)
+                    .iter()
+                    .rev()
+                    .position(|c| *c != ' ')
+                    .expect("Must exist");
+                self.grid.width() - 1 - rev_idx
+            }
+            Direction::Right => self
+                .grid
+                .row(self.position.1)
+                .iter()
+                .position(|c| *c != ' ')
+                .expect("Must exist"),
+            Direction::Up => {
+                let rev_idx = self
+                    .grid
+                    .column(self.position.0)
+                    .rev()
+                    .position(|c| *c != ' ')
+                    .expect("Must exist");
+                self.grid.height() - 1 - rev_idx
+            }
+            Direction::Down => self
+                .grid
+                .column(self.position.0)
+                .position(|c| *c != ' ')
+                .expect("Must exist"),
+        }
+    }
+
+    fn get_final_password(self) -> usize {
+        // Facing right counts 0 and every quarter turn clockwise from there 1 more
+        let facing = (self.facing as usize + 3) % 4;
+        1000 * (self.position.1 + 1) + 4 * (self.position.0 + 1) + facing
+    }
+}
+
+impl FromStr for Map {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        for line in s.lines() {
+            if let Some(other) = line.matches(|c| !" .#".contains(c)).next() {
+                return Err(ParseError::new(s, other, "' ', '.' or '#'"));
+            }
+        }
+        let width = s.lines().map(|l| l.len()).max().unwrap_or_default();
+        let height = s.lines().count();
+        let mut grid = Grid::new(width, height, ' ');
+        s.lines().enumerate().for_each(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .for_each(|(col, c)| grid[(col, row)] = c)
+        });
+        let first_line = s.lines().next().unwrap_or_default();
+        let position_x = first_line
+            .chars()
+            .position(|c| c == '.')
+            .ok_or_else(|| ParseError::new(s, first_line, "a free tile in the first row"))?;
+        let position = (position_x, 0);
+        let facing = Direction::Right;
+
+        Ok(Map {
+            grid,
+            position,
+            facing,
+        })
+    }
+}
+
+// The map with the path walked so far, drawn like in the puzzle description
+#[derive(Clone)]
+pub struct Trail {
+    grid: Grid<char>,
+    instruction: usize,
+}
+
+impl Trail {
+    fn mark(&mut self, map: &Map) {
+        self.grid[map.position] = match map.facing {
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+            Direction::Up => '^',
+        };
+    }
+}
+
+impl Frame for Trail {
+    fn draw(&self) -> String {
+        self.grid.to_string()
+    }
+
+    fn status(&self) -> String {
+        format!("instruction {}", self.instruction)
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
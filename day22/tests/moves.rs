@@ -39,3 +39,20 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn long_walk() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day22")?;
+    cmd.arg("...\n\n100000000000");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The final password is 1008"));
+
+    // Wrapping around from the top leads into a lap that does not pass the start
+    let mut cmd = Command::cargo_bin("day22")?;
+    cmd.arg("  .\n.\n  .\n  .\n\n0L100000000000");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The final password is 3015"));
+    Ok(())
+}
@@ -0,0 +1,343 @@
+use std::{
+    cmp::{max, min},
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+};
+
+use log::{debug, trace};
+use utils::{
+    animate::{Animate, Frame},
+    generate::{Generate, Rng},
+    geom::{Direction, Point2},
+    grid::Grid,
+    Input, Param, Params, ParseError, Part, Solution,
+};
+
+// This indicates the padding around the original map to give room
+// for the elves to spread out. In my puzzle, this number was sufficient, and the map
+// grows whenever the elves reach its edge, so --map-offset only saves growing it.
+const DEFAULT_MAP_OFFSET: usize = 60;
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+    const ANIMATED: bool = true;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "map-offset",
+        help: "Empty tiles around the scan for the elves to spread out before the map grows (default: 60)",
+        min: 1,
+    }];
+
+    type Input = Map;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        let offset = params
+            .get("map-offset")
+            .map_or(DEFAULT_MAP_OFFSET, |offset| offset as usize);
+        Map::new(Input::new(text).dedent(), offset)
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        let mut map = map.clone();
+        map.execute_turns(Some(10));
+        map.get_number_empty_tiles()
+    }
+
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        map.clone()
+            .execute_turns(None)
+            .expect("Should exist in the second part")
+    }
+}
+
+impl Animate for Day23 {
+    type Frame = Round;
+
+    fn frames(map: &Self::Input, part: Part) -> impl Iterator<Item = Self::Frame> + '_ {
+        let rounds = match part {
+            Part::One => 10,
+            Part::Two => usize::MAX,
+        };
+        let mut map = map.clone();
+        let mut direction_priorities = initial_priorities();
+        let mut finished = false;
+        let first = Round {
+            map: map.clone(),
+            round: 0,
+        };
+        std::iter::once(first).chain((1..=rounds).map_while(move |round| {
+            if finished {
+                return None;
+            }
+            finished = map.execute_turn(&direction_priorities);
+            direction_priorities.rotate_left(1);
+            Some(Round {
+                map: map.clone(),
+                round,
+            })
+        }))
+    }
+}
+
+// The size is the width and height of the scan, which is half covered with elves
+impl Generate for Day23 {
+    const SIZE: usize = 72;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let size = size.max(1);
+        // There is at least one elf
+        let elf = rng.below(size * size);
+        (0..size)
+            .map(|y| {
+                let row: String = (0..size)
+                    .map(|x| match y * size + x == elf || rng.chance(0.5) {
+                        true => '#',
+                        false => '.',
+                    })
+                    .collect();
+                row + "\n"
+            })
+            .collect()
+    }
+}
+
+// The elves after a number of rounds
+pub struct Round {
+    map: Map,
+    round: usize,
+}
+
+impl Frame for Round {
+    // Only the rectangle containing all elves, as the map leaves room for them to spread
+    fn draw(&self) -> String {
+        let (min_x, max_x, min_y, max_y) = self.map.bounds();
+        (min_y..=max_y)
+            .map(|y| {
+                (min_x..=max_x)
+                    .map(|x| self.map.grid[(x, y)])
+                    .chain(['\n'])
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn status(&self) -> String {
+        format!("round {}", self.round)
+    }
+}
+
+fn initial_priorities() -> VecDeque<Direction> {
+    VecDeque::from(vec![
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ])
+}
+
+type Point = Point2<usize>;
+
+#[derive(Clone)]
+pub struct Map {
+    grid: Grid<char>,
+    elve_positions: Vec<Point>,
+}
+
+impl Map {
+    fn new(input: Input, offset: usize) -> Result<Self, ParseError> {
+        for line in input.lines() {
+            if let Some(other) = line.matches(|c| !".#".contains(c)).next() {
+                return Err(ParseError::new(input.text(), other, "'.' or '#'"));
+            }
+        }
+        let width = input.lines().map(|l| l.len()).max().unwrap_or_default() + 2 * offset;
+        let height = input.lines().count() + 2 * offset;
+        let mut grid = Grid::new(width, height, '.');
+
+        let mut elve_positions = Vec::new();
+        input.lines().enumerate().for_each(|(row, line)| {
+            line.chars().enumerate().for_each(|(col, c)| {
+                if c == '#' {
+                    elve_positions.push(Point::new(col + offset, row + offset));
+                    grid[(col + offset, row + offset)] = c
+                }
+            })
+        });
+        // The empty tiles are counted in the rectangle around the elves
+        if elve_positions.is_empty() {
+            return Err(ParseError::missing(input.text(), "an elf (#)"));
+        }
+
+        Ok(Map {
+            grid,
+            elve_positions,
+        })
+    }
+}
+impl Map {
+    fn execute_turns(&mut self, number_turns: Option<usize>) -> Option<usize> {
+        let mut direction_priorities = initial_priorities();
+        if let Some(number) = number_turns {
+            (0..number).for_each(|_| {
+                self.execute_turn(&direction_priorities);
+                direction_priorities.rotate_left(1);
+            });
+            None
+        } else {
+            let mut i = 0;
+            while !self.execute_turn(&direction_priorities) {
+                direction_priorities.rotate_left(1);
+                i += 1;
+            }
+            Some(i + 1)
+        }
+    }
+
+    fn execute_turn(&mut self, direction_priorities: &VecDeque<Direction>) -> bool {
+        self.grow();
+        // First half: Each elve makes a proposal for his next position
+        let proposals = self
+            .elve_positions
+            .iter()
+            .map(|&elve| {
+                if self.is_free(elve) {
+                    elve
+                } else {
+                    direction_priorities
+                        .iter()
+                        .find_map(|&direction| self.try_move(elve, direction))
+                        .unwrap_or(elve)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Second half: Move, if you were the only one to propose that tile
+        let mut counter: HashMap<Point, usize> = HashMap::new();
+        proposals.iter().for_each(|&proposal| {
+            counter.entry(proposal).and_modify(|c| *c += 1).or_insert(1);
+        });
+        let duplicates = counter
+            .into_iter()
+            .filter(|(_, c)| *c >= 2)
+            .collect::<HashMap<_, _>>();
+        let updated_positions = proposals
+            .into_iter()
+            .zip(self.elve_positions.iter())
+            .map(|(proposed, original)| {
+                if duplicates.contains_key(&proposed) {
+                    *original
+                } else {
+                    proposed
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let moved = updated_positions
+            .iter()
+            .zip(&self.elve_positions)
+            .filter(|(new, old)| new != old)
+            .count();
+        debug!(
+            "{} of {} elves moved, looking {:?} first",
+            moved,
+            self.elve_positions.len(),
+            direction_priorities[0]
+        );
+
+        // No update: convergence!
+        if moved == 0 {
+            return true;
+        }
+        self.clear_positions();
+        self.set_positions(&updated_positions);
+        self.elve_positions = updated_positions;
+        trace!("The map is\n{}", self);
+
+        false
+    }
+
+    // Doubles the size of the map when an elf is at its edge, where it could not look around
+    fn grow(&mut self) {
+        let (min_x, max_x, min_y, max_y) = self.bounds();
+        let (width, height) = (self.grid.width(), self.grid.height());
+        if min_x > 0 && min_y > 0 && max_x < width - 1 && max_y < height - 1 {
+            return;
+        }
+        let margin = width.max(height) / 2;
+        debug!("Growing the map by {} tiles on every side", margin);
+        self.grid = Grid::new(width + 2 * margin, height + 2 * margin, '.');
+        for elve in &mut self.elve_positions {
+            *elve += Point::new(margin, margin);
+            self.grid[*elve] = '#';
+        }
+    }
+
+    fn clear_positions(&mut self) {
+        self.elve_positions.iter().for_each(|p| {
+            self.grid[*p] = '.';
+        })
+    }
+
+    fn set_positions(&mut self, positions: &[Point]) {
+        positions.iter().for_each(|p| {
+            self.grid[*p] = '#';
+        })
+    }
+
+    // The tile in the direction, if it and the tiles diagonal to it on both sides are free
+    fn try_move(&self, elve: Point, direction: Direction) -> Option<Point> {
+        let target = elve.step(direction);
+        [
+            target.step(direction.turn_left()),
+            target,
+            target.step(direction.turn_right()),
+        ]
+        .iter()
+        .all(|p| self.grid[*p] != '#')
+        .then_some(target)
+    }
+
+    fn is_free(&self, elve: Point) -> bool {
+        elve.neighbours8().all(|p| self.grid[p] != '#')
+    }
+
+    fn get_number_empty_tiles(&self) -> usize {
+        let (min_x, max_x, min_y, max_y) = self.bounds();
+        (min_y..=max_y)
+            .map(|y| {
+                self.grid.row(y)[min_x..=max_x]
+                    .iter()
+                    .filter(|&c| *c == '.')
+                    .count()
+            })
+            .sum()
+    }
+
+    // (min_x, max_x, min_y, max_y) of the elve positions
+    fn bounds(&self) -> (usize, usize, usize, usize) {
+        self.elve_positions.iter().fold(
+            (usize::MAX, 0, usize::MAX, 0),
+            |(min_x, max_x, min_y, max_y), cur| {
+                (
+                    min(min_x, cur.x),
+                    max(max_x, cur.x),
+                    min(min_y, cur.y),
+                    max(max_y, cur.y),
+                )
+            },
+        )
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
@@ -23,6 +23,39 @@ fn simple() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn small_map_offset() -> Result<(), Box<dyn std::error::Error>> {
+    // The map grows when the elves reach its edge
+    let mut cmd = Command::cargo_bin("day23")?;
+    cmd.args(["--map-offset", "1"]).arg(
+        "....#..
+..###.#
+#...#.#
+.#...##
+#.###..
+##.#.##
+.#..#..",
+    );
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("The number of empty tiles is 110"))
+        .stdout(predicate::str::contains(
+            "The first round no elve moves is 20",
+        ));
+
+    let mut cmd = Command::cargo_bin("day23")?;
+    cmd.args(["-i", "../day23/puzzle.txt", "--map-offset", "5"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "The number of empty tiles is 4181",
+        ))
+        .stdout(predicate::str::contains(
+            "The first round no elve moves is 973",
+        ));
+    Ok(())
+}
+
 #[test]
 fn complex() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("day23")?;
@@ -37,3 +70,13 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn no_elves() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day23")?;
+    cmd.arg("...\n...");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains("expected an elf (#)"));
+    Ok(())
+}
@@ -0,0 +1,450 @@
+use std::{collections::HashSet, fmt::Display};
+
+use log::{debug, trace};
+use utils::{
+    animate::{Animate, Frame},
+    generate::{Generate, Rng},
+    geom::{Direction, Point2},
+    grid::Grid,
+    search::astar,
+    Input, ParseError, Part, Solution,
+};
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: usize = 24;
+    const ANIMATED: bool = true;
+
+    type Input = Map;
+    // `None` if there is no way through the blizzards
+    type Part1 = Option<usize>;
+    type Part2 = Option<usize>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Map::new(Input::new(text).dedent())
+    }
+
+    fn part1(map: &Self::Input) -> Self::Part1 {
+        expedition(map, 1).map(|path| arrival(&path))
+    }
+
+    fn part2(map: &Self::Input) -> Self::Part2 {
+        expedition(map, 3).map(|path| arrival(&path))
+    }
+}
+
+impl Animate for Day24 {
+    type Frame = Minute;
+
+    fn frames(map: &Self::Input, part: Part) -> impl Iterator<Item = Self::Frame> + '_ {
+        let trips = match part {
+            Part::One => 1,
+            Part::Two => 3,
+        };
+        let mut blizzards = map.blizzards.clone();
+        expedition(map, trips)
+            .into_iter()
+            .flatten()
+            .map(move |(position, minute)| {
+                if minute > 0 {
+                    blizzards = evolve(&blizzards, map);
+                }
+                let mut frame = map.clone();
+                frame.clear();
+                frame.set_blizzards(&blizzards);
+                frame.set_point(position);
+                Minute { map: frame, minute }
+            })
+    }
+}
+
+// The size is the width of the valley, which is about a third as high like the real input.
+// Valleys that cannot be crossed in both directions are thrown away
+impl Generate for Day24 {
+    const SIZE: usize = 100;
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        let width = size.max(2);
+        let height = (width / 3).max(1);
+        let valley = loop {
+            let mut valley = Grid::new(width, height, '.');
+            for pos in valley.positions().collect::<Vec<_>>() {
+                if rng.chance(0.6) {
+                    valley[pos] = *rng.choose(&['<', '>', '^', 'v']);
+                }
+            }
+            if crosses(&valley, true) && crosses(&valley, false) {
+                break valley;
+            }
+        };
+
+        let mut text = format!("#.{}\n", "#".repeat(width));
+        for row in valley.rows() {
+            text.push('#');
+            text.extend(row);
+            text += "#\n";
+        }
+        text + &format!("{}.#\n", "#".repeat(width))
+    }
+}
+
+// Whether the valley (without walls) can be crossed from the top left to the bottom right or
+// the other way round. Blizzards repeat after a period, so once a whole period adds no new
+// positions at the same minute of the period, there is no way
+fn crosses(valley: &Grid<char>, downwards: bool) -> bool {
+    let (width, height) = (valley.width(), valley.height());
+    let period = (1..)
+        .map(|n| n * width)
+        .find(|n| n % height == 0)
+        .unwrap_or(width);
+    let (first, last) = match downwards {
+        true => ((0, 0), (width - 1, height - 1)),
+        false => ((width - 1, height - 1), (0, 0)),
+    };
+    // Blizzards only move within their row or column, so it is enough to look back there
+    let free = |(x, y): (usize, usize), minute: usize| {
+        valley[((x + width - minute % width) % width, y)] != '>'
+            && valley[((x + minute) % width, y)] != '<'
+            && valley[(x, (y + height - minute % height) % height)] != 'v'
+            && valley[(x, (y + minute) % height)] != '^'
+    };
+
+    let mut reached: HashSet<(usize, usize)> = HashSet::new();
+    let mut visited = HashSet::new();
+    let mut quiet = 0;
+    for minute in 1.. {
+        // The entrance is never hit by a blizzard, so the first tile can be tried every minute
+        let candidates = reached.iter().flat_map(|&(x, y)| {
+            [
+                (x, y),
+                (x + 1, y),
+                (x, y + 1),
+                (x.wrapping_sub(1), y),
+                (x, y.wrapping_sub(1)),
+            ]
+        });
+        reached = candidates
+            .chain([first])
+            .filter(|&(x, y)| x < width && y < height && free((x, y), minute))
+            .collect();
+        if reached.contains(&last) {
+            return true;
+        }
+        let mut new = false;
+        for &pos in &reached {
+            new |= visited.insert((minute % period, pos));
+        }
+        quiet = if new { 0 } else { quiet + 1 };
+        if quiet > period {
+            return false;
+        }
+    }
+    unreachable!("The loop only ends by returning")
+}
+
+// The valley with the blizzards and the expedition at some minute
+pub struct Minute {
+    map: Map,
+    minute: usize,
+}
+
+impl Frame for Minute {
+    fn draw(&self) -> String {
+        self.map.to_string()
+    }
+
+    fn status(&self) -> String {
+        format!("minute {}", self.minute)
+    }
+}
+
+// The positions of the expedition minute by minute, when going back and forth between
+// the start and the end `trips` times, or `None` if one of the trips is impossible
+fn expedition(map: &Map, trips: usize) -> Option<Vec<(Point, usize)>> {
+    let mut forecast = Forecast::new(map);
+    let mut path = vec![(map.start, 0)];
+    let (mut from, mut to) = (map.start, map.end);
+    for trip in 1..=trips {
+        let crossed = crossing(map, &mut forecast, from, to, arrival(&path))?;
+        path.extend(crossed.into_iter().skip(1));
+        debug!(
+            "Trip {} from {} to {} arrives at minute {}",
+            trip,
+            from,
+            to,
+            arrival(&path)
+        );
+        (from, to) = (to, from);
+    }
+    Some(path)
+}
+
+fn arrival(path: &[(Point, usize)]) -> usize {
+    path.last().map_or(0, |(_, minute)| *minute)
+}
+
+fn evolve(blizzards: &[Blizzard], map: &Map) -> Vec<Blizzard> {
+    blizzards
+        .iter()
+        .map(|b| {
+            // A blizzard that reaches a wall starts over at the opposite one
+            let mut position = b.position.step(b.facing);
+            if position.x == 0 {
+                position.x = map.grid.width() - 2;
+            } else if position.x == map.grid.width() - 1 {
+                position.x = 1;
+            }
+            if position.y == 0 {
+                position.y = map.grid.height() - 2;
+            } else if position.y == map.grid.height() - 1 {
+                position.y = 1;
+            }
+            Blizzard {
+                position,
+                facing: b.facing,
+            }
+        })
+        .collect()
+}
+
+// The positions covered by blizzards, minute by minute. They repeat after a period in which
+// the blizzards have crossed the valley both ways a whole number of times
+struct Forecast {
+    blizzards: Vec<Blizzard>,
+    occupied: Vec<HashSet<Point>>,
+    period: usize,
+}
+
+impl Forecast {
+    fn new(map: &Map) -> Self {
+        let (width, height) = (map.grid.width() - 2, map.grid.height() - 2);
+        Forecast {
+            blizzards: map.blizzards.clone(),
+            occupied: vec![],
+            period: (1..)
+                .map(|n| n * width)
+                .find(|n| n % height == 0)
+                .unwrap_or(width),
+        }
+    }
+
+    fn at(&mut self, minute: usize, map: &Map) -> &HashSet<Point> {
+        let minute = minute % self.period;
+        while self.occupied.len() <= minute {
+            if !self.occupied.is_empty() {
+                self.blizzards = evolve(&self.blizzards, map);
+            }
+            self.occupied
+                .push(self.blizzards.iter().map(|b| b.position).collect());
+        }
+        &self.occupied[minute]
+    }
+}
+
+// The fastest way from `start` to `end` when leaving at `minute`, as positions and minutes,
+// or `None` if there is none. The search only tells apart the minutes within the period of
+// the blizzards, so that it ends once every position was tried at every one of them
+fn crossing(
+    map: &Map,
+    forecast: &mut Forecast,
+    start: Point,
+    end: Point,
+    minute: usize,
+) -> Option<Vec<(Point, usize)>> {
+    let period = forecast.period;
+    let successors = |(position, phase): &(Point, usize)| {
+        trace!("At position {}, minute {} of the period", position, phase);
+        let occupied = forecast.at(phase + 1, map);
+        // Waiting is a move as well
+        std::iter::once(*position)
+            .chain(position.neighbours4())
+            .filter(|&p| map.is_open(p) && !occupied.contains(&p))
+            .map(|p| ((p, (phase + 1) % period), 1))
+            .collect::<Vec<_>>()
+    };
+    let (_, path) = astar(
+        (start, minute % period),
+        successors,
+        |(position, _)| position.manhattan(end),
+        |(position, _)| *position == end,
+    )?;
+    Some(
+        path.into_iter()
+            .enumerate()
+            .map(|(step, (position, _))| (position, minute + step))
+            .collect(),
+    )
+}
+
+type Point = Point2<usize>;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+struct Blizzard {
+    position: Point,
+    facing: Direction,
+}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    grid: Grid<char>,
+    blizzards: Vec<Blizzard>,
+    start: Point,
+    end: Point,
+}
+
+impl Map {
+    fn new(input: Input) -> Result<Self, ParseError> {
+        let s = input.text();
+        let mut blizzards = vec![];
+        let first_line = input.lines().next().unwrap_or_default();
+        let last_line = input.lines().last().unwrap_or_default();
+        let width = first_line.chars().count();
+        let height = input.lines().count();
+        for line in input.lines() {
+            if let Some(i) = line.find(|c| !"#.<>^v".contains(c)) {
+                let c = line[i..].chars().next().unwrap_or_default();
+                return Err(ParseError::new(
+                    s,
+                    &line[i..i + c.len_utf8()],
+                    "a wall, ground or blizzard",
+                ));
+            }
+            if line.chars().count() != width {
+                return Err(ParseError::new(
+                    s,
+                    line,
+                    format!("a row of width {}", width),
+                ));
+            }
+        }
+        // Blizzards wrap around at the walls, so these have to enclose the valley, leaving only
+        // a single gap in the first and last row for the start and the end
+        if width < 3 || height < 3 {
+            return Err(ParseError::missing(s, "a valley inside the walls"));
+        }
+        for (y, line) in input.lines().enumerate() {
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let border = x == 0 || x == width - 1 || y == 0 || y == height - 1;
+                let gap = (y == 0 || y == height - 1) && x != 0 && x != width - 1 && c == '.';
+                let allowed = match c {
+                    '#' => border,
+                    '.' => !border || (gap && !line[..i].contains('.')),
+                    _ => !border,
+                };
+                if !allowed {
+                    let expected = match (border, gap) {
+                        (true, true) => "a single gap in the wall",
+                        (true, false) => "a wall around the valley",
+                        (false, _) => "ground or a blizzard inside the valley",
+                    };
+                    return Err(ParseError::new(s, &line[i..i + c.len_utf8()], expected));
+                }
+            }
+        }
+        let mut grid = Grid::new(width, height, '.');
+
+        input.lines().enumerate().for_each(|(i, l)| {
+            l.chars()
+                .collect::<Vec<char>>()
+                .into_iter()
+                .enumerate()
+                .for_each(|(j, c)| {
+                    match c {
+                        '>' => blizzards.push(Blizzard {
+                            position: Point::new(j, i),
+                            facing: Direction::Right,
+                        }),
+                        '<' => blizzards.push(Blizzard {
+                            position: Point::new(j, i),
+                            facing: Direction::Left,
+                        }),
+                        '^' => blizzards.push(Blizzard {
+                            position: Point::new(j, i),
+                            facing: Direction::Up,
+                        }),
+                        'v' => blizzards.push(Blizzard {
+                            position: Point::new(j, i),
+                            facing: Direction::Down,
+                        }),
+                        _ => (),
+                    };
+                    grid[(j, i)] = c;
+                })
+        });
+
+        let start_x = first_line
+            .chars()
+            .position(|c| c == '.')
+            .ok_or_else(|| ParseError::new(s, first_line, "a start point in the first row"))?;
+        let end_x = last_line
+            .chars()
+            .position(|c| c == '.')
+            .ok_or_else(|| ParseError::new(s, last_line, "an end point in the last row"))?;
+        let end_y = height - 1;
+        let start = Point::new(start_x, 0);
+        let end = Point::new(end_x, end_y);
+
+        Ok(Map {
+            grid,
+            blizzards,
+            start,
+            end,
+        })
+    }
+}
+
+impl Map {
+    // Whether the expedition can stand on the position, leaving aside the blizzards
+    fn is_open(&self, position: Point) -> bool {
+        self.grid
+            .get((position.x, position.y))
+            .is_some_and(|&c| c != '#')
+    }
+}
+
+// Functions for drawing the map state at some minute
+impl Map {
+    fn clear(&mut self) {
+        for h in 1..self.grid.height() - 1 {
+            for w in 1..self.grid.width() - 1 {
+                self.grid[(w, h)] = '.';
+            }
+        }
+        self.grid[self.start] = '.';
+        self.grid[self.end] = '.';
+    }
+
+    fn set_point(&mut self, position: Point) {
+        self.grid[position] = 'E';
+    }
+
+    fn set_blizzards(&mut self, blizzards: &[Blizzard]) {
+        blizzards.iter().for_each(|b| {
+            let content = self.grid[b.position];
+            match content {
+                '.' => {
+                    let new_content = match b.facing {
+                        Direction::Left => '<',
+                        Direction::Right => '>',
+                        Direction::Up => '^',
+                        Direction::Down => 'v',
+                    };
+                    self.grid[b.position] = new_content;
+                }
+                '>' | '<' | 'v' | '^' => self.grid[b.position] = '2',
+                n if n.is_ascii_digit() => {
+                    self.grid[b.position] =
+                        char::from_digit(n.to_digit(10).unwrap() + 1, 10).unwrap()
+                }
+                _ => unreachable!(),
+            }
+        })
+    }
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.grid)
+    }
+}
@@ -38,3 +38,88 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
         ));
     Ok(())
 }
+
+#[test]
+fn animation() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.args([
+        "--animate",
+        "--fps",
+        "1000",
+        "-i",
+        "../day24/simple_puzzle.txt",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "#.######\x1b[K\n#>2.<.<#\x1b[K\n#.2v^2<#\x1b[K\n#>..>2>#\x1b[K\n#<....>#\x1b[K\n######E#",
+        ))
+        .stdout(predicate::str::contains("Frame 19 | minute 18 | 1000 fps | done"));
+    Ok(())
+}
+
+#[test]
+fn logging() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.args(["-i", "../day24/simple_puzzle.txt"]);
+    cmd.assert().success().stderr(predicate::str::is_empty());
+
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.args(["-vv", "--log", "day24", "-i", "../day24/simple_puzzle.txt"]);
+    cmd.assert()
+        .success()
+        .stderr(predicate::str::contains(
+            "[TRACE day24] At position (1, 0), minute 0 of the period\n",
+        ))
+        .stderr(predicate::str::contains(
+            "[DEBUG day24] Trip 3 from (1, 0) to (6, 5) arrives at minute 54\n",
+        ))
+        .stderr(predicate::str::contains("utils::search").not());
+    Ok(())
+}
+
+#[test]
+fn blocked_valley() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.arg("#.###\n#...#\n#>>>#\n###.#");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "There is no way through the blizzards",
+        ))
+        .stdout(predicate::str::contains(
+            "There is no way back and forth through the blizzards",
+        ));
+
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.arg("#.######\n#>>.<^v^\n#<^^#^>v\n######.#");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a wall around the valley, found '^'",
+        ))
+        .stderr(predicate::str::contains("--> line 2, column 8"));
+    Ok(())
+}
+
+#[test]
+fn several_gaps() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.arg("#.#.#\n#...#\n#...#\n###.#");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a single gap in the wall, found '.'",
+        ))
+        .stderr(predicate::str::contains("--> line 1, column 4"));
+
+    let mut cmd = Command::cargo_bin("day24")?;
+    cmd.arg("#.###\n#...#\n#...#\n#...#");
+    cmd.assert()
+        .code(1)
+        .stderr(predicate::str::contains(
+            "expected a single gap in the wall, found '.'",
+        ))
+        .stderr(predicate::str::contains("--> line 4, column 3"));
+    Ok(())
+}
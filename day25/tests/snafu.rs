@@ -35,3 +35,21 @@ fn complex() -> Result<(), Box<dyn std::error::Error>> {
     ));
     Ok(())
 }
+
+#[test]
+fn large_numbers() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("day25")?;
+    cmd.arg("1=-01=-0---2111=01-=0--21=-0-2");
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "expected a SNAFU number of at most 27 digits, found '1=-01=-0---2111=01-=0--21=-0-2'",
+    ));
+
+    let mut cmd = Command::cargo_bin("day25")?;
+    cmd.arg(
+        "222222222222222222222222222\n222222222222222222222222222\n222222222222222222222222222",
+    );
+    cmd.assert().success().stdout(predicate::str::contains(
+        "The sum of the snafu numbers is too large",
+    ));
+    Ok(())
+}
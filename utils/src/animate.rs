@@ -0,0 +1,287 @@
+use std::{
+    io::{IsTerminal, Read, Write},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{Part, Solution};
+
+pub const DEFAULT_FPS: u32 = 10;
+const MAX_FPS: u32 = 1000;
+
+const HELP: &str = "space: pause, n: step, +/-: speed, q: quit";
+
+/// One state of a simulation, as shown by `--animate`.
+pub trait Frame {
+    /// The state as text, one line per row.
+    fn draw(&self) -> String;
+
+    /// A short description of the state shown below the frame, like a round number.
+    fn status(&self) -> String {
+        String::new()
+    }
+}
+
+/// A day whose simulation can be watched with `--animate`.
+pub trait Animate: Solution {
+    type Frame: Frame;
+
+    /// The states the given part passes through while being solved, in order.
+    fn frames(input: &Self::Input, part: Part) -> impl Iterator<Item = Self::Frame> + '_;
+}
+
+// The state of the player, which only changes in reaction to keys
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Player {
+    fps: u32,
+    paused: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Redraw,
+    Step,
+    Quit,
+    Ignore,
+}
+
+impl Player {
+    fn handle(&mut self, key: u8) -> Action {
+        match key {
+            b' ' | b'p' => {
+                self.paused = !self.paused;
+                Action::Redraw
+            }
+            b'n' | b'.' if self.paused => Action::Step,
+            b'+' | b'=' => {
+                self.fps = (self.fps * 2).min(MAX_FPS);
+                Action::Redraw
+            }
+            b'-' => {
+                self.fps = (self.fps / 2).max(1);
+                Action::Redraw
+            }
+            b'q' => Action::Quit,
+            _ => Action::Ignore,
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+}
+
+/// Plays the frames in the terminal, redrawing every frame in place.
+///
+/// The animation can be paused, stepped through and sped up with keys while standard input
+/// is a terminal. The last frame stays on screen once all frames were shown.
+pub fn play<F: Frame>(frames: impl Iterator<Item = F>, fps: u32) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    let keys = KeyReader::start();
+    let player = Player {
+        fps: fps.clamp(1, MAX_FPS),
+        paused: false,
+    };
+
+    write!(stdout, "\x1b[?25l\x1b[2J")?;
+    let result = show(&mut stdout, frames, keys.as_ref().map(|k| &k.keys), player);
+    writeln!(stdout, "\x1b[?25h")?;
+    result
+}
+
+fn show<F: Frame>(
+    out: &mut impl Write,
+    frames: impl Iterator<Item = F>,
+    keys: Option<&Receiver<u8>>,
+    mut player: Player,
+) -> std::io::Result<()> {
+    let mut frames = frames.enumerate().peekable();
+    while let Some((n, frame)) = frames.next() {
+        let last = frames.peek().is_none();
+        let next_frame = Instant::now() + player.delay();
+        loop {
+            let status = status_line(n + 1, &frame, &player, last);
+            write!(out, "{}", screen(&frame.draw(), &status))?;
+            out.flush()?;
+            if last {
+                return Ok(());
+            }
+
+            let key = match keys {
+                Some(keys) if player.paused => keys.recv().ok(),
+                Some(keys) => {
+                    match keys.recv_timeout(next_frame.saturating_duration_since(Instant::now())) {
+                        Ok(key) => Some(key),
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => None,
+                    }
+                }
+                None => None,
+            };
+            let Some(key) = key else {
+                // Without keys there is nothing to wait for but the next frame
+                player.paused = false;
+                thread::sleep(next_frame.saturating_duration_since(Instant::now()));
+                break;
+            };
+            match player.handle(key) {
+                Action::Step => break,
+                Action::Quit => return Ok(()),
+                Action::Redraw | Action::Ignore => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+// Moves the cursor to the top left instead of clearing the screen, which avoids flickering
+fn screen(frame: &str, status: &str) -> String {
+    let mut screen = String::from("\x1b[H");
+    for line in frame.lines() {
+        screen.push_str(line);
+        screen.push_str("\x1b[K\n");
+    }
+    screen.push_str(status);
+    screen.push_str("\x1b[K\x1b[J");
+    screen
+}
+
+fn status_line(n: usize, frame: &impl Frame, player: &Player, last: bool) -> String {
+    let mut parts = vec![format!("Frame {}", n)];
+    let status = frame.status();
+    if !status.is_empty() {
+        parts.push(status);
+    }
+    parts.push(format!("{} fps", player.fps));
+    if last {
+        parts.push(String::from("done"));
+    } else if player.paused {
+        parts.push(String::from("paused"));
+    }
+    parts.push(String::from(HELP));
+    parts.join(" | ")
+}
+
+// Reads single key presses from the terminal on a separate thread, so that the animation
+// does not block on them
+struct KeyReader {
+    keys: Receiver<u8>,
+    // The terminal settings to restore when done
+    settings: String,
+}
+
+impl KeyReader {
+    fn start() -> Option<Self> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        // Without canonical mode, keys arrive without waiting for enter
+        let settings = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "min", "1"])?;
+
+        let (sender, keys) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0; 16];
+            while let Ok(read @ 1..) = std::io::stdin().read(&mut buffer) {
+                if buffer[..read].iter().any(|&key| sender.send(key).is_err()) {
+                    break;
+                }
+            }
+        });
+        Some(KeyReader {
+            keys,
+            settings: settings.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for KeyReader {
+    fn drop(&mut self) {
+        stty(&[&self.settings]);
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Counter(usize);
+
+    impl Frame for Counter {
+        fn draw(&self) -> String {
+            format!("{}\n{}", "#".repeat(self.0), ".".repeat(self.0))
+        }
+
+        fn status(&self) -> String {
+            format!("count {}", self.0)
+        }
+    }
+
+    #[test]
+    fn handles_keys() {
+        let mut player = Player {
+            fps: 10,
+            paused: false,
+        };
+        assert_eq!(player.handle(b'n'), Action::Ignore);
+        assert_eq!(player.handle(b' '), Action::Redraw);
+        assert!(player.paused);
+        assert_eq!(player.handle(b'n'), Action::Step);
+        assert_eq!(player.handle(b'+'), Action::Redraw);
+        assert_eq!(player.fps, 20);
+        assert_eq!(player.delay(), Duration::from_millis(50));
+        for _ in 0..10 {
+            player.handle(b'-');
+        }
+        assert_eq!(player.fps, 1);
+        assert_eq!(player.handle(b'q'), Action::Quit);
+    }
+
+    #[test]
+    fn plays_all_frames_without_keys() {
+        let mut out = Vec::new();
+        let player = Player {
+            fps: MAX_FPS,
+            paused: true,
+        };
+        show(&mut out, (1..=3).map(Counter), None, player).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.matches("\x1b[H").count(), 3);
+        assert!(out.ends_with(&format!(
+            "\x1b[H###\x1b[K\n...\x1b[K\nFrame 3 | count 3 | {} fps | done | {}\x1b[K\x1b[J",
+            MAX_FPS, HELP
+        )));
+    }
+
+    #[test]
+    fn draws_in_place() {
+        let player = Player {
+            fps: 5,
+            paused: true,
+        };
+        let status = status_line(3, &Counter(2), &player, false);
+        assert_eq!(
+            status,
+            format!("Frame 3 | count 2 | 5 fps | paused | {}", HELP)
+        );
+        assert_eq!(
+            screen(&Counter(2).draw(), "Frame 3"),
+            "\x1b[H##\x1b[K\n..\x1b[K\nFrame 3\x1b[K\x1b[J"
+        );
+    }
+}
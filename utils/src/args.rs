@@ -0,0 +1,423 @@
+use std::{collections::HashMap, io::Read, path::PathBuf, process};
+
+use crate::{
+    animate::{self, Animate, Frame},
+    render::{self, Export, ImageFormat, Palette},
+    Config, Logging, ParseError, Report, Solution,
+};
+
+/// One of the two parts of a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// How the answers of a day are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Human,
+    Json,
+}
+
+/// A named `--<name> <value>` parameter a day accepts on top of the common arguments.
+pub struct Param {
+    pub name: &'static str,
+    pub help: &'static str,
+    /// The smallest valid value
+    pub min: i64,
+}
+
+impl Param {
+    /// Returns `value` if it is valid for the parameter.
+    pub fn check(&self, value: i64) -> Result<i64, String> {
+        if value < self.min {
+            return Err(format!("expected at least {}, found {}", self.min, value));
+        }
+        Ok(value)
+    }
+}
+
+/// The values given for the named parameters of a day.
+#[derive(Debug, Clone, Default)]
+pub struct Params(pub(crate) HashMap<String, i64>);
+
+impl Params {
+    pub fn get(&self, name: &str) -> Option<i64> {
+        self.0.get(name).copied()
+    }
+}
+
+/// The parsed command line of a single day.
+#[derive(Debug)]
+pub struct Args {
+    pub text: String,
+    pub part: Option<Part>,
+    pub format: Format,
+    pub params: Params,
+    pub animate: bool,
+    pub fps: u32,
+    pub export: Option<Export>,
+    pub logging: Logging,
+}
+
+enum Source {
+    Text(String),
+    File(PathBuf),
+    Stdin,
+}
+
+impl Args {
+    /// Parses the arguments of the running day, exiting with a usage error if they are invalid.
+    pub fn parse<S: Solution>() -> Self {
+        let mut args = std::env::args();
+        let program = args
+            .next()
+            .and_then(|arg| {
+                let path = PathBuf::from(arg);
+                Some(path.file_stem()?.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| format!("day{:02}", S::DAY));
+        let usage = usage(&program, S::PARAMS, S::ANIMATED);
+        let (source, config, mut parsed) = match parse_args(S::PARAMS, S::ANIMATED, args) {
+            Ok(Some(parsed)) => parsed,
+            Ok(None) => {
+                println!("{}", usage);
+                process::exit(0);
+            }
+            Err(msg) => {
+                eprintln!("error: {}\n\n{}", msg, usage);
+                process::exit(2);
+            }
+        };
+        parsed.logging.init();
+
+        if !S::PARAMS.is_empty() {
+            let input = match &source {
+                Source::File(path) => path.file_stem().and_then(|stem| stem.to_str()),
+                _ => None,
+            };
+            parsed.params = configure::<S>(config, input, parsed.params).unwrap_or_else(|msg| {
+                eprintln!("error: {}", msg);
+                process::exit(1);
+            });
+        }
+        parsed.text = read_source(source).unwrap_or_else(|msg| {
+            eprintln!("error: {}", msg);
+            process::exit(1);
+        });
+        parsed
+    }
+
+    /// Parses the puzzle input, exiting with a diagnostic that points at the bad line if it is invalid.
+    pub fn input<S: Solution>(&self) -> S::Input {
+        S::parse_with(&self.text, &self.params).unwrap_or_else(|e| self.exit_invalid(e))
+    }
+
+    /// Solves the requested parts and prints the answers and timings as json.
+    pub fn print_json<S: Solution>(&self) {
+        match Report::run::<S>(self) {
+            Ok(report) => println!("{}", report.to_json()),
+            Err(e) => self.exit_invalid(e),
+        }
+    }
+
+    /// Whether the simulation is to be shown or exported instead of solving the puzzle.
+    pub fn visualises(&self) -> bool {
+        self.animate || self.export.is_some()
+    }
+
+    /// Exports and then plays the simulation of the requested part (the first one without
+    /// `--part`), as requested by `--export` and `--animate`.
+    pub fn visualise<S: Animate>(&self, input: &S::Input) {
+        let part = self.part.unwrap_or(Part::One);
+        if let Some(export) = &self.export {
+            let draw = || S::frames(input, part).map(|frame| frame.draw());
+            let image = match export.format {
+                ImageFormat::Png => {
+                    let last = draw().last().unwrap_or_default();
+                    render::png(&last, &export.palette, export.scale)
+                }
+                ImageFormat::Gif => {
+                    let delay = (100 / self.fps).max(2) as u16;
+                    render::gif(draw, &export.palette, export.scale, delay)
+                }
+            };
+            let image = image.unwrap_or_else(|e| {
+                eprintln!("error: could not export {}: {}", export.path.display(), e);
+                process::exit(1);
+            });
+            if let Err(e) = std::fs::write(&export.path, image) {
+                eprintln!("error: could not write {}: {}", export.path.display(), e);
+                process::exit(1);
+            }
+        }
+        if self.animate {
+            if let Err(e) = animate::play(S::frames(input, part), self.fps) {
+                eprintln!("error: could not animate: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    fn exit_invalid(&self, error: ParseError) -> ! {
+        eprintln!("{}", error.render(&self.text));
+        process::exit(1);
+    }
+
+    /// Whether the given part was requested, which is the case for both parts without `--part`.
+    pub fn runs(&self, part: Part) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+// Values from the command line take precedence over those of the configuration file, which
+// is only required to exist if it was given explicitly
+fn configure<S: Solution>(
+    path: Option<PathBuf>,
+    input: Option<&str>,
+    given: Params,
+) -> Result<Params, String> {
+    let Some(path) = path.or_else(Config::find) else {
+        return Ok(given);
+    };
+    let config = Config::load(&path)?;
+    config
+        .check(S::DAY, S::PARAMS)
+        .map_err(|msg| format!("{}: {}", path.display(), msg))?;
+    let mut params = config.params(S::DAY, input);
+    params.0.extend(given.0);
+    Ok(params)
+}
+
+fn read_source(source: Source) -> Result<String, String> {
+    match source {
+        Source::Text(text) => Ok(text),
+        Source::File(path) => std::fs::read_to_string(&path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e)),
+        Source::Stdin => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("could not read standard input: {}", e))?;
+            Ok(text)
+        }
+    }
+}
+
+// Returns None if the help was requested, the text is only filled in after reading the source
+// and the params after reading the configuration file
+fn parse_args(
+    known_params: &[Param],
+    animated: bool,
+    mut args: impl Iterator<Item = String>,
+) -> Result<Option<(Source, Option<PathBuf>, Args)>, String> {
+    let mut source = None;
+    let mut config = None;
+    let mut parsed = Args {
+        text: String::new(),
+        part: None,
+        format: Format::Human,
+        params: Params::default(),
+        animate: false,
+        fps: animate::DEFAULT_FPS,
+        export: None,
+        logging: Logging::default(),
+    };
+    let mut fps = None;
+    let (mut export, mut scale, mut palette) = (None, None, None);
+
+    let mut set_source = |new: Source| match source.replace(new) {
+        Some(_) => Err(String::from(
+            "only one of <TEXT>, --input and --stdin may be given",
+        )),
+        None => Ok(()),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-i" | "--input" => {
+                let file = args.next().ok_or("--input requires a file")?;
+                set_source(Source::File(PathBuf::from(file)))?;
+            }
+            "--stdin" => set_source(Source::Stdin)?,
+            "--config" if !known_params.is_empty() => {
+                let file = args.next().ok_or("--config requires a file")?;
+                config = Some(PathBuf::from(file));
+            }
+            "--part" => {
+                parsed.part = match args.next().as_deref() {
+                    Some("1") => Some(Part::One),
+                    Some("2") => Some(Part::Two),
+                    Some(other) => return Err(format!("'{}' is not a part, use 1 or 2", other)),
+                    None => return Err(String::from("--part requires 1 or 2")),
+                }
+            }
+            "--format" => {
+                parsed.format = match args.next().as_deref() {
+                    Some("human") => Format::Human,
+                    Some("json") => Format::Json,
+                    Some(other) => {
+                        return Err(format!("'{}' is not a format, use human or json", other))
+                    }
+                    None => return Err(String::from("--format requires human or json")),
+                }
+            }
+            "-v" | "--verbose" => parsed.logging.verbosity += 1,
+            "-vv" => parsed.logging.verbosity += 2,
+            "--log" => {
+                let list = args.next().ok_or("--log requires a list of modules")?;
+                parsed.logging.add_targets(&list)?;
+            }
+            "--animate" if animated => parsed.animate = true,
+            "--fps" if animated => {
+                let value = args.next().ok_or("--fps requires a value")?;
+                fps = Some(
+                    value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|&fps| fps > 0)
+                        .ok_or_else(|| {
+                            format!("'{}' is not a valid number of frames per second", value)
+                        })?,
+                );
+            }
+            "--export" if animated => {
+                let file = args.next().ok_or("--export requires a file")?;
+                export = Some(Export::new(PathBuf::from(file))?);
+            }
+            "--scale" if animated => {
+                let value = args.next().ok_or("--scale requires a value")?;
+                scale = Some(
+                    value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|scale| (1..=64).contains(scale))
+                        .ok_or_else(|| format!("'{}' is not a scale from 1 to 64", value))?,
+                );
+            }
+            "--palette" if animated => {
+                let spec = args.next().ok_or("--palette requires colours")?;
+                palette = Some(spec.parse::<Palette>()?);
+            }
+            // The puzzle text itself spans multiple lines, so it is never mistaken for a flag
+            flag if flag.starts_with('-') && !flag.contains('\n') => {
+                let name = flag.trim_start_matches('-');
+                let param = known_params
+                    .iter()
+                    .find(|p| p.name == name && flag.starts_with("--"))
+                    .ok_or_else(|| format!("unexpected argument '{}'", flag))?;
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} requires a value", flag))?;
+                let value = value
+                    .parse::<i64>()
+                    .map_err(|_| format!("'{}' is not a valid integer for {}", value, flag))?;
+                let value = param
+                    .check(value)
+                    .map_err(|msg| format!("invalid value for {}: {}", flag, msg))?;
+                parsed.params.0.insert(name.to_string(), value);
+            }
+            _ => set_source(Source::Text(arg))?,
+        }
+    }
+
+    match fps {
+        Some(_) if !parsed.animate && export.is_none() => {
+            return Err(String::from("--fps requires --animate or --export"))
+        }
+        Some(fps) => parsed.fps = fps,
+        None => {}
+    }
+    match export {
+        Some(mut export) => {
+            export.scale = scale.unwrap_or(export.scale);
+            export.palette = palette.unwrap_or_default();
+            parsed.export = Some(export);
+        }
+        None if scale.is_some() || palette.is_some() => {
+            return Err(String::from("--scale and --palette require --export"))
+        }
+        None => {}
+    }
+    let source = source.ok_or("no puzzle input given")?;
+    Ok(Some((source, config, parsed)))
+}
+
+fn usage(program: &str, params: &[Param], animated: bool) -> String {
+    let mut options = vec![
+        (
+            String::from("-i, --input <FILE>"),
+            "Read the puzzle input from FILE",
+        ),
+        (
+            String::from("    --stdin"),
+            "Read the puzzle input from standard input",
+        ),
+        (
+            String::from("    --part <1|2>"),
+            "Only solve the given part",
+        ),
+        (
+            String::from("    --format <FORMAT>"),
+            "Print the answers as sentences (human, default) or as json",
+        ),
+        (
+            String::from("-v, --verbose"),
+            "Log what the solver does to standard error, twice (-vv) for every step",
+        ),
+        (
+            String::from("    --log <MODULES>"),
+            "Only log the given modules, like 'day24,utils::search'",
+        ),
+    ];
+    if animated {
+        options.push((
+            String::from("    --animate"),
+            "Watch the simulation of the part in the terminal instead of solving it",
+        ));
+        options.push((
+            String::from("    --fps <N>"),
+            "Frames per second of the animation (default 10)",
+        ));
+        options.push((
+            String::from("    --export <FILE>"),
+            "Save the last frame as .png or all frames as .gif",
+        ));
+        options.push((
+            String::from("    --scale <N>"),
+            "Pixels per tile of the exported image (default 4)",
+        ));
+        options.push((
+            String::from("    --palette <SPEC>"),
+            "Colours of tiles in the exported image, like '#=ffffff,.=000000'",
+        ));
+    }
+    if !params.is_empty() {
+        options.push((
+            String::from("    --config <FILE>"),
+            "Read the parameters below from FILE (default: the closest aoc.toml)",
+        ));
+    }
+    options.extend(
+        params
+            .iter()
+            .map(|p| (format!("    --{} <N>", p.name), p.help)),
+    );
+    options.push((String::from("-h, --help"), "Print this help"));
+
+    let width = options
+        .iter()
+        .map(|(o, _)| o.len())
+        .max()
+        .unwrap_or_default();
+    let mut usage = format!(
+        "Usage: {} [OPTIONS] <TEXT | --input <FILE> | --stdin>\n\nOptions:",
+        program
+    );
+    for (option, help) in options {
+        usage.push_str(&format!("\n  {:<width$}  {}", option, help, width = width));
+    }
+    usage
+}
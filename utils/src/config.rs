@@ -0,0 +1,245 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use toml::{
+    de::{DeTable, DeValue},
+    Spanned,
+};
+
+use crate::{parse_number, Param, Params, ParseError};
+
+/// Values for the parameters of the days, read from `aoc.toml`, so that inputs which need
+/// other values than the defaults can be solved without changing the code.
+///
+/// A `[dayNN]` section applies to all inputs of a day, while a `[dayNN.<input>]` section
+/// only applies to the input file of that name (without `.txt`) and takes precedence.
+#[derive(Debug, Default, Clone)]
+pub struct Config {
+    // Keyed by day and input name, which is `None` for the section of the whole day
+    sections: BTreeMap<(usize, Option<String>), BTreeMap<String, i64>>,
+}
+
+impl Config {
+    /// The name of the configuration file that is looked for if none is given.
+    pub const FILE_NAME: &'static str = "aoc.toml";
+
+    /// Reads the configuration from `path`, rendering errors with the offending line.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+        text.parse().map_err(|e: ParseError| {
+            format!("{}\n  = note: in {}", e.render(&text), path.display())
+        })
+    }
+
+    /// Finds `aoc.toml` in the current directory or the closest of its parents.
+    pub fn find() -> Option<PathBuf> {
+        let dir = std::env::current_dir().ok()?;
+        dir.ancestors()
+            .map(|dir| dir.join(Self::FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// The days that have a section.
+    pub fn days(&self) -> impl Iterator<Item = usize> + '_ {
+        let mut days: Vec<usize> = self.sections.keys().map(|(day, _)| *day).collect();
+        days.dedup();
+        days.into_iter()
+    }
+
+    /// Checks that all values given for the day are known parameters with valid values.
+    pub fn check(&self, day: usize, known: &[Param]) -> Result<(), String> {
+        let sections = self.sections.iter().filter(|((d, _), _)| *d == day);
+        for ((_, input), values) in sections {
+            let section = match input {
+                Some(input) => format!("[day{:02}.{}]", day, input),
+                None => format!("[day{:02}]", day),
+            };
+            for (name, &value) in values {
+                let param = known
+                    .iter()
+                    .find(|p| p.name == *name)
+                    .ok_or_else(|| match known {
+                        [] => format!(
+                            "day {} has no parameters, but {} sets '{}'",
+                            day, section, name
+                        ),
+                        _ => format!(
+                            "unknown parameter '{}' in {}, day {} accepts {}",
+                            name,
+                            section,
+                            day,
+                            known.iter().map(|p| p.name).collect::<Vec<_>>().join(", ")
+                        ),
+                    })?;
+                param
+                    .check(value)
+                    .map_err(|msg| format!("'{}' in {}: {}", name, section, msg))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The values for the given input of the day, which is `None` if it is not read from a file.
+    pub fn params(&self, day: usize, input: Option<&str>) -> Params {
+        let mut params = Params::default();
+        let day_values = self.sections.get(&(day, None));
+        let input_values =
+            input.and_then(|input| self.sections.get(&(day, Some(input.to_string()))));
+        for values in day_values.into_iter().chain(input_values) {
+            params
+                .0
+                .extend(values.iter().map(|(name, &value)| (name.clone(), value)));
+        }
+        params
+    }
+}
+
+// `[dayNN]` and `[dayNN.input]` sections holding integers
+impl FromStr for Config {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = DeTable::parse(s).map_err(|e| ParseError::toml(s, &e))?;
+        let mut sections = BTreeMap::new();
+        for (key, value) in table.get_ref() {
+            let section = &s[key.span()];
+            let day_values = value
+                .get_ref()
+                .as_table()
+                .ok_or_else(|| ParseError::new(s, section, "a section like [day15]"))?;
+            let day = section
+                .strip_prefix("day")
+                .ok_or_else(|| ParseError::new(s, section, "a day like day15"))?;
+            let day: usize = parse_number(s, day)?;
+
+            let mut values = BTreeMap::new();
+            for (name, value) in day_values {
+                match value.get_ref() {
+                    DeValue::Table(input_values) => {
+                        let input_values = input_values
+                            .iter()
+                            .map(|(name, value)| Ok((name.to_string(), integer(s, value)?)))
+                            .collect::<Result<_, ParseError>>()?;
+                        sections.insert((day, Some(name.to_string())), input_values);
+                    }
+                    _ => {
+                        values.insert(name.to_string(), integer(s, value)?);
+                    }
+                }
+            }
+            sections.insert((day, None), values);
+        }
+
+        Ok(Config { sections })
+    }
+}
+
+fn integer(source: &str, value: &Spanned<DeValue>) -> Result<i64, ParseError> {
+    value
+        .get_ref()
+        .as_integer()
+        .and_then(|n| i64::from_str_radix(n.as_str(), n.radix()).ok())
+        .ok_or_else(|| ParseError::new(source, &source[value.span()], "an integer"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "\
+# Parameters of the days
+[day15]
+row = 2_000_000
+search-max = 4000000
+
+[day15.simple_puzzle]  # the example
+row = 10
+
+[day20]
+decryption-key = -3
+";
+
+    const PARAMS: &[Param] = &[
+        Param {
+            name: "row",
+            help: "",
+            min: i64::MIN,
+        },
+        Param {
+            name: "search-max",
+            help: "",
+            min: 1,
+        },
+    ];
+
+    #[test]
+    fn merges_sections() {
+        let config: Config = CONFIG.parse().unwrap();
+        assert_eq!(config.days().collect::<Vec<_>>(), [15, 20]);
+
+        let params = config.params(15, Some("simple_puzzle"));
+        assert_eq!(params.get("row"), Some(10));
+        assert_eq!(params.get("search-max"), Some(4_000_000));
+        assert_eq!(
+            config.params(15, Some("puzzle")).get("row"),
+            Some(2_000_000)
+        );
+        assert_eq!(config.params(15, None).get("row"), Some(2_000_000));
+        assert_eq!(config.params(20, None).get("decryption-key"), Some(-3));
+        assert_eq!(config.params(3, None).get("row"), None);
+    }
+
+    #[test]
+    fn checks_parameters() {
+        let config: Config = CONFIG.parse().unwrap();
+        assert_eq!(config.check(15, PARAMS), Ok(()));
+        assert_eq!(config.check(4, &[]), Ok(()));
+        assert_eq!(
+            config.check(20, PARAMS),
+            Err(String::from(
+                "unknown parameter 'decryption-key' in [day20], day 20 accepts row, search-max"
+            ))
+        );
+        assert!(config
+            .check(20, &[])
+            .unwrap_err()
+            .contains("day 20 has no parameters"));
+
+        let config: Config = "[day15.puzzle]\nsearch-max = 0".parse().unwrap();
+        assert_eq!(
+            config.check(15, PARAMS),
+            Err(String::from(
+                "'search-max' in [day15.puzzle]: expected at least 1, found 0"
+            ))
+        );
+    }
+
+    #[test]
+    fn points_at_errors() {
+        for (text, line, found) in [
+            ("row = 10", 1, "row"),
+            ("[day15]\nrow = ten", 2, "ten"),
+            ("[day15]\nrow = 1\nrow = 2", 3, "row"),
+            ("[day15\n", 1, "[day15"),
+            ("[15]", 1, "15"),
+            ("[day15.a/b]", 1, "/"),
+            ("[day15]\n[day15]", 2, "day15"),
+            ("[day15]\nrow", 2, "row"),
+            ("[day15]\nrow = 1__0", 2, "_"),
+            ("[day15]\nrow = _5", 2, "_"),
+            ("[day15]\nrow = 1.5", 2, "1.5"),
+            (
+                "[day15]\nrow = 9223372036854775808",
+                2,
+                "9223372036854775808",
+            ),
+        ] {
+            let error = text.parse::<Config>().unwrap_err();
+            assert_eq!((error.line, error.text.as_str()), (line, found), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// The cycle of a simulation that eventually repeats itself: the state after step
+/// `start + length` is the same as the one after step `start`.
+///
+/// All detectors take the initial state, a `step` function advancing a state in place and
+/// a `key` function telling which states count as the same, which may be a cheaper summary
+/// of the state. They loop forever if the keys never repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the cycle is entered
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The first step at which the state is the same as after `n` steps.
+    pub fn equivalent(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        self.start + (n - self.start) % self.length
+    }
+
+    /// The number of whole cycles that can be skipped when fast-forwarding to step `n`
+    /// from its [`equivalent`](Cycle::equivalent).
+    pub fn skipped(&self, n: usize) -> usize {
+        (n - self.equivalent(n)) / self.length
+    }
+
+    /// The value after `n` steps of a quantity that grows by the same amount in every pass
+    /// of the cycle, like the height of a tower that is built in a repeating pattern.
+    ///
+    /// `values` holds the value after every step, at least up to `start + length`.
+    pub fn extrapolate(&self, n: usize, values: &[usize]) -> usize {
+        let per_cycle = values[self.start + self.length] - values[self.start];
+        values[self.equivalent(n)] + self.skipped(n) * per_cycle
+    }
+}
+
+/// Finds the cycle by remembering the key of every state, which steps through the
+/// simulation only once at the cost of storing all keys up to the end of the first pass.
+pub fn hashed<S, K: Eq + Hash>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    let mut seen = HashMap::new();
+    let mut n = 0;
+    loop {
+        match seen.entry(key(&state)) {
+            Entry::Occupied(first) => {
+                let start = *first.get();
+                return Cycle {
+                    start,
+                    length: n - start,
+                };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(n);
+            }
+        }
+        step(&mut state);
+        n += 1;
+    }
+}
+
+/// Finds the cycle with Floyd's tortoise and hare, which only keeps three states at a
+/// time but steps through the simulation several times.
+pub fn floyd<S: Clone, K: PartialEq>(
+    start: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    step(&mut tortoise);
+    step(&mut hare);
+    step(&mut hare);
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        step(&mut hare);
+    }
+
+    // The hare is now a multiple of the cycle length ahead, so they meet at its start
+    let mut tortoise = start;
+    let mut cycle_start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        cycle_start += 1;
+    }
+
+    let mut hare = tortoise.clone();
+    step(&mut hare);
+    let mut length = 1;
+    while key(&tortoise) != key(&hare) {
+        step(&mut hare);
+        length += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which needs as little memory as [`floyd`]
+/// but usually fewer steps.
+pub fn brent<S: Clone, K: PartialEq>(
+    start: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    // The tortoise waits at powers of two until the hare comes around to it
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    step(&mut hare);
+    let (mut power, mut length) = (1, 1);
+    while key(&tortoise) != key(&hare) {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        step(&mut hare);
+        length += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        step(&mut hare);
+    }
+    let mut cycle_start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        cycle_start += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Starting from 3, this enters the cycle 10, 14, 15, 6, 7, 1 after 2 steps
+    fn step(x: &mut u32) {
+        *x = (*x * *x + 9) % 19;
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let expected = Cycle {
+            start: 2,
+            length: 6,
+        };
+        assert_eq!(hashed(3, step, |&x| x), expected);
+        assert_eq!(floyd(3, step, |&x| x), expected);
+        assert_eq!(brent(3, step, |&x| x), expected);
+
+        // Keys leave out what does not influence the next states, like a step counter
+        let counted = |(steps, x): &mut (usize, u32)| {
+            *steps += 1;
+            step(x);
+        };
+        assert_eq!(hashed((0, 3), counted, |&(_, x)| x), expected);
+        assert_eq!(floyd((0, 3), counted, |&(_, x)| x), expected);
+    }
+
+    #[test]
+    fn fast_forwards() {
+        let cycle = Cycle {
+            start: 2,
+            length: 6,
+        };
+        assert_eq!(cycle.equivalent(1), 1);
+        assert_eq!(cycle.equivalent(8), 2);
+        assert_eq!(cycle.equivalent(1_000_000_000_000), 4);
+        assert_eq!(cycle.skipped(7), 0);
+        assert_eq!(cycle.skipped(21), 3);
+
+        let mut state = 3;
+        let mut states = vec![state];
+        for _ in 0..100 {
+            step(&mut state);
+            states.push(state);
+        }
+        assert!((0..100).all(|n| states[cycle.equivalent(n)] == states[n]));
+
+        // A counter that grows by 2 per step, plus 1 on every visit of state 10
+        let mut values = vec![0];
+        for n in 1..states.len() {
+            values.push(values[n - 1] + 2 + (states[n] == 10) as usize);
+        }
+        assert!((0..100).all(|n| cycle.extrapolate(n, &values[..9]) == values[n]));
+    }
+}
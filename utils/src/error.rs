@@ -0,0 +1,129 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::Input;
+
+/// A problem with the puzzle input, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line within the parsed text
+    pub line: usize,
+    /// 1-based column (in characters) within that line
+    pub column: usize,
+    /// The offending text, empty if the input ended too early
+    pub text: String,
+    /// What the parser expected instead
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error for `offending`, which should be a slice of `source` to get its position.
+    pub fn new(source: &str, offending: &str, expected: impl Into<String>) -> Self {
+        let start = source.as_ptr() as usize;
+        let pos = offending.as_ptr() as usize;
+        let offset = if (start..=start + source.len()).contains(&pos) {
+            pos - start
+        } else {
+            0
+        };
+
+        let before = &source[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text: offending.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for input that ended before `expected` was found.
+    pub fn missing(source: &str, expected: impl Into<String>) -> Self {
+        let end = source.trim_end();
+        Self::new(source, &end[end.len()..], expected)
+    }
+
+    /// Creates an error for text the `toml` crate could not read, pointing at the part it
+    /// reports. Where something is missing, that is the line up to that point.
+    pub fn toml(source: &str, error: &toml::de::Error) -> Self {
+        let span = error.span().unwrap_or(source.len()..source.len());
+        let expected = format!("valid TOML ({})", error.message());
+        if !span.is_empty() {
+            return Self::new(source, &source[span], expected);
+        }
+        let line_start = source[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let line = source[line_start..span.start].trim();
+        match line.is_empty() {
+            true => Self::missing(source, expected),
+            false => Self::new(source, line, expected),
+        }
+    }
+
+    /// Moves the error down by `lines`, for parsers that only saw a part of the input.
+    pub fn shifted(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+
+    /// Renders a compiler-style diagnostic that shows the offending line of `input`.
+    pub fn render(&self, input: &str) -> String {
+        let source_line = input.lines().nth(self.line - 1).unwrap_or_default();
+        let number = self.line.to_string();
+        let padding = " ".repeat(number.len());
+        let marker = "^".repeat(self.text.chars().count().max(1));
+        format!(
+            "error: expected {}, found {}\n{}--> line {}, column {}\n{} |\n{} | {}\n{} | {}{}",
+            self.expected,
+            self.found(),
+            padding,
+            self.line,
+            self.column,
+            padding,
+            number,
+            source_line,
+            padding,
+            " ".repeat(self.column - 1),
+            marker
+        )
+    }
+
+    fn found(&self) -> String {
+        if self.text.is_empty() {
+            String::from("end of input")
+        } else {
+            format!("'{}'", self.text)
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line,
+            self.column,
+            self.expected,
+            self.found()
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of `source`, as a number.
+pub fn parse_number<T: FromStr>(source: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::new(source, token, "a number"))
+}
+
+/// Parses every line of `text` on its own, see [`Input::parse_lines`].
+pub fn parse_lines<T: FromStr<Err = ParseError>>(text: &str) -> Result<Vec<T>, ParseError> {
+    Input::new(text).parse_lines()
+}
+
+/// Parses every block of `text`, where blocks are separated by an empty line, see
+/// [`Input::parse_blocks`].
+pub fn parse_blocks<T: FromStr<Err = ParseError>>(text: &str) -> Result<Vec<T>, ParseError> {
+    Input::new(text).parse_blocks()
+}
@@ -0,0 +1,99 @@
+use crate::Solution;
+
+/// A day that can make up random puzzle inputs, e.g. to stress test or benchmark it on
+/// larger inputs than the real one.
+pub trait Generate: Solution {
+    /// The size of an input like the real puzzle input.
+    const SIZE: usize;
+
+    /// A valid puzzle input that grows with `size`, where what is counted depends on the day.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+}
+
+/// The input of the day for the given seed, of the typical size if none is given.
+pub fn input<S: Generate>(seed: u64, size: Option<usize>) -> String {
+    S::generate(&mut Rng::new(seed), size.unwrap_or(S::SIZE))
+}
+
+/// A small random number generator (SplitMix64), so that a seed gives the same input
+/// on every machine.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from `0..n`, where `n` must be positive.
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "Should choose from at least one number");
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// A number from `low..=high`.
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        let span = high.abs_diff(low) as usize + 1;
+        low + self.below(span) as i64
+    }
+
+    /// Whether an event with the given probability happened.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_for_a_seed() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(1), numbers(1));
+        assert_ne!(numbers(1), numbers(2));
+        // The first output of SplitMix64 for seed 0
+        assert_eq!(numbers(0)[0], 0xe220a8397b1dcdaf);
+    }
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = Rng::new(7);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            let n = rng.between(-2, 3);
+            assert!((-2..=3).contains(&n));
+            seen[(n + 2) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(rng.between(5, 5), 5);
+        assert!(!rng.chance(0.0) || rng.chance(1.0));
+
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,372 @@
+use std::{
+    fmt::Display,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+/// A point (or vector) in the plane.
+///
+/// Like on screen, `y` grows downwards, so [`Direction::Up`] decreases it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point (or vector) in space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// A coordinate of a point, any of the primitive integers.
+pub trait Coord: Copy {
+    /// The coordinate `delta` further, or `None` if that is out of the range of the type,
+    /// like below zero for unsigned coordinates.
+    fn checked_shift(self, delta: i8) -> Option<Self>;
+}
+
+macro_rules! impl_coord {
+    (signed: $($signed:ty),*; unsigned: $($unsigned:ty),*) => {
+        $(impl Coord for $signed {
+            fn checked_shift(self, delta: i8) -> Option<Self> {
+                self.checked_add(delta.into())
+            }
+        })*
+        $(impl Coord for $unsigned {
+            fn checked_shift(self, delta: i8) -> Option<Self> {
+                self.checked_add_signed(delta.into())
+            }
+        })*
+    };
+}
+
+impl_coord!(signed: i8, i16, i32, i64, i128, isize; unsigned: u8, u16, u32, u64, u128, usize);
+
+// The distance of two coordinates, which works for unsigned types as well
+fn distance<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    a.max(b) - a.min(b)
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point2<T> {
+    /// The number of orthogonal steps between the two points.
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// The number of steps between the two points if diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+}
+
+impl<T: Coord> Point2<T> {
+    /// The point one step away in the given direction.
+    ///
+    /// Panics if the step leaves the range of the coordinates, see [`Point2::checked_step`].
+    pub fn step(self, direction: Direction) -> Self {
+        self.checked_step(direction)
+            .unwrap_or_else(|| panic!("A step {:?} leaves the coordinates", direction))
+    }
+
+    /// The point one step away in the given direction, or `None` if it is out of the range
+    /// of the coordinates, like left of `x = 0` for unsigned ones.
+    pub fn checked_step(self, direction: Direction) -> Option<Self> {
+        let (x, y) = direction.delta();
+        Some(Point2::new(
+            self.x.checked_shift(x)?,
+            self.y.checked_shift(y)?,
+        ))
+    }
+
+    /// The up to 4 orthogonal neighbours, clockwise starting above the point.
+    pub fn neighbours4(self) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.checked_step(d))
+    }
+
+    /// The up to 8 neighbours including diagonals, clockwise starting above the point.
+    pub fn neighbours8(self) -> impl Iterator<Item = Self> {
+        Direction::ALL.into_iter().flat_map(move |d| {
+            let straight = self.checked_step(d);
+            let diagonal = straight.and_then(|p| p.checked_step(d.turn_right()));
+            [straight, diagonal].into_iter().flatten()
+        })
+    }
+}
+
+impl<T: Neg<Output = T>> Point2<T> {
+    /// Rotates the vector by a quarter turn clockwise around the origin.
+    pub fn rotate_right(self) -> Self {
+        Point2::new(-self.y, self.x)
+    }
+
+    /// Rotates the vector by a quarter turn counterclockwise around the origin.
+    pub fn rotate_left(self) -> Self {
+        Point2::new(self.y, -self.x)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    /// The number of orthogonal steps between the two points.
+    pub fn manhattan(self, other: Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// The number of steps between the two points if diagonal steps are allowed.
+    pub fn chebyshev(self, other: Self) -> T {
+        distance(self.x, other.x)
+            .max(distance(self.y, other.y))
+            .max(distance(self.z, other.z))
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    /// The up to 6 neighbours that share a face with the point.
+    pub fn neighbours6(self) -> impl Iterator<Item = Self> {
+        const OFFSETS: [(i8, i8, i8); 6] = [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ];
+        OFFSETS
+            .into_iter()
+            .filter_map(move |offset| self.checked_shift(offset))
+    }
+
+    /// The up to 26 neighbours that share a face, an edge or a corner with the point.
+    pub fn neighbours26(self) -> impl Iterator<Item = Self> {
+        (-1..=1i8)
+            .flat_map(|x| (-1..=1i8).flat_map(move |y| (-1..=1i8).map(move |z| (x, y, z))))
+            .filter(|&offset| offset != (0, 0, 0))
+            .filter_map(move |offset| self.checked_shift(offset))
+    }
+
+    fn checked_shift(self, (x, y, z): (i8, i8, i8)) -> Option<Self> {
+        Some(Point3::new(
+            self.x.checked_shift(x)?,
+            self.y.checked_shift(y)?,
+            self.z.checked_shift(z)?,
+        ))
+    }
+}
+
+macro_rules! impl_ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                $point { $($field: self.$field + rhs.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                $point { $($field: self.$field - rhs.$field),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self::Output {
+                $point { $($field: self.$field * rhs),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $point { $($field: -self.$field),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                $(self.$field += rhs.$field;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                $(self.$field -= rhs.$field;)*
+            }
+        }
+    };
+}
+
+impl_ops!(Point2 { x, y });
+impl_ops!(Point3 { x, y, z });
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/// One of the four directions on a map, where up is towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting with `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The direction after a quarter turn clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(1)
+    }
+
+    /// The direction after a quarter turn counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(-1)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// The direction after the given number of quarter turns, clockwise if positive.
+    pub fn rotate(self, quarter_turns: i32) -> Self {
+        Self::ALL[(self as i32 + quarter_turns).rem_euclid(4) as usize]
+    }
+
+    /// The vector of a single step in this direction.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = self.delta();
+        Point2::new(x.into(), y.into())
+    }
+
+    fn delta(self) -> (i8, i8) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Point2::new(3, -2);
+        let b = Point2::new(-1, 5);
+        assert_eq!(a + b, Point2::new(2, 3));
+        assert_eq!(a - b, Point2::new(4, -7));
+        assert_eq!(a * 3, Point2::new(9, -6));
+        assert_eq!(-a, Point2::new(-3, 2));
+
+        let mut c = Point3::new(1u32, 2, 3);
+        c += Point3::new(1, 1, 1);
+        assert_eq!(c, Point3::new(2, 3, 4));
+        c -= Point3::new(2, 0, 1);
+        assert_eq!(c, Point3::new(0, 3, 3));
+        assert_eq!(c.to_string(), "(0, 3, 3)");
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point2::new(1usize, 7);
+        let b = Point2::new(4usize, 2);
+        assert_eq!(a.manhattan(b), 8);
+        assert_eq!(a.chebyshev(b), 5);
+        assert_eq!(b.manhattan(a), 8);
+
+        let c = Point3::new(-1, 0, 2);
+        let d = Point3::new(1, -3, 2);
+        assert_eq!(c.manhattan(d), 5);
+        assert_eq!(c.chebyshev(d), 3);
+    }
+
+    #[test]
+    fn neighbours() {
+        let p = Point2::new(0i64, 0);
+        assert_eq!(
+            p.neighbours4().collect::<Vec<_>>(),
+            [
+                Point2::new(0, -1),
+                Point2::new(1, 0),
+                Point2::new(0, 1),
+                Point2::new(-1, 0)
+            ]
+        );
+        let all = p.neighbours8().collect::<Vec<_>>();
+        assert_eq!(all.len(), 8);
+        assert_eq!(all[1], Point2::new(1, -1));
+        assert!(all.iter().all(|n| n.chebyshev(p) == 1));
+
+        let q = Point3::new(5isize, 5, 5);
+        assert_eq!(q.neighbours6().count(), 6);
+        assert!(q.neighbours6().all(|n| n.manhattan(q) == 1));
+        assert_eq!(q.neighbours26().count(), 26);
+        assert!(q.neighbours26().all(|n| n.chebyshev(q) == 1));
+
+        // Unsigned points have no neighbours below zero
+        let corner = Point2::new(0usize, 0);
+        assert_eq!(
+            corner.neighbours4().collect::<Vec<_>>(),
+            [Point2::new(1, 0), Point2::new(0, 1)]
+        );
+        assert_eq!(corner.neighbours8().count(), 3);
+        assert_eq!(corner.checked_step(Direction::Left), None);
+        assert_eq!(Point3::new(0u8, 0, 5).neighbours26().count(), 11);
+    }
+
+    #[test]
+    fn directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Down.opposite(), Direction::Up);
+        assert_eq!(Direction::Right.rotate(-5), Direction::Up);
+
+        for d in Direction::ALL {
+            let offset = d.offset::<i32>();
+            assert_eq!(offset.rotate_right(), d.turn_right().offset());
+            assert_eq!(offset.rotate_left(), d.turn_left().offset());
+            assert_eq!(-offset, d.opposite().offset());
+        }
+        assert_eq!(Point2::new(2, 2).step(Direction::Up), Point2::new(2, 1));
+    }
+}
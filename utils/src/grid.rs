@@ -0,0 +1,342 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::{geom::Point2, Input, ParseError};
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// Cells are always addressed as `(x, y)`, where `x` is the column and `y` the row,
+/// with `(0, 0)` in the top left corner. A [`Point2<usize>`] can be used as index as well.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid from its rows, or `None` if they differ in length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, |row| row.len());
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses a map with one character per cell, converting every character with `cell`.
+    ///
+    /// Errors point at the first character `cell` rejects, or at a row of a different width.
+    pub fn parse_with(
+        s: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Input::new(s).grid(expected, cell)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.contains(pos)
+            .then(|| &mut self.cells[pos.1 * self.width + pos.0])
+    }
+
+    /// All positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// All cells together with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell (row by row) that matches `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The up to 4 positions above, right of, below and left of `pos` that lie in the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(pos, &OFFSETS_4)
+    }
+
+    /// Like [`Grid::neighbours4`], but including the diagonals.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbours(pos, &OFFSETS_8)
+    }
+
+    fn neighbours<'a>(
+        &'a self,
+        (x, y): (usize, usize),
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            self.contains(pos).then_some(pos)
+        })
+    }
+
+    /// The cells of row `y`, from left to right.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells of column `x`, from top to bottom.
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "Column {} is out of bounds", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl DoubleEndedIterator<Item = &T> + ExactSizeIterator> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Adds rows of `value` below the last one until the grid is `height` rows high.
+    pub fn grow(&mut self, height: usize, value: T)
+    where
+        T: Clone,
+    {
+        if height > self.height {
+            self.cells.resize(height * self.width, value);
+            self.height = height;
+        }
+    }
+
+    /// Mirrors the grid along its diagonal, so that rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    /// Converts every cell with `f`, keeping the layout.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        self.get(pos).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} is out of bounds for a {}x{} grid",
+                pos, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| {
+            panic!(
+                "Position {:?} is out of bounds for a {}x{} grid",
+                pos, width, height
+            )
+        })
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point2<usize>) -> &Self::Output {
+        &self[(pos.x, pos.y)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, pos: Point2<usize>) -> &mut Self::Output {
+        &mut self[(pos.x, pos.y)]
+    }
+}
+
+impl<T: TryFrom<char>> FromStr for Grid<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, "a valid cell", |c| T::try_from(c).ok())
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#..\n.#.\n..#\n#..";
+
+    #[test]
+    fn parses_and_displays() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[(1, 1)], '#');
+        assert_eq!(grid[(0, 3)], '#');
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.to_string(), format!("{}\n", MAP));
+    }
+
+    #[test]
+    fn points_at_errors() {
+        let error = "#..\n.#\n".parse::<Grid<char>>().unwrap_err();
+        assert_eq!((error.line, error.column), (2, 1));
+        assert_eq!(error.expected, "a row of width 3");
+
+        // Only characters up to U+00FF fit into a byte
+        let error = "..\n.€".parse::<Grid<u8>>().unwrap_err();
+        assert_eq!(error.text, "€");
+        let error =
+            Grid::parse_with("..\n.x", "'.' or '#'", |c| (c == '.').then_some(c)).unwrap_err();
+        assert_eq!((error.line, error.column), (2, 2));
+        assert_eq!(error.expected, "'.' or '#'");
+
+        assert!("".parse::<Grid<char>>().is_err());
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours8((2, 0)).collect::<Vec<_>>(),
+            [(2, 1), (1, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn rows_columns_and_transpose() {
+        let grid = Grid::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), [3, 6]);
+        assert_eq!(grid.column(0).rev().copied().collect::<Vec<_>>(), [4, 1]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 3);
+
+        let transposed = grid.transpose();
+        assert_eq!((transposed.width(), transposed.height()), (2, 3));
+        assert_eq!(transposed.row(2), [3, 6]);
+        assert_eq!(transposed[(1, 0)], grid[(0, 1)]);
+        assert_eq!(transposed.transpose(), grid);
+
+        assert!(Grid::from_rows(vec![vec![1], vec![2, 3]]).is_none());
+    }
+
+    #[test]
+    fn positions_and_mapping() {
+        let grid: Grid<char> = MAP.parse().unwrap();
+        let walls = grid.map(|&c| c == '#');
+        assert_eq!(
+            walls
+                .iter()
+                .filter(|(_, &w)| w)
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>(),
+            [(0, 0), (1, 1), (2, 2), (0, 3)]
+        );
+        assert_eq!(grid.position(|&c| c == '#'), Some((0, 0)));
+
+        let mut grid = grid;
+        grid[(2, 3)] = '#';
+        assert_eq!(grid.row(3), ['#', '.', '#']);
+        grid[Point2::new(1, 3)] = 'o';
+        assert_eq!(grid[Point2::new(1, 3)], 'o');
+    }
+
+    #[test]
+    fn grows() {
+        let mut grid = Grid::from_rows(vec![vec![1, 2]]).unwrap();
+        grid.grow(3, 0);
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid.row(2), [0, 0]);
+        grid.grow(1, 5);
+        assert_eq!(grid.height(), 3);
+    }
+}
@@ -0,0 +1,239 @@
+use std::str::FromStr;
+
+use crate::{grid::Grid, parse_number, ParseError};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+/// Puzzle text without the differences in how it may be given: a byte order mark, CRLF line
+/// endings and blank lines at the end are ignored.
+///
+/// Every line and block is a slice of the original text, so that errors created for them
+/// point at the right position in it.
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    text: &'a str,
+    // The indentation removed from every line
+    indent: usize,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let text = text.strip_prefix(BYTE_ORDER_MARK).unwrap_or(text);
+        let end = text
+            .lines()
+            .rfind(|line| !line.trim().is_empty())
+            .map_or(0, |line| offset(text, line) + line.len());
+        Input {
+            text: &text[..end],
+            indent: 0,
+        }
+    }
+
+    /// Ignores the indentation that all lines but the first have in common, like the one of
+    /// a multi-line string literal whose first line follows the opening quote.
+    ///
+    /// Only for inputs where leading whitespace carries no meaning.
+    pub fn dedent(self) -> Self {
+        let indent = self
+            .text
+            .lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        Input { indent, ..self }
+    }
+
+    /// The whole text, including any indentation.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The lines without their line endings and indentation.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.text.lines().map(|line| self.strip_indent(line))
+    }
+
+    /// The groups of lines separated by blank lines, with the number of the line (counted from
+    /// 0) each one starts on, to shift the errors found in them.
+    pub fn blocks(&self) -> impl Iterator<Item = (usize, Input<'a>)> + '_ {
+        let mut lines = self.text.lines().enumerate().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|(_, line)| line.trim().is_empty()).is_some() {}
+            let (first_line, first) = lines.next()?;
+            let mut last = first;
+            while let Some((_, line)) = lines.next_if(|(_, line)| !line.trim().is_empty()) {
+                last = line;
+            }
+            let block = Input {
+                text: &self.text[offset(self.text, first)..offset(self.text, last) + last.len()],
+                indent: self.indent,
+            };
+            Some((first_line, block))
+        })
+    }
+
+    /// Parses every line on its own.
+    pub fn parse_lines<T: FromStr<Err = ParseError>>(&self) -> Result<Vec<T>, ParseError> {
+        self.text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let stripped = self.strip_indent(line);
+                stripped.parse().map_err(|e: ParseError| {
+                    let indent = line.len() - stripped.len();
+                    ParseError {
+                        column: e.column + indent,
+                        ..e
+                    }
+                    .shifted(i)
+                })
+            })
+            .collect()
+    }
+
+    /// Parses every block on its own.
+    pub fn parse_blocks<T: FromStr<Err = ParseError>>(&self) -> Result<Vec<T>, ParseError> {
+        self.blocks()
+            .map(|(first_line, block)| {
+                block
+                    .text()
+                    .parse()
+                    .map_err(|e: ParseError| e.shifted(first_line))
+            })
+            .collect()
+    }
+
+    /// Parses a map with one character per cell, converting every character with `cell`.
+    ///
+    /// Errors point at the first character `cell` rejects, or at a row of a different width.
+    pub fn grid<T>(
+        &self,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut rows = Vec::new();
+        for line in self.lines() {
+            let row = line
+                .char_indices()
+                .map(|(i, c)| {
+                    cell(c).ok_or_else(|| {
+                        ParseError::new(self.text, &line[i..i + c.len_utf8()], expected)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if let Some(width) = rows.first().map(Vec::len).filter(|&w| w != row.len()) {
+                let expected = format!("a row of width {}", width);
+                return Err(ParseError::new(self.text, line, expected));
+            }
+            rows.push(row);
+        }
+        if rows.is_empty() {
+            return Err(ParseError::missing(self.text, "a row of the map"));
+        }
+        Ok(Grid::from_rows(rows).expect("Rows should have the same width"))
+    }
+
+    /// Parses every word as a number.
+    pub fn numbers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        self.text
+            .split_whitespace()
+            .map(|word| parse_number(self.text, word))
+            .collect()
+    }
+
+    fn strip_indent(&self, line: &'a str) -> &'a str {
+        // Indentation of other lines may end within a wide whitespace character of this one
+        let whitespace = line.len() - line.trim_start().len();
+        let mut end = whitespace.min(self.indent);
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        &line[end..]
+    }
+}
+
+// The byte offset of `part`, a slice of `text`
+fn offset(text: &str, part: &str) -> usize {
+    part.as_ptr() as usize - text.as_ptr() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn differences_are_ignored() {
+        let expected: Vec<&str> = vec!["1 2", "", "3"];
+        for text in [
+            "1 2\n\n3",
+            "1 2\n\n3\n",
+            "1 2\r\n\r\n3\r\n",
+            "\u{feff}1 2\n\n3\n\n  \n",
+        ] {
+            let input = Input::new(text);
+            assert_eq!(input.lines().collect::<Vec<_>>(), expected, "{:?}", text);
+            assert_eq!(input.numbers::<u32>(), Ok(vec![1, 2, 3]));
+        }
+    }
+
+    #[test]
+    fn dedent_keeps_relative_indentation() {
+        let input = Input::new("a\n        b\n\n          c\n        d").dedent();
+        assert_eq!(
+            input.lines().collect::<Vec<_>>(),
+            ["a", "b", "", "  c", "d"]
+        );
+
+        let map = Input::new("  ..\n.#..\n").dedent();
+        assert_eq!(map.lines().collect::<Vec<_>>(), ["  ..", ".#.."]);
+
+        // A wide whitespace character is kept rather than cut in half
+        let input = Input::new("a\n\u{85}b\n c").dedent();
+        assert_eq!(input.lines().collect::<Vec<_>>(), ["a", "\u{85}b", "c"]);
+    }
+
+    #[test]
+    fn blocks_know_their_first_line() {
+        let input = Input::new("1\n    2\n\n\n    3\r\n\r\n      4\n    5\n").dedent();
+        let blocks: Vec<(usize, Vec<&str>)> = input
+            .blocks()
+            .map(|(first_line, block)| (first_line, block.lines().collect()))
+            .collect();
+        assert_eq!(
+            blocks,
+            [(0, vec!["1", "2"]), (4, vec!["3"]), (6, vec!["  4", "5"])]
+        );
+    }
+
+    #[test]
+    fn errors_point_into_original_text() {
+        let input = Input::new("12\n        3x\n        45").dedent();
+        let error = input.parse_lines::<Number>().unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 9, "3x")
+        );
+
+        let error = input.grid("a digit", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            (error.line, error.column, error.text.as_str()),
+            (2, 10, "x")
+        );
+
+        let error = Input::new("12\n3\n").grid("a digit", |c| c.to_digit(10));
+        assert_eq!(error.unwrap_err().expected, "a row of width 2");
+    }
+
+    #[derive(Debug)]
+    struct Number;
+
+    impl FromStr for Number {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            parse_number::<u32>(s, s).map(|_| Number)
+        }
+    }
+}
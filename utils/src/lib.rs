@@ -1,16 +1,132 @@
-pub fn parse_text() -> String {
-    match std::env::args().len() {
-        2 => std::env::args()
-            .nth(1)
-            .expect("If there is only one argument, it should be the problem text"),
-        3 => {
-            assert!(std::env::args().nth(1).unwrap() == "-i");
-            let filename = std::env::args()
-                .nth(2)
-                .expect("There should be a file as argument");
-
-            std::fs::read_to_string(filename).expect("The file should exist")
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+pub use args::{Args, Format, Param, Params, Part};
+pub use config::Config;
+pub use error::{parse_blocks, parse_lines, parse_number, ParseError};
+pub use input::Input;
+pub use logging::Logging;
+pub use report::{Report, Step, Timings};
+
+pub mod animate;
+pub mod cycle;
+pub mod generate;
+pub mod geom;
+pub mod grid;
+pub mod reference;
+pub mod render;
+pub mod search;
+
+mod args;
+mod config;
+mod error;
+mod input;
+mod logging;
+mod report;
+
+/// The solver of a single day.
+///
+/// The puzzle text is parsed once into `Input`, which is then shared by both parts.
+pub trait Solution {
+    const DAY: usize;
+    /// Named parameters this day accepts on top of the common arguments, which can also be
+    /// set in `aoc.toml`.
+    const PARAMS: &'static [Param] = &[];
+    /// Whether the day implements [`animate::Animate`], which enables `--animate` and `--export`.
+    const ANIMATED: bool = false;
+
+    type Input;
+    type Part1: Into<Answer>;
+    type Part2: Into<Answer>;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError>;
+
+    /// Like `parse`, but honouring the values given for `PARAMS`.
+    fn parse_with(text: &str, _params: &Params) -> Result<Self::Input, ParseError> {
+        Self::parse(text)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Part1;
+    fn part2(input: &Self::Input) -> Self::Part2;
+}
+
+/// Type-erased answer of a single part, used where days with different answer types
+/// are handled together (e.g. the `aoc` runner).
+///
+/// In JSON, it is a number, a string or `null` for `Unsolved`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+    // There is no second part on day 25, and day 22 only solves its second part for some inputs
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+            Answer::Unsolved => write!(f, "-"),
         }
-        _ => panic!("Either we have one argument (the problem text) or 2 (where it is -i file)"),
     }
 }
+
+macro_rules! impl_answer_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Number(i64::try_from(value).expect("Answer should fit into an i64"))
+            }
+        })*
+    };
+}
+
+impl_answer_from_number!(u64, usize, isize);
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Number(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Number(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<()> for Answer {
+    fn from(_: ()) -> Self {
+        Answer::Unsolved
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Answer::Unsolved, Into::into)
+    }
+}
+
+/// The answers of both parts of a day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Answer,
+    pub part2: Answer,
+}
+
+pub fn solve<S: Solution>(text: &str) -> Result<Answers, ParseError> {
+    let input = S::parse(text)?;
+    Ok(Answers {
+        part1: S::part1(&input).into(),
+        part2: S::part2(&input).into(),
+    })
+}
@@ -0,0 +1,104 @@
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Which messages of the solvers are written to standard error, set with `-v`, `-vv` and
+/// `--log`.
+///
+/// The solvers log through the [`log`] macros: the state after every round of a simulation at
+/// the debug level and every node a search expands at the trace level. Messages are tagged
+/// with the module they come from, which starts with the crate of the day, like `day24` or
+/// `utils::search`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Logging {
+    /// 0 for warnings only, 1 for debug and 2 or more for trace messages
+    pub verbosity: u8,
+    /// Only messages of these modules and their submodules are written, all if it is empty
+    pub targets: Vec<String>,
+}
+
+impl Logging {
+    /// Adds the modules of a comma-separated list like `day24,utils::search` to the targets.
+    pub fn add_targets(&mut self, list: &str) -> Result<(), String> {
+        for target in list.split(',').map(str::trim) {
+            if target.is_empty() || target.contains(char::is_whitespace) {
+                return Err(format!("'{}' is not a list of modules", list));
+            }
+            self.targets.push(target.to_string());
+        }
+        Ok(())
+    }
+
+    /// The most detailed level written. Choosing modules without `-v` shows their debug
+    /// messages, as warnings alone are rarely what is looked for.
+    pub fn level(&self) -> LevelFilter {
+        match self.verbosity {
+            0 if self.targets.is_empty() => LevelFilter::Warn,
+            0 | 1 => LevelFilter::Debug,
+            _ => LevelFilter::Trace,
+        }
+    }
+
+    /// Installs the logger for the rest of the process. Only the first call has an effect.
+    pub fn init(&self) {
+        let logger = Box::leak(Box::new(Logger {
+            level: self.level(),
+            targets: self.targets.clone(),
+        }));
+        if log::set_logger(logger).is_ok() {
+            log::set_max_level(self.level());
+        }
+    }
+}
+
+struct Logger {
+    level: LevelFilter,
+    targets: Vec<String>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+            && (self.targets.is_empty()
+                || self.targets.iter().any(|t| within(metadata.target(), t)))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+// Whether `target` is the module `module` or one of its submodules, so that `day2` does not
+// cover `day24`
+fn within(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_modules() {
+        assert!(within("day24", "day24"));
+        assert!(within("utils::search", "utils"));
+        assert!(!within("day24", "day2"));
+        assert!(!within("utils", "utils::search"));
+    }
+
+    #[test]
+    fn chooses_level() {
+        let mut logging = Logging::default();
+        assert_eq!(logging.level(), LevelFilter::Warn);
+        logging.add_targets("day14, utils::cycle").unwrap();
+        assert_eq!(logging.targets, ["day14", "utils::cycle"]);
+        assert_eq!(logging.level(), LevelFilter::Debug);
+        logging.verbosity = 3;
+        assert_eq!(logging.level(), LevelFilter::Trace);
+        assert!(logging.add_targets("day14,,day17").is_err());
+    }
+}
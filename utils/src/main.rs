@@ -1,3 +1,50 @@
+use utils::{Args, Format, Param, Params, ParseError, Part, Solution};
+
+// Echoes the puzzle input, so that the argument handling shared by all days can be tested
+struct Echo;
+
+impl Solution for Echo {
+    const DAY: usize = 0;
+    const PARAMS: &'static [Param] = &[Param {
+        name: "offset",
+        help: "Added to the number of lines (default: 0)",
+        min: i64::MIN,
+    }];
+
+    type Input = (String, i64);
+    type Part1 = String;
+    type Part2 = i64;
+
+    fn parse(text: &str) -> Result<Self::Input, ParseError> {
+        Self::parse_with(text, &Params::default())
+    }
+
+    fn parse_with(text: &str, params: &Params) -> Result<Self::Input, ParseError> {
+        Ok((text.to_string(), params.get("offset").unwrap_or(0)))
+    }
+
+    fn part1((text, _): &Self::Input) -> Self::Part1 {
+        text.clone()
+    }
+
+    fn part2((text, offset): &Self::Input) -> Self::Part2 {
+        text.lines().count() as i64 + offset
+    }
+}
+
 fn main() {
-    println!("{}", utils::parse_text());
+    let args = Args::parse::<Echo>();
+    if args.format == Format::Json {
+        args.print_json::<Echo>();
+        return;
+    }
+
+    let input = args.input::<Echo>();
+
+    if args.runs(Part::One) {
+        println!("{}", Echo::part1(&input));
+    }
+    if args.runs(Part::Two) {
+        println!("Number of lines: {}", Echo::part2(&input));
+    }
 }
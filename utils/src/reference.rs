@@ -0,0 +1,118 @@
+use std::ops::Range;
+
+use crate::{
+    generate::{self, Generate},
+    Answer, Solution,
+};
+
+/// A day with a slow but obviously correct solver, to check the shortcuts of the real one
+/// against on small inputs.
+pub trait Reference: Solution {
+    fn reference_part1(input: &Self::Input) -> Self::Part1;
+
+    /// `None` if the second part cannot be solved without a shortcut, like dropping a
+    /// trillion rocks on day 17.
+    fn reference_part2(input: &Self::Input) -> Option<Self::Part2>;
+}
+
+/// Solves the inputs generated for `seeds` both ways, panicking with the input on the first
+/// answer that differs from the reference.
+pub fn check<S: Reference + Generate>(seeds: Range<u64>, size: usize) {
+    for seed in seeds {
+        let text = generate::input::<S>(seed, Some(size));
+        check_input::<S>(&text, &format!("seed {}", seed));
+    }
+}
+
+/// Like [`check`], but for a given input, e.g. one that is too unlikely to be generated.
+pub fn check_input<S: Reference>(text: &str, name: &str) {
+    let input = S::parse(text)
+        .unwrap_or_else(|e| panic!("{} should be valid:\n{}\n{}", name, e.render(text), text));
+
+    let (part1, expected): (Answer, Answer) =
+        (S::part1(&input).into(), S::reference_part1(&input).into());
+    assert_eq!(
+        part1, expected,
+        "part 1 differs from the reference for {}:\n{}",
+        name, text
+    );
+
+    if let Some(expected) = S::reference_part2(&input) {
+        let (part2, expected): (Answer, Answer) = (S::part2(&input).into(), expected.into());
+        assert_eq!(
+            part2, expected,
+            "part 2 differs from the reference for {}:\n{}",
+            name, text
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate::Rng, ParseError};
+
+    // Finds the largest number, but its first part forgets the last one
+    struct Largest;
+
+    impl Solution for Largest {
+        const DAY: usize = 0;
+
+        type Input = Vec<i64>;
+        type Part1 = i64;
+        type Part2 = i64;
+
+        fn parse(text: &str) -> Result<Self::Input, ParseError> {
+            text.lines()
+                .map(|line| crate::parse_number(text, line))
+                .collect()
+        }
+
+        fn part1(numbers: &Self::Input) -> Self::Part1 {
+            numbers[..numbers.len() - 1]
+                .iter()
+                .copied()
+                .max()
+                .unwrap_or(0)
+        }
+
+        fn part2(numbers: &Self::Input) -> Self::Part2 {
+            numbers.iter().copied().max().unwrap_or(0)
+        }
+    }
+
+    impl Reference for Largest {
+        fn reference_part1(numbers: &Self::Input) -> Self::Part1 {
+            let mut largest = 0;
+            for &n in numbers {
+                largest = largest.max(n);
+            }
+            largest
+        }
+
+        fn reference_part2(numbers: &Self::Input) -> Option<Self::Part2> {
+            Some(Self::reference_part1(numbers))
+        }
+    }
+
+    impl Generate for Largest {
+        const SIZE: usize = 10;
+
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            (0..size)
+                .map(|_| format!("{}\n", rng.between(1, 100)))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn agrees_without_shortcut() {
+        check_input::<Largest>("3\n7\n1\n", "example");
+    }
+
+    #[test]
+    #[should_panic(expected = "part 1 differs from the reference for seed")]
+    fn finds_wrong_shortcut() {
+        check::<Largest>(0..100, 3);
+    }
+}
@@ -0,0 +1,394 @@
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+type Rgb = [u8; 3];
+
+const BACKGROUND: Rgb = [15, 15, 35];
+// Tiles without a colour of their own are drawn in the yellow of the stars
+const OTHER: Rgb = [255, 255, 102];
+
+/// The colours of the tiles of a map, by the character that shows them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette(HashMap<char, Rgb>);
+
+impl Default for Palette {
+    fn default() -> Self {
+        let colors = [
+            (" .", BACKGROUND),
+            ("#", [204, 204, 204]),
+            ("o", [255, 204, 102]),
+            ("<>^v", [102, 153, 255]),
+            ("0123456789", [51, 102, 204]),
+            ("E", [0, 204, 0]),
+            ("|+-", [128, 128, 128]),
+        ];
+        Palette(
+            colors
+                .into_iter()
+                .flat_map(|(tiles, color)| tiles.chars().map(move |tile| (tile, color)))
+                .collect(),
+        )
+    }
+}
+
+impl Palette {
+    pub fn color(&self, tile: char) -> Rgb {
+        self.0.get(&tile).copied().unwrap_or(OTHER)
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    /// Parses changes to the default palette like `#=ffffff,.=000000`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut palette = Palette::default();
+        for entry in s.split(',').filter(|entry| !entry.is_empty()) {
+            let mut chars = entry.chars();
+            let (Some(tile), Some(hex)) = (chars.next(), chars.as_str().strip_prefix('=')) else {
+                return Err(format!("'{}' is not a colour like #=ff0000", entry));
+            };
+            let hex = hex.strip_prefix('#').unwrap_or(hex);
+            let color = u32::from_str_radix(hex, 16)
+                .ok()
+                .filter(|_| hex.len() == 6)
+                .ok_or_else(|| format!("'{}' is not a hex colour like ff0000", hex))?;
+            let [_, r, g, b] = color.to_be_bytes();
+            palette.0.insert(tile, [r, g, b]);
+        }
+        Ok(palette)
+    }
+}
+
+/// The image formats a simulation can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// A still of the last frame
+    Png,
+    /// All frames as an animation
+    Gif,
+}
+
+/// Where and how to export the frames of a simulation.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub path: PathBuf,
+    pub format: ImageFormat,
+    /// The width and height of a tile in pixels
+    pub scale: usize,
+    pub palette: Palette,
+}
+
+impl Export {
+    pub const DEFAULT_SCALE: usize = 4;
+
+    /// Exports to `path`, in the format given by its extension.
+    pub fn new(path: PathBuf) -> Result<Self, String> {
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("png") => ImageFormat::Png,
+            Some("gif") => ImageFormat::Gif,
+            _ => {
+                return Err(format!(
+                    "cannot export to {}, use a .png or .gif file",
+                    path.display()
+                ))
+            }
+        };
+        Ok(Export {
+            path,
+            format,
+            scale: Self::DEFAULT_SCALE,
+            palette: Palette::default(),
+        })
+    }
+}
+
+// The colours of a set of tiles, of which an image can only use 256
+struct ColorTable {
+    colors: Vec<Rgb>,
+    index: HashMap<char, u8>,
+}
+
+impl ColorTable {
+    fn new(tiles: impl IntoIterator<Item = char>, palette: &Palette) -> Self {
+        // The background comes first, as it is also used for padding lines that are too short
+        let mut table = ColorTable {
+            colors: vec![palette.color(' ')],
+            index: HashMap::from([(' ', 0)]),
+        };
+        for tile in tiles {
+            if table.index.contains_key(&tile) {
+                continue;
+            }
+            let color = palette.color(tile);
+            let index = match table.colors.iter().position(|&c| c == color) {
+                Some(index) => index,
+                None if table.colors.len() < 256 => {
+                    table.colors.push(color);
+                    table.colors.len() - 1
+                }
+                None => 0,
+            };
+            table.index.insert(tile, index as u8);
+        }
+        table
+    }
+
+    // Converts the frame to colour indices, `scale` pixels per tile in both directions
+    fn raster(&self, frame: &str, (width, height): (usize, usize), scale: usize) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(width * height * scale * scale);
+        let mut lines = frame.lines();
+        for _ in 0..height {
+            let mut row = Vec::with_capacity(width * scale);
+            let mut tiles = lines.next().unwrap_or_default().chars();
+            for _ in 0..width {
+                let index = tiles.next().map_or(0, |tile| self.index[&tile]);
+                row.extend(std::iter::repeat_n(index, scale));
+            }
+            for _ in 0..scale {
+                pixels.extend(&row);
+            }
+        }
+        pixels
+    }
+}
+
+// The size of a frame in tiles, at least one by one
+fn size(frame: &str) -> (usize, usize) {
+    let width = frame.lines().map(|l| l.chars().count()).max();
+    (
+        width.unwrap_or_default().max(1),
+        frame.lines().count().max(1),
+    )
+}
+
+/// Draws a frame as a PNG image.
+pub fn png(frame: &str, palette: &Palette, scale: usize) -> Result<Vec<u8>, String> {
+    let (width, height) = size(frame);
+    let table = ColorTable::new(frame.chars().filter(|&c| c != '\n'), palette);
+    let (pixel_width, pixel_height) = pixel_size((width, height), scale, u32::MAX as usize)?;
+    let pixels = table.raster(frame, (width, height), scale);
+
+    let mut png = Vec::new();
+    let mut encoder = ::png::Encoder::new(&mut png, pixel_width as u32, pixel_height as u32);
+    encoder.set_color(::png::ColorType::Indexed);
+    encoder.set_depth(::png::BitDepth::Eight);
+    encoder.set_compression(::png::Compression::Best);
+    encoder.set_palette(table.colors.as_flattened());
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer
+        .write_image_data(&pixels)
+        .and_then(|_| writer.finish())
+        .map_err(|e| e.to_string())?;
+    Ok(png)
+}
+
+// The size of a frame in pixels, which the image format limits to `max` in both directions
+fn pixel_size(
+    (width, height): (usize, usize),
+    scale: usize,
+    max: usize,
+) -> Result<(usize, usize), String> {
+    match (width.checked_mul(scale), height.checked_mul(scale)) {
+        (Some(w), Some(h)) if w <= max && h <= max => Ok((w, h)),
+        _ => Err(format!(
+            "{}x{} tiles at a scale of {} do not fit in an image of at most {}x{} pixels",
+            width, height, scale, max, max
+        )),
+    }
+}
+
+/// Draws the frames as an animated GIF that shows every frame for `delay` hundredths of a
+/// second and loops forever.
+///
+/// `frames` is called twice, first to find the size of the animation and the tiles it uses,
+/// which saves keeping all frames in memory. Frames smaller than the largest one are drawn
+/// in the top left corner.
+pub fn gif<I: Iterator<Item = String>>(
+    frames: impl Fn() -> I,
+    palette: &Palette,
+    scale: usize,
+    delay: u16,
+) -> Result<Vec<u8>, String> {
+    let mut tiles = Vec::new();
+    let (mut width, mut height) = (1, 1);
+    for frame in frames() {
+        let size = size(&frame);
+        (width, height) = (width.max(size.0), height.max(size.1));
+        for tile in frame.chars().filter(|&c| c != '\n') {
+            if !tiles.contains(&tile) {
+                tiles.push(tile);
+            }
+        }
+    }
+    let table = ColorTable::new(tiles, palette);
+    let (pixel_width, pixel_height) = pixel_size((width, height), scale, u16::MAX as usize)?;
+
+    let mut gif = Vec::new();
+    let mut encoder = ::gif::Encoder::new(
+        &mut gif,
+        pixel_width as u16,
+        pixel_height as u16,
+        table.colors.as_flattened(),
+    )
+    .map_err(|e| e.to_string())?;
+    encoder
+        .set_repeat(::gif::Repeat::Infinite)
+        .map_err(|e| e.to_string())?;
+
+    let mut previous: Option<Vec<u8>> = None;
+    for frame in frames() {
+        let pixels = table.raster(&frame, (width, height), scale);
+        // Only the rectangle that changed since the previous frame needs to be stored
+        let (left, top, right, bottom) = match &previous {
+            Some(previous) => changed(previous, &pixels, pixel_width).unwrap_or((0, 0, 0, 0)),
+            None => (0, 0, pixel_width - 1, pixel_height - 1),
+        };
+        let rectangle: Vec<u8> = (top..=bottom)
+            .flat_map(|y| &pixels[y * pixel_width + left..=y * pixel_width + right])
+            .copied()
+            .collect();
+        let mut image = ::gif::Frame::from_indexed_pixels(
+            (right - left + 1) as u16,
+            (bottom - top + 1) as u16,
+            rectangle,
+            None,
+        );
+        (image.left, image.top) = (left as u16, top as u16);
+        image.delay = delay;
+        // Keep the previous frame below this one
+        image.dispose = ::gif::DisposalMethod::Keep;
+        encoder.write_frame(&image).map_err(|e| e.to_string())?;
+
+        previous = Some(pixels);
+    }
+    encoder.into_inner().map_err(|e| e.to_string())?;
+    Ok(gif)
+}
+
+// The bounding box (left, top, right, bottom) of the pixels that differ
+fn changed(a: &[u8], b: &[u8], width: usize) -> Option<(usize, usize, usize, usize)> {
+    let mut bounds: Option<(usize, usize, usize, usize)> = None;
+    for (i, _) in a.iter().zip(b).enumerate().filter(|(_, (a, b))| a != b) {
+        let (x, y) = (i % width, i / width);
+        bounds = Some(match bounds {
+            None => (x, y, x, y),
+            Some((left, top, right, bottom)) => (left.min(x), top, right.max(x), bottom.max(y)),
+        });
+    }
+    bounds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "#.o\n.E#\n";
+
+    #[test]
+    fn parses_palettes() {
+        let palette: Palette = "#=ff0000,.=#00ff00".parse().unwrap();
+        assert_eq!(palette.color('#'), [255, 0, 0]);
+        assert_eq!(palette.color('.'), [0, 255, 0]);
+        assert_eq!(palette.color(' '), BACKGROUND);
+        assert_eq!(palette.color('x'), OTHER);
+        assert_eq!(
+            "==123456".parse::<Palette>().unwrap().color('='),
+            [0x12, 0x34, 0x56]
+        );
+
+        assert!("#".parse::<Palette>().is_err());
+        assert!("#=red".parse::<Palette>().is_err());
+        assert!("#=fffffff".parse::<Palette>().is_err());
+    }
+
+    #[test]
+    fn encodes_png() {
+        let palette: Palette = "o=ff0000".parse().unwrap();
+        let png = png(MAP, &palette, 3).unwrap();
+        let decoder = ::png::Decoder::new(png.as_slice());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((info.width, info.height), (9, 6));
+
+        let colors = reader.info().palette.as_ref().unwrap().to_vec();
+        let color = |x: usize, y: usize| {
+            let index = pixels[y * 9 + x] as usize * 3;
+            [colors[index], colors[index + 1], colors[index + 2]]
+        };
+        assert_eq!(color(0, 0), palette.color('#'));
+        assert_eq!(color(2, 2), palette.color('#'));
+        assert_eq!(color(3, 0), palette.color('.'));
+        assert_eq!(color(8, 2), [255, 0, 0]);
+        assert_eq!(color(4, 4), palette.color('E'));
+    }
+
+    #[test]
+    fn compresses_repetitions() {
+        let map = format!("{}\n", "#".repeat(100)).repeat(100);
+        assert!(png(&map, &Palette::default(), 4).unwrap().len() < 2000);
+    }
+
+    #[test]
+    fn encodes_gif() {
+        // Many frames with many colours grow the LZW codes to their maximum size
+        let frames = || {
+            (0..20).map(|i| {
+                (0..40)
+                    .map(|y| {
+                        (0..40)
+                            .map(|x| char::from(b"#.o<>^v0123456789E|+-"[(x * y + i) % 21]))
+                            .collect::<String>()
+                            + "\n"
+                    })
+                    .collect::<String>()
+            })
+        };
+        let gif = gif(frames, &Palette::default(), 2, 5).unwrap();
+
+        let mut options = ::gif::DecodeOptions::new();
+        options.set_color_output(::gif::ColorOutput::RGBA);
+        let mut decoder = options.read_info(gif.as_slice()).unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (80, 80));
+        let mut count = 0;
+        let mut screen = vec![0; 80 * 80 * 4];
+        while let Some(frame) = decoder.read_next_frame().unwrap() {
+            assert_eq!(frame.delay, 5);
+            // Draw the changed rectangle on top of the previous frame
+            for y in 0..frame.height as usize {
+                for x in 0..frame.width as usize {
+                    let (sx, sy) = (x + frame.left as usize, y + frame.top as usize);
+                    let pixel = &frame.buffer[(y * frame.width as usize + x) * 4..][..4];
+                    screen[(sy * 80 + sx) * 4..][..4].copy_from_slice(pixel);
+                }
+            }
+            let expected = frames().nth(count).unwrap();
+            let tile = expected.lines().nth(13).unwrap().chars().nth(7).unwrap();
+            assert_eq!(
+                screen[(26 * 80 + 15) * 4..][..3],
+                Palette::default().color(tile)
+            );
+            count += 1;
+        }
+        assert_eq!(count, 20);
+    }
+
+    #[test]
+    fn pads_smaller_frames() {
+        let frames = || ["#\n", "##\n##\n", ""].into_iter().map(String::from);
+        let gif = gif(frames, &Palette::default(), 1, 10).unwrap();
+        let decoder = ::gif::DecodeOptions::new()
+            .read_info(gif.as_slice())
+            .unwrap();
+        assert_eq!((decoder.width(), decoder.height()), (2, 2));
+        assert_eq!(decoder.into_iter().count(), 3);
+    }
+
+    #[test]
+    fn rejects_large_gifs() {
+        let frames = || std::iter::once(format!("{}\n", "#".repeat(16384)));
+        let error = gif(frames, &Palette::default(), 4, 10).unwrap_err();
+        assert!(error.contains("at most 65535x65535 pixels"), "{}", error);
+        assert!(gif(frames, &Palette::default(), 3, 10).is_ok());
+    }
+}
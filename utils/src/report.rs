@@ -0,0 +1,203 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Answer, Answers, Args, Params, ParseError, Part, Solution};
+
+/// The answers of a single day together with the time each step took.
+///
+/// Parts that were not requested with `--part` are `None`.
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: usize,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+    pub timings: Timings,
+}
+
+/// A step of solving a day that has just finished, reported while the next one runs.
+#[derive(Debug, Clone)]
+pub enum Step {
+    Parsed(Duration),
+    Solved(Part, Answer, Duration),
+}
+
+#[derive(Debug, Clone)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+impl Report {
+    /// Solves the requested parts of the day, timing parsing and each part separately.
+    pub fn run<S: Solution>(args: &Args) -> Result<Self, ParseError> {
+        Self::solve::<S>(
+            &args.text,
+            &args.params,
+            |part| args.runs(part),
+            &mut |_| (),
+        )
+    }
+
+    /// Solves both parts of the day with the given parameters, timing each step.
+    pub fn measure<S: Solution>(text: &str, params: &Params) -> Result<Self, ParseError> {
+        Self::solve::<S>(text, params, |_| true, &mut |_| ())
+    }
+
+    /// Like `measure`, but only solves `part` if one is given, and tells `on_step` about every
+    /// step as soon as it is done, so that a caller on another thread can tell which part is
+    /// running.
+    pub fn measure_steps<S: Solution>(
+        text: &str,
+        params: &Params,
+        part: Option<Part>,
+        on_step: &mut dyn FnMut(Step),
+    ) -> Result<Self, ParseError> {
+        Self::solve::<S>(text, params, |p| part.is_none_or(|part| part == p), on_step)
+    }
+
+    fn solve<S: Solution>(
+        text: &str,
+        params: &Params,
+        runs: impl Fn(Part) -> bool,
+        on_step: &mut dyn FnMut(Step),
+    ) -> Result<Self, ParseError> {
+        let start = Instant::now();
+        let input = S::parse_with(text, params)?;
+        let parse = start.elapsed();
+        on_step(Step::Parsed(parse));
+
+        let (part1, time1) = timed(runs(Part::One), || S::part1(&input).into());
+        if let (Some(answer), Some(time)) = (&part1, time1) {
+            on_step(Step::Solved(Part::One, answer.clone(), time));
+        }
+        let (part2, time2) = timed(runs(Part::Two), || S::part2(&input).into());
+        if let (Some(answer), Some(time)) = (&part2, time2) {
+            on_step(Step::Solved(Part::Two, answer.clone(), time));
+        }
+
+        Ok(Report {
+            day: S::DAY,
+            part1,
+            part2,
+            timings: Timings {
+                parse,
+                part1: time1,
+                part2: time2,
+            },
+        })
+    }
+
+    /// The answers of both parts, where parts that were not run count as unsolved.
+    pub fn answers(&self) -> Answers {
+        Answers {
+            part1: self.part1.clone().unwrap_or(Answer::Unsolved),
+            part2: self.part2.clone().unwrap_or(Answer::Unsolved),
+        }
+    }
+
+    /// Renders the report as a single line of JSON, with timings in milliseconds.
+    pub fn to_json(&self) -> String {
+        let json = JsonReport {
+            day: self.day,
+            part1: self.part1.clone(),
+            part2: self.part2.clone(),
+            timings: JsonTimings {
+                parse_ms: millis(self.timings.parse),
+                part1_ms: self.timings.part1.map(millis),
+                part2_ms: self.timings.part2.map(millis),
+            },
+        };
+        serde_json::to_string(&json).expect("Reports should serialize")
+    }
+
+    /// Reads a report back from the JSON of `to_json`. Unsolved parts come back as `None`.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let json: JsonReport = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        let duration = |ms: f64| Duration::from_secs_f64(ms.max(0.0) / 1000.0);
+        Ok(Report {
+            day: json.day,
+            part1: json.part1,
+            part2: json.part2,
+            timings: Timings {
+                parse: duration(json.timings.parse_ms),
+                part1: json.timings.part1_ms.map(duration),
+                part2: json.timings.part2_ms.map(duration),
+            },
+        })
+    }
+}
+
+// The layout of a report in JSON
+#[derive(Serialize, Deserialize)]
+struct JsonReport {
+    day: usize,
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+    timings: JsonTimings,
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonTimings {
+    parse_ms: f64,
+    part1_ms: Option<f64>,
+    part2_ms: Option<f64>,
+}
+
+impl Timings {
+    /// The time spent parsing and solving all parts that were run.
+    pub fn total(&self) -> Duration {
+        self.parse + self.part1.unwrap_or_default() + self.part2.unwrap_or_default()
+    }
+}
+
+fn timed(requested: bool, solve: impl FnOnce() -> Answer) -> (Option<Answer>, Option<Duration>) {
+    if !requested {
+        return (None, None);
+    }
+    let start = Instant::now();
+    let answer = solve();
+    (Some(answer), Some(start.elapsed()))
+}
+
+// Milliseconds, rounded to microseconds
+fn millis(time: Duration) -> f64 {
+    (time.as_secs_f64() * 1_000_000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_json_back() {
+        let report = Report {
+            day: 10,
+            part1: Some(Answer::Number(14060)),
+            part2: Some(Answer::Text(String::from("#.\n.\"#\u{1}"))),
+            timings: Timings {
+                parse: Duration::from_micros(100),
+                part1: Some(Duration::from_millis(12)),
+                part2: None,
+            },
+        };
+        let json = report.to_json();
+        assert_eq!(
+            json,
+            r##"{"day":10,"part1":14060,"part2":"#.\n.\"#\u0001","timings":{"parse_ms":0.1,"part1_ms":12.0,"part2_ms":null}}"##
+        );
+        let read = Report::from_json(&json).unwrap();
+        assert_eq!((read.part1, read.part2), (report.part1, report.part2));
+        assert_eq!(read.timings.part1, report.timings.part1);
+
+        // Keys may come in any order, and unsolved parts are null
+        let read = Report::from_json(
+            r#"{"timings":{"part2_ms":null,"part1_ms":1.5,"parse_ms":0},"part2":null,"part1":"2=-1=0","day":25}"#,
+        )
+        .unwrap();
+        assert_eq!(read.part1, Some(Answer::Text(String::from("2=-1=0"))));
+        assert_eq!(read.part2, None);
+        assert!(Report::from_json(r#"{"day":25,"part1":[1]}"#).is_err());
+    }
+}
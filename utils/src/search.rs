@@ -0,0 +1,317 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use log::debug;
+
+/// The cost of a path, where `Default` is the cost of the empty path.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<C: Copy + Ord + Add<Output = C> + Default> Cost for C {}
+
+/// Finds a path with the fewest steps from `start` to a node satisfying `is_goal`.
+///
+/// Returns the number of steps together with the path, which includes both `start`
+/// and the goal, or `None` if no goal is reachable.
+pub fn bfs<N, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    bfs_multi([start], successors, is_goal)
+}
+
+/// Like [`bfs`], but starting from all of `starts` at once, which finds the path
+/// from the start closest to a goal.
+pub fn bfs_multi<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited = Visited::default();
+    let mut queue: VecDeque<usize> = starts
+        .into_iter()
+        .filter_map(|start| visited.improve(start, None, 0))
+        .collect();
+
+    while let Some(current) = queue.pop_front() {
+        let (node, steps) = (&visited.nodes[current].node, visited.nodes[current].cost);
+        visited.expanded += 1;
+        if is_goal(node) {
+            visited.report(true);
+            return Some((steps, visited.path(current)));
+        }
+        for successor in successors(node) {
+            // The first visit of a node is always the one with the fewest steps
+            if !visited.index.contains_key(&successor) {
+                queue.extend(visited.improve(successor, Some(current), steps + 1));
+            }
+        }
+    }
+    visited.report(false);
+    None
+}
+
+/// Finds a cheapest path from `start` to a node satisfying `is_goal`, where `successors`
+/// returns the neighbours of a node together with the cost of moving there.
+///
+/// Returns the total cost together with the path, which includes both `start`
+/// and the goal, or `None` if no goal is reachable.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_multi([start], successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but starting from all of `starts` at once.
+pub fn dijkstra_multi<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_multi(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but guided towards the goal by `heuristic`, an estimate of the
+/// remaining cost from a node.
+///
+/// The path is only guaranteed to be cheapest if the heuristic never overestimates.
+pub fn astar<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar_multi([start], successors, heuristic, is_goal)
+}
+
+/// Like [`astar`], but starting from all of `starts` at once.
+pub fn astar_multi<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(C, Vec<N>)>
+where
+    N: Eq + Hash + Clone,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut visited = Visited::default();
+    let mut open = BinaryHeap::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        if let Some(index) = visited.improve(start, None, C::default()) {
+            open.push(Reverse((estimate, C::default(), index)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, current))) = open.pop() {
+        // Instead of updating the priority of a node in the queue when a cheaper way to it
+        // is found, it is pushed again and the outdated entry is skipped here
+        if cost > visited.nodes[current].cost {
+            continue;
+        }
+        let node = &visited.nodes[current].node;
+        visited.expanded += 1;
+        if is_goal(node) {
+            visited.report(true);
+            return Some((cost, visited.path(current)));
+        }
+        for (successor, step) in successors(node) {
+            let estimate = heuristic(&successor);
+            let successor_cost = cost + step;
+            if let Some(index) = visited.improve(successor, Some(current), successor_cost) {
+                open.push(Reverse((successor_cost + estimate, successor_cost, index)));
+            }
+        }
+    }
+    visited.report(false);
+    None
+}
+
+struct Entry<N, C> {
+    node: N,
+    parent: Option<usize>,
+    cost: C,
+}
+
+// Every node seen so far with the cheapest known way to reach it, so that paths can be
+// reconstructed without storing them in the queue
+struct Visited<N, C> {
+    index: HashMap<N, usize>,
+    nodes: Vec<Entry<N, C>>,
+    // How many nodes were taken from the queue
+    expanded: usize,
+}
+
+impl<N, C> Default for Visited<N, C> {
+    fn default() -> Self {
+        Visited {
+            index: HashMap::new(),
+            nodes: Vec::new(),
+            expanded: 0,
+        }
+    }
+}
+
+impl<N, C> Visited<N, C> {
+    fn report(&self, found: bool) {
+        let outcome = if found { "Reached" } else { "Could not reach" };
+        debug!(
+            "{} a goal after expanding {} of {} nodes seen",
+            outcome,
+            self.expanded,
+            self.nodes.len()
+        );
+    }
+}
+
+impl<N: Eq + Hash + Clone, C: Ord> Visited<N, C> {
+    // Records `cost` as the cost of reaching `node` from `parent` if it is cheaper than
+    // what is known, returning the index of the node in that case
+    fn improve(&mut self, node: N, parent: Option<usize>, cost: C) -> Option<usize> {
+        match self.index.get(&node) {
+            Some(&index) if self.nodes[index].cost <= cost => None,
+            Some(&index) => {
+                self.nodes[index].parent = parent;
+                self.nodes[index].cost = cost;
+                Some(index)
+            }
+            None => {
+                let index = self.nodes.len();
+                self.index.insert(node.clone(), index);
+                self.nodes.push(Entry { node, parent, cost });
+                Some(index)
+            }
+        }
+    }
+
+    fn path(&self, mut index: usize) -> Vec<N> {
+        let mut path = vec![self.nodes[index].node.clone()];
+        while let Some(parent) = self.nodes[index].parent {
+            path.push(self.nodes[parent].node.clone());
+            index = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    const MAZE: &str = "\
+S.#.....
+.##.##..
+...#....
+.#...#G.";
+
+    fn open_neighbours(maze: &Grid<char>, pos: (usize, usize)) -> Vec<(usize, usize)> {
+        maze.neighbours4(pos).filter(|&n| maze[n] != '#').collect()
+    }
+
+    #[test]
+    fn finds_shortest_paths_in_a_maze() {
+        let maze: Grid<char> = MAZE.parse().unwrap();
+        let start = maze.position(|&c| c == 'S').unwrap();
+        let goal = maze.position(|&c| c == 'G').unwrap();
+
+        let (steps, path) = bfs(start, |&p| open_neighbours(&maze, p), |&p| p == goal).unwrap();
+        assert_eq!(steps, 11);
+        assert_eq!(path.len(), steps + 1);
+        assert_eq!((path[0], path[steps]), (start, goal));
+        assert!(path
+            .windows(2)
+            .all(|w| maze.neighbours4(w[0]).any(|n| n == w[1])));
+
+        let unit_cost = |&p: &(usize, usize)| open_neighbours(&maze, p).into_iter().map(|n| (n, 1));
+        let manhattan = |&(x, y): &(usize, usize)| x.abs_diff(goal.0) + y.abs_diff(goal.1);
+        assert_eq!(
+            dijkstra(start, unit_cost, |&p| p == goal).map(|(cost, _)| cost),
+            Some(11)
+        );
+        assert_eq!(
+            astar(start, unit_cost, manhattan, |&p| p == goal).map(|(cost, _)| cost),
+            Some(11)
+        );
+
+        let walled_in = |&p: &(usize, usize)| {
+            open_neighbours(&maze, p)
+                .into_iter()
+                .filter(|&n| n != (0, 1))
+        };
+        assert_eq!(bfs(start, walled_in, |&p| p == goal), None);
+    }
+
+    #[test]
+    fn updates_cheaper_paths() {
+        // The direct edge to 'c' is found first, but going through 'b' is cheaper
+        let edges = |&n: &char| match n {
+            'a' => vec![('c', 10), ('b', 1)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+        assert_eq!(
+            dijkstra('a', edges, |&n| n == 'd'),
+            Some((4, vec!['a', 'b', 'c', 'd']))
+        );
+        assert_eq!(
+            bfs(
+                'a',
+                |&n| edges(&n).into_iter().map(|(n, _)| n),
+                |&n| n == 'd'
+            ),
+            Some((2, vec!['a', 'c', 'd']))
+        );
+        assert_eq!(dijkstra('a', edges, |&n| n == 'e'), None);
+    }
+
+    #[test]
+    fn starts_from_several_nodes() {
+        let successors = |&n: &i32| [n + 1, n - 1];
+        assert_eq!(
+            bfs_multi([0, 17, -3], successors, |&n| n == 12),
+            Some((5, vec![17, 16, 15, 14, 13, 12]))
+        );
+        assert_eq!(bfs_multi([4], successors, |&n| n == 4), Some((0, vec![4])));
+
+        let costs = |&n: &i32| [(n + 1, 1), (n * 2, 1)];
+        assert_eq!(
+            dijkstra_multi([1, 5], costs, |&n| n == 20).map(|(cost, _)| cost),
+            Some(2)
+        );
+        assert_eq!(astar_multi([], costs, |_| 0, |&n| n == 20), None);
+    }
+}
@@ -56,3 +56,114 @@ fn file_as_argument() -> Result<(), Box<dyn std::error::Error>> {
         .stdout(predicate::str::contains(argument));
     Ok(())
 }
+
+#[test]
+fn stdin_as_argument() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.arg("--stdin")
+        .stdin(std::fs::File::open("tests/file_argument.txt")?);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("1000\n2000\n3000\n\n4000"))
+        .stdout(predicate::str::contains("Number of lines: 14"));
+    Ok(())
+}
+
+#[test]
+fn single_part() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.args(["-i", "tests/file_argument.txt", "--part", "2"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Number of lines: 14"))
+        .stdout(predicate::str::contains("1000").not());
+    Ok(())
+}
+
+#[test]
+fn named_parameter() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.args([
+        "--offset",
+        "-4",
+        "--part",
+        "2",
+        "-i",
+        "tests/file_argument.txt",
+    ]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Number of lines: 10"));
+    Ok(())
+}
+
+#[test]
+fn help() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.arg("--help");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: utils [OPTIONS]"))
+        .stdout(predicate::str::contains(
+            "--offset <N>       Added to the number of lines (default: 0)",
+        ));
+    Ok(())
+}
+
+#[test]
+fn usage_errors() -> Result<(), Box<dyn std::error::Error>> {
+    for (args, message) in [
+        (vec!["--unknown"], "unexpected argument '--unknown'"),
+        (vec!["--part", "3", "text"], "'3' is not a part, use 1 or 2"),
+        (
+            vec!["--offset", "many", "text"],
+            "'many' is not a valid integer",
+        ),
+        (vec!["-i"], "--input requires a file"),
+        (vec!["--config"], "--config requires a file"),
+        (
+            vec!["text", "--stdin"],
+            "only one of <TEXT>, --input and --stdin",
+        ),
+        (vec![], "no puzzle input given"),
+        // Only days with a simulation can be animated
+        (vec!["--animate", "text"], "unexpected argument '--animate'"),
+    ] {
+        let mut cmd = Command::cargo_bin("utils")?;
+        cmd.args(args);
+        cmd.assert()
+            .code(2)
+            .stderr(predicate::str::contains(message))
+            .stderr(predicate::str::contains("Usage: utils"))
+            .stderr(predicate::str::contains("panicked").not());
+    }
+    Ok(())
+}
+
+#[test]
+fn missing_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.args(["-i", "does_not_exist.txt"]);
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "could not read does_not_exist.txt",
+    ));
+
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.args(["--config", "does_not_exist.toml", "text"]);
+    cmd.assert().code(1).stderr(predicate::str::contains(
+        "could not read does_not_exist.toml",
+    ));
+    Ok(())
+}
+
+#[test]
+fn json_output() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("utils")?;
+    cmd.args(["--format", "json", "--part", "1", "say \"hi\"\n\tbye"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::is_match(
+            r#"^\{"day":0,"part1":"say \\"hi\\"\\n\\tbye","part2":null,"timings":\{"parse_ms":[0-9.]+,"part1_ms":[0-9.]+,"part2_ms":null\}\}\n$"#,
+        )?);
+    Ok(())
+}